@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::file::FileAttachment;
+
 /// Metadata for one file in a multipart message payload.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AttachmentPayload {
@@ -16,6 +18,8 @@ pub struct AttachmentBuilder {
     filename: String,
     description: Option<String>,
     spoiler: bool,
+    data: Option<Vec<u8>>,
+    content_type: Option<String>,
 }
 
 impl AttachmentBuilder {
@@ -30,9 +34,35 @@ impl AttachmentBuilder {
             filename: name,
             description: None,
             spoiler: false,
+            data: None,
+            content_type: None,
         }
     }
 
+    /// Creates a builder that carries the file contents too, for use with
+    /// [`MessagePayload::attach`](crate::MessagePayload::attach), which assigns the `id`.
+    pub fn from_bytes(filename: impl Into<String>, data: Vec<u8>) -> Self {
+        Self::new(0, filename).bytes(data)
+    }
+
+    /// Sets the file contents uploaded alongside this metadata.
+    pub fn bytes(mut self, data: Vec<u8>) -> Self {
+        assert!(!data.is_empty(), "file data must not be empty");
+        self.data = Some(data);
+        self
+    }
+
+    /// Sets the MIME type of the uploaded part.
+    pub fn content_type(mut self, mime: impl Into<String>) -> Self {
+        self.content_type = Some(mime.into());
+        self
+    }
+
+    pub(crate) fn with_id(mut self, id: u32) -> Self {
+        self.id = id;
+        self
+    }
+
     /// Sets the visible file name.
     pub fn name(mut self, name: impl Into<String>) -> Self {
         let n = name.into();
@@ -62,6 +92,22 @@ impl AttachmentBuilder {
         self
     }
 
+    /// Builds the metadata and the matching multipart file, sharing one filename.
+    ///
+    /// Panics if no bytes were set.
+    pub fn build_with_file(self) -> (AttachmentPayload, FileAttachment) {
+        let data = self.data.expect("attachment bytes are required");
+        let mut file = FileAttachment::new(self.filename.clone(), data);
+        file.content_type = self.content_type;
+        file.description = self.description.clone();
+        let payload = AttachmentPayload {
+            id: self.id,
+            filename: self.filename,
+            description: self.description,
+        };
+        (payload, file)
+    }
+
     /// Builds final serializable attachment metadata.
     pub fn build(self) -> AttachmentPayload {
         AttachmentPayload {
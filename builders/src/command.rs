@@ -0,0 +1,89 @@
+use fluxer_types::{ApiApplicationCommandOption, ApplicationCommandCreateRequest};
+use fluxer_util::Permissions;
+
+const NAME_MAX: usize = 32;
+const DESCRIPTION_MAX: usize = 100;
+const OPTIONS_MAX: usize = 25;
+const CHAT_INPUT: u8 = 1;
+
+#[derive(Debug, Clone)]
+pub struct ApplicationCommandBuilder {
+    data: ApplicationCommandCreateRequest,
+}
+
+impl ApplicationCommandBuilder {
+    pub fn new(name: impl Into<String>, description: impl Into<String>) -> Self {
+        let name = name.into();
+        let description = description.into();
+        assert!(
+            !name.is_empty() && name.chars().count() <= NAME_MAX,
+            "command name must be 1-{NAME_MAX} characters"
+        );
+        assert!(
+            description.chars().count() <= DESCRIPTION_MAX,
+            "command description must be <= {DESCRIPTION_MAX} characters"
+        );
+        Self {
+            data: ApplicationCommandCreateRequest {
+                name,
+                description,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// The command type: 1 for slash commands (default), 2 for user and 3 for message commands.
+    pub fn kind(mut self, kind: u8) -> Self {
+        self.data.kind = Some(kind);
+        self
+    }
+
+    pub fn options(mut self, options: Vec<ApiApplicationCommandOption>) -> Self {
+        assert!(
+            options.len() <= OPTIONS_MAX,
+            "options must be <= {OPTIONS_MAX}"
+        );
+        self.data.options = Some(options);
+        self
+    }
+
+    pub fn add_option(mut self, option: ApiApplicationCommandOption) -> Self {
+        let list = self.data.options.get_or_insert_with(Vec::new);
+        assert!(list.len() < OPTIONS_MAX, "options must be <= {OPTIONS_MAX}");
+        list.push(option);
+        self
+    }
+
+    pub fn default_member_permissions(mut self, permissions: Permissions) -> Self {
+        self.data.default_member_permissions =
+            Some(fluxer_util::permissions_to_string(permissions));
+        self
+    }
+
+    pub fn dm_permission(mut self, allowed: bool) -> Self {
+        self.data.dm_permission = Some(allowed);
+        self
+    }
+
+    pub fn nsfw(mut self, nsfw: bool) -> Self {
+        self.data.nsfw = Some(nsfw);
+        self
+    }
+
+    pub fn build(self) -> ApplicationCommandCreateRequest {
+        if self.data.kind.unwrap_or(CHAT_INPUT) == CHAT_INPUT {
+            assert!(
+                !self.data.description.is_empty(),
+                "slash commands need a description"
+            );
+            assert!(
+                self.data
+                    .name
+                    .chars()
+                    .all(|c| !c.is_uppercase() && !c.is_whitespace()),
+                "slash command names must be lowercase without spaces"
+            );
+        }
+        self.data
+    }
+}
@@ -0,0 +1,475 @@
+use fluxer_types::{InteractionCallbackData, InteractionCallbackType, InteractionResponse};
+use serde_json::{Map, Value, json};
+
+pub const ACTION_ROWS_MAX: usize = 5;
+const ROW_COMPONENTS_MAX: usize = 5;
+const SELECT_OPTIONS_MAX: usize = 25;
+const CUSTOM_ID_MAX: usize = 100;
+const LABEL_MAX: usize = 80;
+
+const ACTION_ROW: u8 = 1;
+const BUTTON: u8 = 2;
+const STRING_SELECT: u8 = 3;
+const TEXT_INPUT: u8 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ButtonStyle {
+    Primary = 1,
+    Secondary = 2,
+    Success = 3,
+    Danger = 4,
+    Link = 5,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextInputStyle {
+    Short = 1,
+    Paragraph = 2,
+}
+
+#[derive(Debug, Clone)]
+pub struct Button {
+    style: ButtonStyle,
+    label: Option<String>,
+    custom_id: Option<String>,
+    url: Option<String>,
+    emoji: Option<Value>,
+    disabled: bool,
+}
+
+impl Button {
+    /// A non-link button that sends an interaction with `custom_id` when clicked.
+    pub fn new(style: ButtonStyle, custom_id: impl Into<String>) -> Self {
+        assert!(
+            style != ButtonStyle::Link,
+            "use Button::link for link buttons"
+        );
+        let custom_id = custom_id.into();
+        assert_custom_id(&custom_id);
+        Self {
+            style,
+            label: None,
+            custom_id: Some(custom_id),
+            url: None,
+            emoji: None,
+            disabled: false,
+        }
+    }
+
+    pub fn link(url: impl Into<String>) -> Self {
+        Self {
+            style: ButtonStyle::Link,
+            label: None,
+            custom_id: None,
+            url: Some(url.into()),
+            emoji: None,
+            disabled: false,
+        }
+    }
+
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        let label = label.into();
+        assert!(
+            label.chars().count() <= LABEL_MAX,
+            "button label must be <= {LABEL_MAX} characters"
+        );
+        self.label = Some(label);
+        self
+    }
+
+    pub fn emoji(mut self, name: impl Into<String>) -> Self {
+        self.emoji = Some(json!({ "name": name.into() }));
+        self
+    }
+
+    pub fn custom_emoji(
+        mut self,
+        name: impl Into<String>,
+        id: impl Into<String>,
+        animated: bool,
+    ) -> Self {
+        self.emoji = Some(json!({ "name": name.into(), "id": id.into(), "animated": animated }));
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    pub fn to_value(&self) -> Value {
+        assert!(
+            self.label.is_some() || self.emoji.is_some(),
+            "buttons need a label or an emoji"
+        );
+        let mut obj = Map::new();
+        obj.insert("type".into(), json!(BUTTON));
+        obj.insert("style".into(), json!(self.style as u8));
+        insert_opt(&mut obj, "label", self.label.as_ref().map(|l| json!(l)));
+        insert_opt(
+            &mut obj,
+            "custom_id",
+            self.custom_id.as_ref().map(|c| json!(c)),
+        );
+        insert_opt(&mut obj, "url", self.url.as_ref().map(|u| json!(u)));
+        insert_opt(&mut obj, "emoji", self.emoji.clone());
+        if self.disabled {
+            obj.insert("disabled".into(), json!(true));
+        }
+        Value::Object(obj)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SelectOption {
+    label: String,
+    value: String,
+    description: Option<String>,
+    emoji: Option<Value>,
+    default: bool,
+}
+
+impl SelectOption {
+    pub fn new(label: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            value: value.into(),
+            description: None,
+            emoji: None,
+            default: false,
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn emoji(mut self, name: impl Into<String>) -> Self {
+        self.emoji = Some(json!({ "name": name.into() }));
+        self
+    }
+
+    pub fn default_selected(mut self, default: bool) -> Self {
+        self.default = default;
+        self
+    }
+
+    fn to_value(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert("label".into(), json!(self.label));
+        obj.insert("value".into(), json!(self.value));
+        insert_opt(
+            &mut obj,
+            "description",
+            self.description.as_ref().map(|d| json!(d)),
+        );
+        insert_opt(&mut obj, "emoji", self.emoji.clone());
+        if self.default {
+            obj.insert("default".into(), json!(true));
+        }
+        Value::Object(obj)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SelectMenu {
+    custom_id: String,
+    options: Vec<SelectOption>,
+    placeholder: Option<String>,
+    min_values: Option<u8>,
+    max_values: Option<u8>,
+    disabled: bool,
+}
+
+impl SelectMenu {
+    pub fn new(custom_id: impl Into<String>) -> Self {
+        let custom_id = custom_id.into();
+        assert_custom_id(&custom_id);
+        Self {
+            custom_id,
+            options: Vec::new(),
+            placeholder: None,
+            min_values: None,
+            max_values: None,
+            disabled: false,
+        }
+    }
+
+    pub fn options(mut self, options: Vec<SelectOption>) -> Self {
+        assert!(
+            options.len() <= SELECT_OPTIONS_MAX,
+            "select options must be <= {SELECT_OPTIONS_MAX}"
+        );
+        self.options = options;
+        self
+    }
+
+    pub fn add_option(mut self, option: SelectOption) -> Self {
+        assert!(
+            self.options.len() < SELECT_OPTIONS_MAX,
+            "select options must be <= {SELECT_OPTIONS_MAX}"
+        );
+        self.options.push(option);
+        self
+    }
+
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = Some(placeholder.into());
+        self
+    }
+
+    pub fn min_values(mut self, min: u8) -> Self {
+        self.min_values = Some(min);
+        self
+    }
+
+    pub fn max_values(mut self, max: u8) -> Self {
+        self.max_values = Some(max);
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    pub fn to_value(&self) -> Value {
+        assert!(
+            !self.options.is_empty(),
+            "select menus need at least one option"
+        );
+        let mut obj = Map::new();
+        obj.insert("type".into(), json!(STRING_SELECT));
+        obj.insert("custom_id".into(), json!(self.custom_id));
+        obj.insert(
+            "options".into(),
+            Value::Array(self.options.iter().map(SelectOption::to_value).collect()),
+        );
+        insert_opt(
+            &mut obj,
+            "placeholder",
+            self.placeholder.as_ref().map(|p| json!(p)),
+        );
+        insert_opt(&mut obj, "min_values", self.min_values.map(|v| json!(v)));
+        insert_opt(&mut obj, "max_values", self.max_values.map(|v| json!(v)));
+        if self.disabled {
+            obj.insert("disabled".into(), json!(true));
+        }
+        Value::Object(obj)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TextInput {
+    custom_id: String,
+    label: String,
+    style: TextInputStyle,
+    min_length: Option<u16>,
+    max_length: Option<u16>,
+    required: Option<bool>,
+    value: Option<String>,
+    placeholder: Option<String>,
+}
+
+impl TextInput {
+    pub fn new(
+        custom_id: impl Into<String>,
+        label: impl Into<String>,
+        style: TextInputStyle,
+    ) -> Self {
+        let custom_id = custom_id.into();
+        assert_custom_id(&custom_id);
+        Self {
+            custom_id,
+            label: label.into(),
+            style,
+            min_length: None,
+            max_length: None,
+            required: None,
+            value: None,
+            placeholder: None,
+        }
+    }
+
+    pub fn min_length(mut self, min: u16) -> Self {
+        self.min_length = Some(min);
+        self
+    }
+
+    pub fn max_length(mut self, max: u16) -> Self {
+        self.max_length = Some(max);
+        self
+    }
+
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = Some(required);
+        self
+    }
+
+    pub fn value(mut self, value: impl Into<String>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = Some(placeholder.into());
+        self
+    }
+
+    pub fn to_value(&self) -> Value {
+        let mut obj = Map::new();
+        obj.insert("type".into(), json!(TEXT_INPUT));
+        obj.insert("custom_id".into(), json!(self.custom_id));
+        obj.insert("label".into(), json!(self.label));
+        obj.insert("style".into(), json!(self.style as u8));
+        insert_opt(&mut obj, "min_length", self.min_length.map(|v| json!(v)));
+        insert_opt(&mut obj, "max_length", self.max_length.map(|v| json!(v)));
+        insert_opt(&mut obj, "required", self.required.map(|v| json!(v)));
+        insert_opt(&mut obj, "value", self.value.as_ref().map(|v| json!(v)));
+        insert_opt(
+            &mut obj,
+            "placeholder",
+            self.placeholder.as_ref().map(|p| json!(p)),
+        );
+        Value::Object(obj)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Component {
+    Button(Button),
+    SelectMenu(SelectMenu),
+    TextInput(TextInput),
+}
+
+impl Component {
+    /// How many of a row's five slots this component takes up.
+    fn width(&self) -> usize {
+        match self {
+            Self::Button(_) => 1,
+            Self::SelectMenu(_) | Self::TextInput(_) => ROW_COMPONENTS_MAX,
+        }
+    }
+
+    pub fn to_value(&self) -> Value {
+        match self {
+            Self::Button(b) => b.to_value(),
+            Self::SelectMenu(s) => s.to_value(),
+            Self::TextInput(t) => t.to_value(),
+        }
+    }
+}
+
+impl From<Button> for Component {
+    fn from(b: Button) -> Self {
+        Self::Button(b)
+    }
+}
+
+impl From<SelectMenu> for Component {
+    fn from(s: SelectMenu) -> Self {
+        Self::SelectMenu(s)
+    }
+}
+
+impl From<TextInput> for Component {
+    fn from(t: TextInput) -> Self {
+        Self::TextInput(t)
+    }
+}
+
+/// A row of up to five buttons, or a single select menu or text input.
+#[derive(Debug, Clone, Default)]
+pub struct ActionRow {
+    components: Vec<Component>,
+}
+
+impl ActionRow {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(mut self, component: impl Into<Component>) -> Self {
+        let component = component.into();
+        let used: usize = self.components.iter().map(Component::width).sum();
+        assert!(
+            used + component.width() <= ROW_COMPONENTS_MAX,
+            "an action row holds up to {ROW_COMPONENTS_MAX} buttons or a single select menu or text input"
+        );
+        self.components.push(component);
+        self
+    }
+
+    pub fn to_value(&self) -> Value {
+        assert!(!self.components.is_empty(), "action rows cannot be empty");
+        json!({
+            "type": ACTION_ROW,
+            "components": self.components.iter().map(Component::to_value).collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// Serializes rows for a message or interaction response, enforcing the five-row limit.
+pub fn action_rows(rows: &[ActionRow]) -> Vec<Value> {
+    assert!(
+        rows.len() <= ACTION_ROWS_MAX,
+        "messages hold up to {ACTION_ROWS_MAX} action rows"
+    );
+    rows.iter().map(ActionRow::to_value).collect()
+}
+
+#[derive(Debug, Clone)]
+pub struct ModalBuilder {
+    custom_id: String,
+    title: String,
+    rows: Vec<ActionRow>,
+}
+
+impl ModalBuilder {
+    pub fn new(custom_id: impl Into<String>, title: impl Into<String>) -> Self {
+        let custom_id = custom_id.into();
+        assert_custom_id(&custom_id);
+        Self {
+            custom_id,
+            title: title.into(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Adds a text input on its own row.
+    pub fn text_input(mut self, input: TextInput) -> Self {
+        assert!(
+            self.rows.len() < ACTION_ROWS_MAX,
+            "modals hold up to {ACTION_ROWS_MAX} rows"
+        );
+        self.rows.push(ActionRow::new().push(input));
+        self
+    }
+
+    pub fn build(self) -> InteractionResponse {
+        assert!(!self.rows.is_empty(), "modals need at least one text input");
+        InteractionResponse {
+            kind: InteractionCallbackType::Modal,
+            data: Some(InteractionCallbackData {
+                custom_id: Some(self.custom_id),
+                title: Some(self.title),
+                components: Some(action_rows(&self.rows)),
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+fn assert_custom_id(custom_id: &str) {
+    assert!(
+        !custom_id.is_empty() && custom_id.len() <= CUSTOM_ID_MAX,
+        "custom_id must be 1-{CUSTOM_ID_MAX} characters"
+    );
+}
+
+fn insert_opt(obj: &mut Map<String, Value>, key: &str, value: Option<Value>) {
+    if let Some(value) = value {
+        obj.insert(key.to_string(), value);
+    }
+}
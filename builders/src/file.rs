@@ -51,24 +51,17 @@ impl FileAttachment {
 }
 
 pub fn build_multipart_form(payload: &MessagePayloadData, files: &[FileAttachment]) -> Form {
-    let attachment_meta: Vec<AttachmentPayload> = files
-        .iter()
-        .enumerate()
-        .map(|(i, f)| AttachmentPayload {
-            id: i as u32,
-            filename: f.filename(),
-            description: f.description.clone(),
-        })
-        .collect();
-
     let mut payload_clone = payload.clone();
-    if payload_clone.attachments.is_none()
-        || payload_clone
-            .attachments
-            .as_ref()
-            .is_some_and(|a| a.is_empty())
-    {
-        payload_clone.attachments = Some(attachment_meta);
+    // Files without explicit metadata get a descriptor derived from the file itself.
+    for (i, f) in files.iter().enumerate() {
+        let attachments = payload_clone.attachments.get_or_insert_with(Vec::new);
+        if !attachments.iter().any(|a| a.id == i as u32) {
+            attachments.push(AttachmentPayload {
+                id: i as u32,
+                filename: f.filename(),
+                description: f.description.clone(),
+            });
+        }
     }
 
     let json_str =
@@ -0,0 +1,283 @@
+use std::collections::HashSet;
+
+use fluxer_types::{
+    ApiChannelOverwrite, ChannelType, CreateRoleBody, GuildCreateChannel, GuildCreateRequest,
+    GuildCreateRole, OverwriteType,
+};
+use fluxer_util::Permissions;
+
+/// Placeholder id of the `@everyone` role in a [`GuildCreateRequest`].
+pub const EVERYONE_PLACEHOLDER: &str = "0";
+
+const NAME_MAX: usize = 100;
+
+/// One channel of a [`GuildLayoutBuilder`]. Parent and overwrite ids are placeholders returned
+/// by the layout builder.
+#[derive(Debug, Clone)]
+pub struct LayoutChannel {
+    data: GuildCreateChannel,
+}
+
+impl LayoutChannel {
+    pub fn new(name: impl Into<String>, kind: ChannelType) -> Self {
+        let name = name.into();
+        assert!(
+            !name.is_empty() && name.chars().count() <= NAME_MAX,
+            "channel name must be 1-{NAME_MAX} characters"
+        );
+        Self {
+            data: GuildCreateChannel {
+                id: String::new(),
+                name,
+                kind,
+                parent_id: None,
+                topic: None,
+                permission_overwrites: Vec::new(),
+            },
+        }
+    }
+
+    pub fn text(name: impl Into<String>) -> Self {
+        Self::new(name, ChannelType::GuildText)
+    }
+
+    pub fn voice(name: impl Into<String>) -> Self {
+        Self::new(name, ChannelType::GuildVoice)
+    }
+
+    pub fn parent(mut self, category: impl Into<String>) -> Self {
+        self.data.parent_id = Some(category.into());
+        self
+    }
+
+    pub fn topic(mut self, topic: impl Into<String>) -> Self {
+        self.data.topic = Some(topic.into());
+        self
+    }
+
+    /// Adds a role overwrite; `role` is a placeholder from [`GuildLayoutBuilder::role`] or
+    /// [`EVERYONE_PLACEHOLDER`].
+    pub fn role_overwrite(
+        mut self,
+        role: impl Into<String>,
+        allow: Permissions,
+        deny: Permissions,
+    ) -> Self {
+        self.data.permission_overwrites.push(ApiChannelOverwrite {
+            id: role.into(),
+            kind: OverwriteType::Role,
+            allow: fluxer_util::permissions_to_string(allow),
+            deny: fluxer_util::permissions_to_string(deny),
+        });
+        self
+    }
+}
+
+/// Accumulates roles and channels for guild creation, handing out placeholder ids so channels
+/// can reference categories and roles that don't exist yet.
+#[derive(Debug, Clone)]
+pub struct GuildLayoutBuilder {
+    data: GuildCreateRequest,
+    next_id: u64,
+}
+
+impl GuildLayoutBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        let name = name.into();
+        assert!(
+            !name.is_empty() && name.chars().count() <= NAME_MAX,
+            "guild name must be 1-{NAME_MAX} characters"
+        );
+        Self {
+            data: GuildCreateRequest {
+                name,
+                ..Default::default()
+            },
+            next_id: 1,
+        }
+    }
+
+    /// Guild icon as a `data:` URI.
+    pub fn icon(&mut self, data_uri: impl Into<String>) -> &mut Self {
+        self.data.icon = Some(data_uri.into());
+        self
+    }
+
+    /// Sets the permissions of `@everyone`.
+    pub fn everyone(&mut self, permissions: Permissions) -> &mut Self {
+        self.data.roles.retain(|r| r.id != EVERYONE_PLACEHOLDER);
+        self.data.roles.insert(
+            0,
+            GuildCreateRole {
+                id: EVERYONE_PLACEHOLDER.to_string(),
+                role: CreateRoleBody {
+                    permissions: Some(fluxer_util::permissions_to_string(permissions)),
+                    ..Default::default()
+                },
+            },
+        );
+        self
+    }
+
+    /// Adds a role and returns its placeholder id.
+    pub fn role(&mut self, role: CreateRoleBody) -> String {
+        let id = self.placeholder();
+        self.data.roles.push(GuildCreateRole {
+            id: id.clone(),
+            role,
+        });
+        id
+    }
+
+    /// Adds a category and returns its placeholder id.
+    pub fn category(&mut self, name: impl Into<String>) -> String {
+        self.channel(LayoutChannel::new(name, ChannelType::GuildCategory))
+    }
+
+    /// Adds a channel and returns its placeholder id.
+    pub fn channel(&mut self, channel: LayoutChannel) -> String {
+        let id = self.placeholder();
+        let mut data = channel.data;
+        data.id = id.clone();
+        self.data.channels.push(data);
+        id
+    }
+
+    pub fn system_channel(&mut self, channel: impl Into<String>) -> &mut Self {
+        self.data.system_channel_id = Some(channel.into());
+        self
+    }
+
+    /// Builds the request body.
+    ///
+    /// # Panics
+    ///
+    /// If a channel's parent is not a category of this layout, an overwrite names an undefined
+    /// role, or the system channel is undefined.
+    pub fn build(&self) -> GuildCreateRequest {
+        let roles: HashSet<&str> = self
+            .data
+            .roles
+            .iter()
+            .map(|r| r.id.as_str())
+            .chain([EVERYONE_PLACEHOLDER])
+            .collect();
+        let categories: HashSet<&str> = self
+            .data
+            .channels
+            .iter()
+            .filter(|c| c.kind == ChannelType::GuildCategory)
+            .map(|c| c.id.as_str())
+            .collect();
+
+        for channel in &self.data.channels {
+            if let Some(parent) = &channel.parent_id {
+                assert!(
+                    channel.kind != ChannelType::GuildCategory,
+                    "category {:?} cannot have a parent",
+                    channel.name
+                );
+                assert!(
+                    categories.contains(parent.as_str()),
+                    "channel {:?} references undefined category {parent:?}",
+                    channel.name
+                );
+            }
+            for overwrite in &channel.permission_overwrites {
+                assert!(
+                    roles.contains(overwrite.id.as_str()),
+                    "channel {:?} references undefined role {:?}",
+                    channel.name,
+                    overwrite.id
+                );
+            }
+        }
+        if let Some(system) = &self.data.system_channel_id {
+            assert!(
+                self.data.channels.iter().any(|c| &c.id == system),
+                "system channel {system:?} is not defined"
+            );
+        }
+
+        self.data.clone()
+    }
+
+    fn placeholder(&mut self) -> String {
+        let id = self.next_id.to_string();
+        self.next_id += 1;
+        id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn links_channels_to_categories_and_roles() {
+        let mut layout = GuildLayoutBuilder::new("Guild");
+        let moderators = layout.role(CreateRoleBody {
+            name: Some("Moderators".to_string()),
+            ..Default::default()
+        });
+        let category = layout.category("Staff");
+        let channel = layout.channel(
+            LayoutChannel::text("mod-chat")
+                .parent(&category)
+                .role_overwrite(
+                    EVERYONE_PLACEHOLDER,
+                    Permissions::empty(),
+                    Permissions::VIEW_CHANNEL,
+                )
+                .role_overwrite(&moderators, Permissions::VIEW_CHANNEL, Permissions::empty()),
+        );
+        layout.system_channel(&channel);
+
+        let body = serde_json::to_value(layout.build()).unwrap();
+        let view = Permissions::VIEW_CHANNEL.bits().to_string();
+        assert_eq!(
+            body,
+            json!({
+                "name": "Guild",
+                "roles": [{ "id": "1", "name": "Moderators" }],
+                "channels": [
+                    { "id": "2", "name": "Staff", "type": 4 },
+                    {
+                        "id": "3",
+                        "name": "mod-chat",
+                        "type": 0,
+                        "parent_id": "2",
+                        "permission_overwrites": [
+                            { "id": "0", "type": 0, "allow": "0", "deny": view },
+                            { "id": "1", "type": 0, "allow": view, "deny": "0" },
+                        ],
+                    },
+                ],
+                "system_channel_id": "3",
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "undefined role")]
+    fn rejects_an_undefined_role() {
+        let mut layout = GuildLayoutBuilder::new("Guild");
+        layout.channel(LayoutChannel::text("general").role_overwrite(
+            "9",
+            Permissions::VIEW_CHANNEL,
+            Permissions::empty(),
+        ));
+        layout.build();
+    }
+
+    #[test]
+    #[should_panic(expected = "undefined category")]
+    fn rejects_a_parent_that_is_not_a_category() {
+        let mut layout = GuildLayoutBuilder::new("Guild");
+        let text = layout.channel(LayoutChannel::text("general"));
+        layout.channel(LayoutChannel::text("other").parent(text));
+        layout.build();
+    }
+}
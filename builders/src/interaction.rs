@@ -0,0 +1,226 @@
+use std::fmt;
+
+use fluxer_types::{
+    ApiEmbed, InteractionCallbackData, InteractionCallbackType, InteractionResponse,
+};
+use fluxer_util::MessageFlags;
+use serde_json::Value;
+
+const CONTENT_MAX: usize = 2000;
+const EMBEDS_MAX: usize = 10;
+/// Top-level component types that only exist in the components-v2 layout system.
+const V2_COMPONENT_TYPES: [u64; 7] = [9, 10, 11, 12, 13, 14, 17];
+
+#[derive(Debug, Clone)]
+pub struct InteractionResponseBuilder {
+    kind: InteractionCallbackType,
+    data: InteractionCallbackData,
+    flags: MessageFlags,
+}
+
+impl InteractionResponseBuilder {
+    pub fn new(kind: InteractionCallbackType) -> Self {
+        Self {
+            kind,
+            data: InteractionCallbackData::default(),
+            flags: MessageFlags::empty(),
+        }
+    }
+
+    pub fn message() -> Self {
+        Self::new(InteractionCallbackType::ChannelMessageWithSource)
+    }
+
+    pub fn deferred() -> Self {
+        Self::new(InteractionCallbackType::DeferredChannelMessageWithSource)
+    }
+
+    pub fn update() -> Self {
+        Self::new(InteractionCallbackType::UpdateMessage)
+    }
+
+    pub fn content(mut self, content: impl Into<String>) -> Self {
+        let c = content.into();
+        assert!(
+            c.len() <= CONTENT_MAX,
+            "content must be <= {CONTENT_MAX} characters"
+        );
+        self.data.content = Some(c);
+        self
+    }
+
+    pub fn embeds(mut self, embeds: Vec<ApiEmbed>) -> Self {
+        assert!(embeds.len() <= EMBEDS_MAX, "embeds must be <= {EMBEDS_MAX}");
+        self.data.embeds = Some(embeds);
+        self
+    }
+
+    pub fn add_embed(mut self, embed: ApiEmbed) -> Self {
+        let list = self.data.embeds.get_or_insert_with(Vec::new);
+        assert!(list.len() < EMBEDS_MAX, "embeds must be <= {EMBEDS_MAX}");
+        list.push(embed);
+        self
+    }
+
+    pub fn components(mut self, components: Vec<Value>) -> Self {
+        self.data.components = Some(components);
+        self
+    }
+
+    pub fn action_rows(mut self, rows: &[crate::component::ActionRow]) -> Self {
+        self.data.components = Some(crate::component::action_rows(rows));
+        self
+    }
+
+    pub fn tts(mut self, tts: bool) -> Self {
+        self.data.tts = Some(tts);
+        self
+    }
+
+    pub fn ephemeral(mut self, ephemeral: bool) -> Self {
+        self.flags.set(MessageFlags::EPHEMERAL, ephemeral);
+        self
+    }
+
+    pub fn suppress_embeds(mut self, suppress: bool) -> Self {
+        self.flags.set(MessageFlags::SUPPRESS_EMBEDS, suppress);
+        self
+    }
+
+    pub fn flags(mut self, flags: MessageFlags) -> Self {
+        assert!(
+            MessageFlags::INTERACTION_RESPONSE.contains(flags),
+            "flags {flags:?} cannot be set on an interaction response"
+        );
+        self.flags |= flags;
+        self
+    }
+
+    /// Like [`try_build`](Self::try_build), but panics if the response is invalid.
+    pub fn build(self) -> InteractionResponse {
+        match self.try_build() {
+            Ok(response) => response,
+            Err(err) => panic!("{err}"),
+        }
+    }
+
+    /// Builds the response, setting `IS_COMPONENTS_V2` when any top-level component is a
+    /// components-v2 type.
+    ///
+    /// Fails if the v2 flag is combined with `content` or `embeds`, which the API rejects.
+    pub fn try_build(mut self) -> Result<InteractionResponse, InteractionResponseError> {
+        if self
+            .data
+            .components
+            .as_deref()
+            .is_some_and(|c| c.iter().any(is_v2_component))
+        {
+            self.flags |= MessageFlags::IS_COMPONENTS_V2;
+        }
+        if self.flags.contains(MessageFlags::IS_COMPONENTS_V2)
+            && (self.data.content.is_some() || self.data.embeds.is_some())
+        {
+            return Err(InteractionResponseError::ComponentsV2WithContent);
+        }
+
+        if !self.flags.is_empty() {
+            self.data.flags = Some(self.flags.bits());
+        }
+        let has_data = self.data.content.is_some()
+            || self.data.embeds.is_some()
+            || self.data.components.is_some()
+            || self.data.tts.is_some()
+            || self.data.flags.is_some();
+
+        Ok(InteractionResponse {
+            kind: self.kind,
+            data: has_data.then_some(self.data),
+        })
+    }
+}
+
+/// Why [`InteractionResponseBuilder::try_build`] rejected a response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InteractionResponseError {
+    /// `IS_COMPONENTS_V2` was set, or implied by a v2 component, alongside content or embeds.
+    ComponentsV2WithContent,
+}
+
+impl fmt::Display for InteractionResponseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ComponentsV2WithContent => {
+                f.write_str("IS_COMPONENTS_V2 responses cannot have content or embeds")
+            }
+        }
+    }
+}
+
+impl std::error::Error for InteractionResponseError {}
+
+fn is_v2_component(component: &Value) -> bool {
+    component
+        .get("type")
+        .and_then(Value::as_u64)
+        .is_some_and(|t| V2_COMPONENT_TYPES.contains(&t))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn container() -> Value {
+        json!({ "type": 17, "components": [{ "type": 10, "content": "Hello" }] })
+    }
+
+    #[test]
+    fn v2_components_set_the_flag() {
+        let response = InteractionResponseBuilder::message()
+            .components(vec![container()])
+            .ephemeral(true)
+            .build();
+
+        let flags = MessageFlags::from_bits_retain(response.data.unwrap().flags.unwrap());
+        assert_eq!(
+            flags,
+            MessageFlags::IS_COMPONENTS_V2 | MessageFlags::EPHEMERAL
+        );
+    }
+
+    #[test]
+    fn action_rows_leave_the_flag_unset() {
+        let row = json!({ "type": 1, "components": [{ "type": 2, "style": 1, "custom_id": "a" }] });
+        let response = InteractionResponseBuilder::message()
+            .content("Pick one")
+            .components(vec![row])
+            .build();
+
+        assert_eq!(response.data.unwrap().flags, None);
+    }
+
+    #[test]
+    fn v2_components_reject_content_and_embeds() {
+        let with_content = InteractionResponseBuilder::message()
+            .content("Hello")
+            .components(vec![container()])
+            .try_build();
+        assert_eq!(
+            with_content.unwrap_err(),
+            InteractionResponseError::ComponentsV2WithContent
+        );
+
+        let with_embed = InteractionResponseBuilder::message()
+            .flags(MessageFlags::IS_COMPONENTS_V2)
+            .add_embed(crate::EmbedBuilder::new().title("Hi").build())
+            .try_build();
+        assert!(with_embed.is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot be set on an interaction response")]
+    fn rejects_flags_interaction_responses_cannot_carry() {
+        let _ = InteractionResponseBuilder::message().flags(MessageFlags::CROSSPOSTED);
+    }
+}
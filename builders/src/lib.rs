@@ -1,9 +1,21 @@
 pub mod attachment;
+pub mod command;
+pub mod component;
 pub mod embed;
 pub mod file;
+pub mod guild;
+pub mod interaction;
 pub mod message;
+pub mod poll;
+pub mod webhook;
 
 pub use attachment::*;
+pub use command::*;
+pub use component::*;
 pub use embed::*;
 pub use file::*;
+pub use guild::*;
+pub use interaction::*;
 pub use message::*;
+pub use poll::*;
+pub use webhook::*;
@@ -1,5 +1,7 @@
-use fluxer_types::{ApiEmbed, ApiMessageReference};
+use fluxer_types::{ApiEmbed, ApiMessageReference, Patch, PollCreateRequest};
+use fluxer_util::MessageFlags;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use crate::attachment::AttachmentPayload;
 use crate::embed::EmbedBuilder;
@@ -22,6 +24,12 @@ pub struct MessagePayloadData {
     pub tts: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub flags: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub components: Patch<Vec<Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub poll: Option<PollCreateRequest>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -82,6 +90,28 @@ impl MessagePayload {
         self
     }
 
+    pub fn components(mut self, components: Vec<Value>) -> Self {
+        self.data.components = Patch::Value(components);
+        self
+    }
+
+    pub fn action_rows(mut self, rows: &[crate::component::ActionRow]) -> Self {
+        self.data.components = Patch::Value(crate::component::action_rows(rows));
+        self
+    }
+
+    /// Attaches a poll; only valid when creating a message.
+    pub fn poll(mut self, poll: PollCreateRequest) -> Self {
+        self.data.poll = Some(poll);
+        self
+    }
+
+    /// Sends `components: []` so an edit removes every component from the message.
+    pub fn clear_components(mut self) -> Self {
+        self.data.components = Patch::Value(Vec::new());
+        self
+    }
+
     pub fn tts(mut self, tts: bool) -> Self {
         self.data.tts = Some(tts);
         self
@@ -92,6 +122,31 @@ impl MessagePayload {
         self
     }
 
+    pub fn suppress_embeds(self, suppress: bool) -> Self {
+        self.set_flag(MessageFlags::SUPPRESS_EMBEDS, suppress)
+    }
+
+    /// Sends the message without push or desktop notifications.
+    pub fn silent(self, silent: bool) -> Self {
+        self.set_flag(MessageFlags::SUPPRESS_NOTIFICATIONS, silent)
+    }
+
+    fn set_flag(mut self, flag: MessageFlags, value: bool) -> Self {
+        let mut flags = MessageFlags::from_bits_retain(self.data.flags.unwrap_or(0));
+        flags.set(flag, value);
+        self.data.flags = Some(flags.bits());
+        self
+    }
+
+    pub fn nonce(mut self, nonce: impl Into<String>) -> Self {
+        self.data.nonce = Some(nonce.into());
+        self
+    }
+
+    pub fn auto_nonce(self) -> Self {
+        self.nonce(fluxer_util::Nonce::generate())
+    }
+
     pub fn build(self) -> MessagePayloadData {
         self.data
     }
@@ -114,6 +169,18 @@ impl MessagePayload {
         self
     }
 
+    /// Adds a file and its metadata together, assigning the `id` from the file's position.
+    pub fn attach(mut self, attachment: crate::attachment::AttachmentBuilder) -> Self {
+        let id = self.files.len() as u32;
+        let (meta, file) = attachment.with_id(id).build_with_file();
+        self.data
+            .attachments
+            .get_or_insert_with(Vec::new)
+            .push(meta);
+        self.files.push(file);
+        self
+    }
+
     pub fn attach_files(mut self, files: impl IntoIterator<Item = FileAttachment>) -> Self {
         self.files.extend(files);
         self
@@ -123,3 +190,76 @@ impl MessagePayload {
         Self::new().content(content)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn clear_components_sends_an_empty_list() {
+        let data = MessagePayload::new().clear_components().build();
+        assert_eq!(
+            serde_json::to_value(data).unwrap(),
+            json!({ "components": [] })
+        );
+    }
+
+    #[test]
+    fn leaves_components_out_unless_set() {
+        let data = MessagePayload::from_content("hi").build();
+        assert_eq!(
+            serde_json::to_value(data).unwrap(),
+            json!({ "content": "hi" })
+        );
+    }
+
+    #[test]
+    fn edit_from_a_received_link_embed_drops_read_only_fields() {
+        let received: fluxer_types::ApiMessage = serde_json::from_value(json!({
+            "id": "2",
+            "channel_id": "1",
+            "author": { "id": "3", "username": "u", "discriminator": "0001" },
+            "type": 0,
+            "content": "https://example.com",
+            "timestamp": "2024-01-01T00:00:00.000Z",
+            "pinned": false,
+            "embeds": [{
+                "type": "link",
+                "url": "https://example.com",
+                "title": "Example",
+                "provider": { "name": "Example", "url": "https://example.com" },
+                "thumbnail": {
+                    "url": "https://example.com/a.png",
+                    "proxy_url": "https://media.test/a.png",
+                    "width": 16,
+                    "height": 16,
+                },
+                "video": {
+                    "url": "https://example.com/a.mp4",
+                    "proxy_url": "https://media.test/a.mp4",
+                },
+                "author": {
+                    "name": "Someone",
+                    "icon_url": "https://example.com/i.png",
+                    "proxy_icon_url": "https://media.test/i.png",
+                },
+            }],
+        }))
+        .unwrap();
+
+        let data = MessagePayload::new()
+            .embeds(received.embeds.unwrap())
+            .build();
+        let embed = &serde_json::to_value(data).unwrap()["embeds"][0];
+
+        assert_eq!(embed["title"], "Example");
+        assert!(embed.get("type").is_none());
+        assert!(embed.get("provider").is_none());
+        assert!(embed["thumbnail"].get("proxy_url").is_none());
+        assert_eq!(embed["thumbnail"]["url"], "https://example.com/a.png");
+        assert!(embed["video"].get("proxy_url").is_none());
+        assert!(embed["author"].get("proxy_icon_url").is_none());
+    }
+}
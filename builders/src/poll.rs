@@ -0,0 +1,83 @@
+use fluxer_types::{ApiPollAnswer, ApiPollMedia, ApiReactionEmoji, PollCreateRequest};
+
+const QUESTION_MAX: usize = 300;
+const ANSWER_MAX: usize = 55;
+const ANSWERS_MAX: usize = 10;
+const DURATION_MAX_HOURS: u32 = 768;
+const DEFAULT_DURATION_HOURS: u32 = 24;
+
+#[derive(Debug, Clone)]
+pub struct PollBuilder {
+    data: PollCreateRequest,
+}
+
+impl PollBuilder {
+    pub fn new(question: impl Into<String>) -> Self {
+        let question = question.into();
+        assert!(
+            !question.is_empty() && question.chars().count() <= QUESTION_MAX,
+            "poll question must be 1-{QUESTION_MAX} characters"
+        );
+        Self {
+            data: PollCreateRequest {
+                question: ApiPollMedia {
+                    text: Some(question),
+                    emoji: None,
+                },
+                answers: Vec::new(),
+                duration_hours: DEFAULT_DURATION_HOURS,
+                allow_multiselect: false,
+                layout_type: None,
+            },
+        }
+    }
+
+    pub fn answer(self, text: impl Into<String>) -> Self {
+        self.push_answer(text.into(), None)
+    }
+
+    pub fn answer_with_emoji(self, text: impl Into<String>, emoji: ApiReactionEmoji) -> Self {
+        self.push_answer(text.into(), Some(emoji))
+    }
+
+    pub fn duration_hours(mut self, hours: u32) -> Self {
+        assert!(
+            (1..=DURATION_MAX_HOURS).contains(&hours),
+            "poll duration must be 1-{DURATION_MAX_HOURS} hours"
+        );
+        self.data.duration_hours = hours;
+        self
+    }
+
+    pub fn allow_multiselect(mut self, allow: bool) -> Self {
+        self.data.allow_multiselect = allow;
+        self
+    }
+
+    pub fn build(self) -> PollCreateRequest {
+        assert!(
+            !self.data.answers.is_empty(),
+            "a poll needs at least one answer"
+        );
+        self.data
+    }
+
+    fn push_answer(mut self, text: String, emoji: Option<ApiReactionEmoji>) -> Self {
+        assert!(
+            !text.is_empty() && text.chars().count() <= ANSWER_MAX,
+            "poll answers must be 1-{ANSWER_MAX} characters"
+        );
+        assert!(
+            self.data.answers.len() < ANSWERS_MAX,
+            "answers must be <= {ANSWERS_MAX}"
+        );
+        self.data.answers.push(ApiPollAnswer {
+            answer_id: None,
+            poll_media: ApiPollMedia {
+                text: Some(text),
+                emoji,
+            },
+        });
+        self
+    }
+}
@@ -0,0 +1,89 @@
+use std::path::Path;
+
+use fluxer_types::WebhookCreateRequest;
+use fluxer_util::{ImageData, ImageError, ImageFormat};
+
+/// Image formats accepted for webhook avatars.
+pub const WEBHOOK_AVATAR_FORMATS: &[ImageFormat] =
+    &[ImageFormat::Png, ImageFormat::Jpeg, ImageFormat::Gif];
+
+const NAME_MAX: usize = 80;
+
+#[derive(Debug, Clone)]
+pub struct WebhookCreateBuilder {
+    data: WebhookCreateRequest,
+}
+
+impl WebhookCreateBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        let name = name.into();
+        assert!(
+            !name.is_empty() && name.chars().count() <= NAME_MAX,
+            "webhook name must be 1-{NAME_MAX} characters"
+        );
+        Self {
+            data: WebhookCreateRequest { name, avatar: None },
+        }
+    }
+
+    /// Sets the avatar from an already encoded `data:` URI.
+    pub fn avatar(mut self, data_uri: impl Into<String>) -> Self {
+        self.data.avatar = Some(data_uri.into());
+        self
+    }
+
+    pub fn avatar_image(mut self, image: ImageData) -> Result<Self, ImageError> {
+        let image = image.require(WEBHOOK_AVATAR_FORMATS)?;
+        self.data.avatar = Some(image.to_data_uri());
+        Ok(self)
+    }
+
+    pub fn avatar_from_bytes(self, bytes: impl Into<Vec<u8>>) -> Result<Self, ImageError> {
+        self.avatar_image(ImageData::from_bytes(bytes)?)
+    }
+
+    pub fn avatar_from_path(self, path: impl AsRef<Path>) -> Result<Self, ImageError> {
+        self.avatar_image(ImageData::from_path(path)?)
+    }
+
+    pub fn build(self) -> WebhookCreateRequest {
+        self.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR";
+
+    #[test]
+    fn builds_a_png_avatar_data_uri() {
+        let body = WebhookCreateBuilder::new("hook")
+            .avatar_from_bytes(PNG)
+            .unwrap()
+            .build();
+        assert_eq!(body.name, "hook");
+        assert_eq!(
+            body.avatar.as_deref(),
+            Some("data:image/png;base64,iVBORw0KGgoAAAANSUhEUg==")
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_avatar_formats() {
+        let webp = b"RIFF\x00\x00\x00\x00WEBPVP8 ";
+        assert!(matches!(
+            WebhookCreateBuilder::new("hook").avatar_from_bytes(webp.as_slice()),
+            Err(ImageError::UnsupportedFormat(ImageFormat::Webp))
+        ));
+        assert!(matches!(
+            WebhookCreateBuilder::new("hook").avatar_from_bytes(b"not an image".as_slice()),
+            Err(ImageError::UnknownFormat)
+        ));
+        assert!(matches!(
+            WebhookCreateBuilder::new("hook").avatar_from_bytes(Vec::new()),
+            Err(ImageError::Empty)
+        ));
+    }
+}
@@ -0,0 +1,330 @@
+use std::collections::HashMap;
+
+use fluxer_types::interaction::{ApiApplicationCommand, ApplicationCommandCreateRequest};
+
+#[derive(Debug, Clone, Default)]
+pub struct CommandSyncSummary {
+    pub created: Vec<String>,
+    pub updated: Vec<String>,
+    pub deleted: Vec<String>,
+    pub unchanged: Vec<String>,
+}
+
+impl CommandSyncSummary {
+    pub fn is_noop(&self) -> bool {
+        self.created.is_empty() && self.updated.is_empty() && self.deleted.is_empty()
+    }
+}
+
+pub struct ApplicationCommandManager<'a> {
+    rest: &'a fluxer_rest::Rest,
+}
+
+impl<'a> ApplicationCommandManager<'a> {
+    pub fn new(rest: &'a fluxer_rest::Rest) -> Self {
+        Self { rest }
+    }
+
+    pub async fn fetch_global(&self, app_id: &str) -> crate::Result<Vec<ApiApplicationCommand>> {
+        let data: Vec<ApiApplicationCommand> = self
+            .rest
+            .get(&fluxer_types::Routes::application_commands(app_id))
+            .await?;
+        Ok(data)
+    }
+
+    pub async fn create_global(
+        &self,
+        app_id: &str,
+        body: &ApplicationCommandCreateRequest,
+    ) -> crate::Result<ApiApplicationCommand> {
+        let data: ApiApplicationCommand = self
+            .rest
+            .post(
+                &fluxer_types::Routes::application_commands(app_id),
+                Some(body),
+            )
+            .await?;
+        Ok(data)
+    }
+
+    pub async fn edit_global(
+        &self,
+        app_id: &str,
+        command_id: &str,
+        body: &ApplicationCommandCreateRequest,
+    ) -> crate::Result<ApiApplicationCommand> {
+        let data: ApiApplicationCommand = self
+            .rest
+            .patch(
+                &fluxer_types::Routes::application_command(app_id, command_id),
+                Some(body),
+            )
+            .await?;
+        Ok(data)
+    }
+
+    pub async fn delete_global(&self, app_id: &str, command_id: &str) -> crate::Result<()> {
+        self.rest
+            .delete_route(&fluxer_types::Routes::application_command(
+                app_id, command_id,
+            ))
+            .await?;
+        Ok(())
+    }
+
+    /// Replaces every global command with `commands` in one request.
+    pub async fn bulk_overwrite_global(
+        &self,
+        app_id: &str,
+        commands: &[ApplicationCommandCreateRequest],
+    ) -> crate::Result<Vec<ApiApplicationCommand>> {
+        let data: Vec<ApiApplicationCommand> = self
+            .rest
+            .put(
+                &fluxer_types::Routes::application_commands(app_id),
+                Some(&commands),
+            )
+            .await?;
+        Ok(data)
+    }
+
+    pub async fn fetch_guild(
+        &self,
+        app_id: &str,
+        guild_id: &str,
+    ) -> crate::Result<Vec<ApiApplicationCommand>> {
+        let data: Vec<ApiApplicationCommand> = self
+            .rest
+            .get(&fluxer_types::Routes::guild_application_commands(
+                app_id, guild_id,
+            ))
+            .await?;
+        Ok(data)
+    }
+
+    pub async fn create_guild(
+        &self,
+        app_id: &str,
+        guild_id: &str,
+        body: &ApplicationCommandCreateRequest,
+    ) -> crate::Result<ApiApplicationCommand> {
+        let data: ApiApplicationCommand = self
+            .rest
+            .post(
+                &fluxer_types::Routes::guild_application_commands(app_id, guild_id),
+                Some(body),
+            )
+            .await?;
+        Ok(data)
+    }
+
+    pub async fn edit_guild(
+        &self,
+        app_id: &str,
+        guild_id: &str,
+        command_id: &str,
+        body: &ApplicationCommandCreateRequest,
+    ) -> crate::Result<ApiApplicationCommand> {
+        let data: ApiApplicationCommand = self
+            .rest
+            .patch(
+                &fluxer_types::Routes::guild_application_command(app_id, guild_id, command_id),
+                Some(body),
+            )
+            .await?;
+        Ok(data)
+    }
+
+    pub async fn delete_guild(
+        &self,
+        app_id: &str,
+        guild_id: &str,
+        command_id: &str,
+    ) -> crate::Result<()> {
+        self.rest
+            .delete_route(&fluxer_types::Routes::guild_application_command(
+                app_id, guild_id, command_id,
+            ))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn bulk_overwrite_guild(
+        &self,
+        app_id: &str,
+        guild_id: &str,
+        commands: &[ApplicationCommandCreateRequest],
+    ) -> crate::Result<Vec<ApiApplicationCommand>> {
+        let data: Vec<ApiApplicationCommand> = self
+            .rest
+            .put(
+                &fluxer_types::Routes::guild_application_commands(app_id, guild_id),
+                Some(&commands),
+            )
+            .await?;
+        Ok(data)
+    }
+
+    /// Brings the global commands in line with `desired`, keyed by name.
+    ///
+    /// Only commands whose definition differs are written, so calling this on every startup
+    /// with an unchanged set performs a single fetch and no writes.
+    pub async fn sync_global(
+        &self,
+        app_id: &str,
+        desired: Vec<ApplicationCommandCreateRequest>,
+    ) -> crate::Result<CommandSyncSummary> {
+        let mut existing: HashMap<String, ApiApplicationCommand> = self
+            .fetch_global(app_id)
+            .await?
+            .into_iter()
+            .map(|cmd| (cmd.name.clone(), cmd))
+            .collect();
+        let mut summary = CommandSyncSummary::default();
+
+        for command in &desired {
+            match existing.remove(&command.name) {
+                Some(current) if command.matches(&current) => {
+                    summary.unchanged.push(command.name.clone());
+                }
+                Some(current) => {
+                    self.edit_global(app_id, &current.id, command).await?;
+                    summary.updated.push(command.name.clone());
+                }
+                None => {
+                    self.create_global(app_id, command).await?;
+                    summary.created.push(command.name.clone());
+                }
+            }
+        }
+
+        for (name, stale) in existing {
+            self.delete_global(app_id, &stale.id).await?;
+            summary.deleted.push(name);
+        }
+
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fluxer_rest::{MockTransport, Rest, RestOptions};
+    use fluxer_types::interaction::ApiApplicationCommandOption;
+    use serde_json::json;
+
+    use super::*;
+
+    fn mock_rest() -> (Rest, MockTransport) {
+        let mock = MockTransport::new();
+        (
+            Rest::with_transport(RestOptions::default(), mock.clone()),
+            mock,
+        )
+    }
+
+    fn desired(name: &str, description: &str) -> ApplicationCommandCreateRequest {
+        ApplicationCommandCreateRequest {
+            name: name.to_string(),
+            description: description.to_string(),
+            options: Some(vec![ApiApplicationCommandOption {
+                kind: 3,
+                name: "query".to_string(),
+                description: "What to search for".to_string(),
+                required: None,
+                choices: None,
+                options: None,
+            }]),
+            ..Default::default()
+        }
+    }
+
+    /// A registered command as the API returns it, with every default spelled out.
+    fn existing(id: &str, name: &str, description: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "application_id": "1",
+            "name": name,
+            "description": description,
+            "type": 1,
+            "options": [{
+                "type": 3,
+                "name": "query",
+                "description": "What to search for",
+                "required": false,
+                "choices": [],
+                "options": [],
+            }],
+            "default_member_permissions": null,
+            "dm_permission": true,
+            "nsfw": false,
+            "version": "1",
+        })
+    }
+
+    #[tokio::test]
+    async fn unchanged_commands_cause_no_writes() {
+        let (rest, mock) = mock_rest();
+        mock.push_json(
+            200,
+            &json!([
+                existing("10", "search", "Search"),
+                existing("11", "find", "Find")
+            ]),
+        );
+
+        let summary = ApplicationCommandManager::new(&rest)
+            .sync_global(
+                "1",
+                vec![desired("search", "Search"), desired("find", "Find")],
+            )
+            .await
+            .unwrap();
+
+        assert!(summary.is_noop());
+        assert_eq!(summary.unchanged, ["search", "find"]);
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method.as_str(), "GET");
+    }
+
+    #[tokio::test]
+    async fn applies_only_the_differences() {
+        let (rest, mock) = mock_rest();
+        mock.push_json(
+            200,
+            &json!([
+                existing("10", "search", "Search"),
+                existing("11", "find", "Old description"),
+                existing("12", "stale", "Stale"),
+            ]),
+        )
+        .push_json(200, &existing("11", "find", "Find"))
+        .push_json(200, &existing("13", "new", "New"))
+        .push_response(204, Vec::new());
+
+        let summary = ApplicationCommandManager::new(&rest)
+            .sync_global(
+                "1",
+                vec![
+                    desired("search", "Search"),
+                    desired("find", "Find"),
+                    desired("new", "New"),
+                ],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(summary.unchanged, ["search"]);
+        assert_eq!(summary.updated, ["find"]);
+        assert_eq!(summary.created, ["new"]);
+        assert_eq!(summary.deleted, ["stale"]);
+        let methods: Vec<_> = mock
+            .requests()
+            .iter()
+            .map(|r| r.method.as_str().to_string())
+            .collect();
+        assert_eq!(methods, ["GET", "PATCH", "POST", "DELETE"]);
+    }
+}
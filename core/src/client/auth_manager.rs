@@ -0,0 +1,57 @@
+use fluxer_types::auth::{
+    AuthResponse, LoginRequest, MfaRequest, RegisterRequest, VerifyEmailRequest,
+};
+
+/// Account authentication for user clients. Use it with a [`Rest`](fluxer_rest::Rest) that has no
+/// token set, then pass the returned token to [`Rest::set_token`](fluxer_rest::Rest::set_token).
+pub struct AuthManager<'a> {
+    rest: &'a fluxer_rest::Rest,
+}
+
+impl<'a> AuthManager<'a> {
+    pub fn new(rest: &'a fluxer_rest::Rest) -> Self {
+        Self { rest }
+    }
+
+    /// Logs in; when the response [requires MFA](AuthResponse::requires_mfa), follow up with
+    /// [`mfa_totp`](Self::mfa_totp) using its ticket.
+    pub async fn login(&self, body: &LoginRequest) -> crate::Result<AuthResponse> {
+        let data: AuthResponse = self
+            .rest
+            .post(fluxer_types::Routes::auth_login(), Some(body))
+            .await?;
+        Ok(data)
+    }
+
+    pub async fn register(&self, body: &RegisterRequest) -> crate::Result<AuthResponse> {
+        let data: AuthResponse = self
+            .rest
+            .post(fluxer_types::Routes::auth_register(), Some(body))
+            .await?;
+        Ok(data)
+    }
+
+    pub async fn mfa_totp(&self, body: &MfaRequest) -> crate::Result<AuthResponse> {
+        let data: AuthResponse = self
+            .rest
+            .post(fluxer_types::Routes::auth_login_mfa_totp(), Some(body))
+            .await?;
+        Ok(data)
+    }
+
+    /// Invalidates the token the client is currently using.
+    pub async fn logout(&self) -> crate::Result<()> {
+        self.rest
+            .post::<serde_json::Value>(fluxer_types::Routes::auth_logout(), Option::<&()>::None)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn verify_email(&self, body: &VerifyEmailRequest) -> crate::Result<AuthResponse> {
+        let data: AuthResponse = self
+            .rest
+            .post(fluxer_types::Routes::auth_verify_email(), Some(body))
+            .await?;
+        Ok(data)
+    }
+}
@@ -1,9 +1,13 @@
 use dashmap::DashMap;
 
-use fluxer_types::channel::ApiChannel;
+use fluxer_types::channel::{
+    ApiChannel, EditPermissionsRequest, ModifyChannelRequest, StartForumThreadRequest,
+};
 use fluxer_types::message::ApiMessage;
 
 use crate::structures::channel::Channel;
+use crate::structures::guild::Guild;
+use crate::structures::typing_indicator::TypingIndicator;
 
 pub struct ChannelManager<'a> {
     cache: &'a DashMap<String, Channel>,
@@ -26,6 +30,73 @@ impl<'a> ChannelManager<'a> {
         Ok(channel)
     }
 
+    /// Fetches every channel in a guild, caching them; match on each with [`Channel::as_typed`].
+    pub async fn fetch_guild_channels(&self, guild_id: &str) -> crate::Result<Vec<Channel>> {
+        let data = Guild::from_id(guild_id).fetch_channels(self.rest).await?;
+        let channels: Vec<Channel> = data.iter().map(Channel::from_api).collect();
+        for channel in &channels {
+            self.cache.insert(channel.id.clone(), channel.clone());
+        }
+        Ok(channels)
+    }
+
+    /// Edits a channel and updates the cache with the result.
+    pub async fn modify(
+        &self,
+        id: &str,
+        body: &ModifyChannelRequest,
+        reason: Option<&str>,
+    ) -> crate::Result<Channel> {
+        let data: ApiChannel = self
+            .rest
+            .patch_with_reason(&fluxer_types::Routes::channel(id), Some(body), reason)
+            .await?;
+        let channel = Channel::from_api(&data);
+        self.cache.insert(channel.id.clone(), channel.clone());
+        Ok(channel)
+    }
+
+    pub async fn edit_permissions(
+        &self,
+        channel_id: &str,
+        overwrite_id: &str,
+        body: &EditPermissionsRequest,
+        reason: Option<&str>,
+    ) -> crate::Result<()> {
+        let _: serde_json::Value = self
+            .rest
+            .put_with_reason(
+                &fluxer_types::Routes::channel_permission(channel_id, overwrite_id),
+                Some(body),
+                reason,
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete_permission(
+        &self,
+        channel_id: &str,
+        overwrite_id: &str,
+        reason: Option<&str>,
+    ) -> crate::Result<()> {
+        self.rest
+            .delete_with_reason(
+                &fluxer_types::Routes::channel_permission(channel_id, overwrite_id),
+                reason,
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub async fn trigger_typing(&self, channel_id: &str) -> crate::Result<()> {
+        Channel::from_id(channel_id).send_typing(self.rest).await
+    }
+
+    pub fn start_typing(&self, channel_id: &str) -> TypingIndicator {
+        TypingIndicator::start(self.rest.clone(), channel_id)
+    }
+
     pub async fn resolve(&self, id: &str) -> crate::Result<Channel> {
         if let Some(ch) = self.get(id) {
             return Ok(ch);
@@ -33,6 +104,20 @@ impl<'a> ChannelManager<'a> {
         self.fetch(id).await
     }
 
+    /// Creates a post in a forum channel and caches the new thread. See
+    /// [`Channel::start_forum_thread`].
+    pub async fn start_forum_thread(
+        &self,
+        channel_id: &str,
+        body: &StartForumThreadRequest,
+    ) -> crate::Result<(Channel, Option<ApiMessage>)> {
+        let (thread, message) = Channel::from_id(channel_id)
+            .start_forum_thread(self.rest, body)
+            .await?;
+        self.cache.insert(thread.id.clone(), thread.clone());
+        Ok((thread, message))
+    }
+
     pub async fn send(
         &self,
         channel_id: &str,
@@ -61,4 +146,14 @@ impl<'a> ChannelManager<'a> {
             .await?;
         Ok(msg)
     }
+
+    /// See [`Message::add_reactions`](crate::structures::message::Message::add_reactions).
+    pub async fn add_reactions(
+        &self,
+        channel_id: &str,
+        message_id: &str,
+        emojis: &[&str],
+    ) -> crate::Result<()> {
+        crate::structures::message::add_reactions(self.rest, channel_id, message_id, emojis).await
+    }
 }
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
@@ -9,10 +9,13 @@ use tokio::sync::{RwLock, mpsc};
 use tracing::warn;
 
 use fluxer_rest::{Rest, RestOptions};
+use fluxer_types::Snowflake;
 use fluxer_types::gateway::{GatewayOpcode, GatewayPresenceUpdateSendData};
 use fluxer_types::message::ApiMessage;
-use fluxer_ws::{WebSocketManager, WebSocketManagerOptions, WsEvent};
+use fluxer_ws::{SendOverflowPolicy, WebSocketManager, WebSocketManagerOptions, WsEvent};
 
+use crate::client::{DispatchWorkerPool, MemberChunker, Resolver};
+use crate::collectors::component_collector::{ComponentAwaiter, ComponentFilter};
 use crate::collectors::message_collector::{MessageCollector, MessageCollectorOptions};
 use crate::collectors::reaction_collector::{
     CollectedReaction, ReactionCollector, ReactionCollectorOptions,
@@ -21,10 +24,13 @@ use crate::structures::channel::Channel;
 use crate::structures::client_user::ClientUser;
 use crate::structures::guild::Guild;
 use crate::structures::guild_member::GuildMember;
+use crate::structures::message_reaction::ReactionKey;
 use crate::structures::user::User;
+use crate::structures::voice_state::VoiceState;
 
 use super::event_parser;
-use super::typed_events::DispatchEvent;
+use super::handler::{HandlerHandle, Registered};
+use super::typed_events::{DispatchEvent, SessionEvent};
 #[cfg(feature = "voice")]
 use fluxer_voice::{FluxerVoiceConnection, VoiceError, VoiceManager};
 
@@ -33,12 +39,17 @@ type EventCallback = Box<dyn Fn(Value) -> Pin<Box<dyn Future<Output = ()> + Send
 type TypedEventCallback =
     Box<dyn Fn(DispatchEvent) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>;
 
+/// Messages tracked by `track_reaction_counts` when no explicit limit is set.
+pub const DEFAULT_REACTION_COUNTS_LIMIT: usize = 1_000;
+
 #[derive(Debug, Clone, Default)]
 pub struct CacheSizeLimits {
     pub guilds: Option<usize>,
     pub channels: Option<usize>,
     pub users: Option<usize>,
     pub members: Option<usize>,
+    /// Messages with aggregated reaction counts; `None` uses [`DEFAULT_REACTION_COUNTS_LIMIT`].
+    pub reaction_counts: Option<usize>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -47,8 +58,22 @@ pub struct ClientOptions {
     pub presence: Option<GatewayPresenceUpdateSendData>,
     pub rest: Option<RestOptions>,
     pub gateway_version: Option<String>,
+    pub reconnect_max_delay: Option<std::time::Duration>,
+    pub send_queue_capacity: Option<usize>,
+    pub send_overflow_policy: SendOverflowPolicy,
     pub wait_for_guilds: bool,
     pub cache: CacheSizeLimits,
+    pub track_reaction_counts: bool,
+    /// Total shards; `None` uses the count recommended by `GET /gateway/bot`.
+    pub shard_count: Option<u32>,
+    /// Shards run by this process; `None` runs all of them.
+    pub shard_ids: Option<Vec<u32>>,
+    /// Gateway payload compression; the default receives plain JSON text frames.
+    pub compression: fluxer_ws::CompressionMode,
+    /// Privileged intents enabled for the application. When set, [`Client::login`] fails with
+    /// [`Error::DisallowedIntents`](crate::Error::DisallowedIntents) before connecting if
+    /// `intents` asks for any other privileged intent.
+    pub privileged_intents_allowed: Option<fluxer_util::GatewayIntents>,
 }
 
 pub struct Client {
@@ -57,71 +82,155 @@ pub struct Client {
     pub channels: DashMap<String, Channel>,
     pub users: DashMap<String, User>,
     pub members: DashMap<String, DashMap<String, GuildMember>>,
+    /// Guild id -> user id -> voice state, for users currently in a voice channel.
+    pub voice_states: DashMap<String, DashMap<String, VoiceState>>,
+    pub reaction_counts: DashMap<String, HashMap<ReactionKey, u32>>,
+    /// Message ids in `reaction_counts`, least recently updated first.
+    reaction_counts_order: VecDeque<String>,
     options: ClientOptions,
-    handlers: HashMap<String, Vec<EventCallback>>,
-    typed_handlers: Vec<TypedEventCallback>,
+    handlers: HashMap<String, Vec<Registered<EventCallback>>>,
+    typed_handlers: Vec<Registered<TypedEventCallback>>,
+    worker_pool: Option<Registered<DispatchWorkerPool>>,
     ready: bool,
     ready_at: Option<std::time::Instant>,
     user: Option<ClientUser>,
+    application_flags: Option<fluxer_util::ApplicationFlags>,
     ws_manager: Option<Arc<RwLock<WebSocketManager>>>,
     expected_guilds: std::collections::HashSet<String>,
     received_guilds: std::collections::HashSet<String>,
     message_collector_senders: Vec<mpsc::UnboundedSender<ApiMessage>>,
     reaction_collector_senders: Vec<mpsc::UnboundedSender<CollectedReaction>>,
+    session_senders: Vec<mpsc::UnboundedSender<SessionEvent>>,
+    member_chunker: MemberChunker,
+    component_awaiter: ComponentAwaiter,
     #[cfg(feature = "voice")]
     pub voice: Arc<VoiceManager>,
 }
 
 impl Client {
+    /// # Panics
+    ///
+    /// If the REST options are invalid, e.g. an unparsable proxy URL; use [`Client::try_new`] to
+    /// handle that.
     pub fn new(options: ClientOptions) -> Self {
-        let rest = Rest::new(options.rest.clone().unwrap_or_default());
-        Self {
+        Self::try_new(options).expect("invalid client options")
+    }
+
+    /// Like [`Client::new`], but fails if the REST options are invalid.
+    pub fn try_new(options: ClientOptions) -> crate::Result<Self> {
+        let rest = Rest::try_new(options.rest.clone().unwrap_or_default())?;
+        let member_chunker = MemberChunker::new(options.intents);
+        Ok(Self {
             rest,
             guilds: DashMap::new(),
             channels: DashMap::new(),
             users: DashMap::new(),
             members: DashMap::new(),
+            voice_states: DashMap::new(),
+            reaction_counts: DashMap::new(),
+            reaction_counts_order: VecDeque::new(),
             options,
             handlers: HashMap::new(),
             typed_handlers: Vec::new(),
+            worker_pool: None,
             ready: false,
             ready_at: None,
             user: None,
+            application_flags: None,
             ws_manager: None,
             expected_guilds: std::collections::HashSet::new(),
             received_guilds: std::collections::HashSet::new(),
             message_collector_senders: Vec::new(),
             reaction_collector_senders: Vec::new(),
+            session_senders: Vec::new(),
+            member_chunker,
+            component_awaiter: ComponentAwaiter::default(),
             #[cfg(feature = "voice")]
             voice: Arc::new(VoiceManager::new()),
-        }
+        })
     }
 
-    pub fn on<F, Fut>(&mut self, event: &str, callback: F)
+    /// Registers a handler for raw dispatches named `event`, e.g. `"MESSAGE_CREATE"`.
+    pub fn on<F, Fut>(&mut self, event: &str, callback: F) -> HandlerHandle
     where
         F: Fn(Value) -> Fut + Send + Sync + 'static,
         Fut: Future<Output = ()> + Send + 'static,
     {
         let wrapped: EventCallback = Box::new(move |data| Box::pin(callback(data)));
-        self.handlers
-            .entry(event.to_string())
-            .or_default()
-            .push(wrapped);
+        let (registered, handle) = Registered::new(wrapped);
+        let handlers = self.handlers.entry(event.to_string()).or_default();
+        handlers.retain(Registered::is_active);
+        handlers.push(registered);
+        handle
     }
 
-    pub fn on_typed<F, Fut>(&mut self, callback: F)
+    pub fn on_typed<F, Fut>(&mut self, callback: F) -> HandlerHandle
     where
         F: Fn(DispatchEvent) -> Fut + Send + Sync + 'static,
         Fut: Future<Output = ()> + Send + 'static,
     {
         let wrapped: TypedEventCallback = Box::new(move |event| Box::pin(callback(event)));
-        self.typed_handlers.push(wrapped);
+        let (registered, handle) = Registered::new(wrapped);
+        self.typed_handlers.retain(Registered::is_active);
+        self.typed_handlers.push(registered);
+        handle
+    }
+
+    /// Registers a typed handler that runs on `worker_count` tasks, preserving per-guild order.
+    ///
+    /// Unlike [`Client::on_typed`], which spawns a task per event, events for the same guild are
+    /// handled sequentially by one worker. Only one ordered handler is kept: registering another
+    /// unregisters the previous one and stops its workers. Must be called within a Tokio runtime.
+    pub fn on_typed_ordered<F, Fut>(&mut self, worker_count: usize, callback: F) -> HandlerHandle
+    where
+        F: Fn(DispatchEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        if let Some(previous) = self.worker_pool.take()
+            && previous.is_active()
+        {
+            warn!("Replacing the existing ordered dispatch handler");
+            previous.unregister();
+        }
+        let (registered, handle) = Registered::new(DispatchWorkerPool::new(worker_count, callback));
+        self.worker_pool = Some(registered);
+        handle
+    }
+
+    pub fn resolver(&self) -> Resolver<'_> {
+        Resolver::new(self)
+    }
+
+    /// A cloneable handle for requesting guild members over the gateway.
+    pub fn member_chunker(&self) -> MemberChunker {
+        self.member_chunker.clone()
+    }
+
+    /// Requests all members of `guild_id` over the gateway and waits until they are cached.
+    /// Requires the `GUILD_MEMBERS` intent.
+    pub async fn warm_guild(&self, guild_id: &str) -> crate::Result<usize> {
+        self.member_chunker.warm_guild(guild_id).await
+    }
+
+    /// Resolves the given users' members of `guild_id` over the gateway, batching ids
+    /// instead of fetching each member over HTTP.
+    pub async fn fetch_members(
+        &self,
+        guild_id: &str,
+        user_ids: &[Snowflake],
+    ) -> crate::Result<Vec<GuildMember>> {
+        self.member_chunker.fetch_members(guild_id, user_ids).await
     }
 
     pub fn user(&self) -> Option<&ClientUser> {
         self.user.as_ref()
     }
 
+    /// The application's flags from READY, e.g. to check the message content grant at runtime.
+    pub fn application_flags(&self) -> Option<fluxer_util::ApplicationFlags> {
+        self.application_flags
+    }
+
     pub fn is_ready(&self) -> bool {
         self.ready
     }
@@ -130,6 +239,11 @@ impl Client {
         self.ready_at
     }
 
+    /// Aggregated reaction counts for a message, kept when `track_reaction_counts` is enabled.
+    pub fn reaction_counts(&self, message_id: &str) -> Option<HashMap<ReactionKey, u32>> {
+        self.reaction_counts.get(message_id).map(|r| r.clone())
+    }
+
     pub fn get_or_create_user(&self, data: &fluxer_types::user::ApiUser) -> User {
         if let Some(mut existing) = self.users.get_mut(&data.id) {
             existing.patch(data);
@@ -167,11 +281,82 @@ impl Client {
         Err("Not connected".to_string())
     }
 
+    /// Commands waiting in each shard's outbound queue; empty when not connected.
+    pub async fn queued_commands(&self) -> HashMap<u32, usize> {
+        match &self.ws_manager {
+            Some(mgr) => mgr.read().await.queued_commands().await,
+            None => HashMap::new(),
+        }
+    }
+
+    /// Voice states of the users currently connected to `channel_id`.
+    pub fn voice_states_in(&self, channel_id: &str) -> Vec<VoiceState> {
+        self.voice_states
+            .iter()
+            .flat_map(|guild| {
+                guild
+                    .iter()
+                    .filter(|state| state.channel_id == channel_id)
+                    .map(|state| state.clone())
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    pub fn voice_state(&self, guild_id: &str, user_id: &str) -> Option<VoiceState> {
+        self.voice_states
+            .get(guild_id)?
+            .get(user_id)
+            .map(|state| state.clone())
+    }
+
+    /// Code `shard_id` last closed with; `None` if it hasn't closed or when not connected.
+    pub async fn last_close_code(&self, shard_id: u32) -> Option<u16> {
+        match &self.ws_manager {
+            Some(mgr) => mgr.read().await.last_close_code(shard_id).await,
+            None => None,
+        }
+    }
+
+    /// Heartbeat round-trip of `shard_id`; `None` before its first ACK or when not connected.
+    pub async fn latency(&self, shard_id: u32) -> Option<std::time::Duration> {
+        match &self.ws_manager {
+            Some(mgr) => mgr.read().await.latency(shard_id).await,
+            None => None,
+        }
+    }
+
+    /// Total shards the client was started with; `None` when not connected.
+    pub async fn shard_count(&self) -> Option<u32> {
+        match &self.ws_manager {
+            Some(mgr) => Some(mgr.read().await.shard_count()),
+            None => None,
+        }
+    }
+
+    /// Recommended shard count and session start limits for the bot token.
+    pub async fn fetch_gateway_bot(
+        &self,
+    ) -> crate::Result<fluxer_types::gateway::ApiGatewayBotResponse> {
+        Ok(self.rest.gateway_bot().await?)
+    }
+
     pub async fn fetch_instance(&self) -> crate::Result<Value> {
         let data: Value = self.rest.get(fluxer_types::Routes::instance()).await?;
         Ok(data)
     }
 
+    /// Fetches the bot's own application using the bot token.
+    pub async fn current_application(
+        &self,
+    ) -> crate::Result<fluxer_types::application::ApiApplication> {
+        let app = self
+            .rest
+            .get(fluxer_types::Routes::current_application())
+            .await?;
+        Ok(app)
+    }
+
     pub fn create_message_collector(
         &mut self,
         options: MessageCollectorOptions,
@@ -181,6 +366,36 @@ impl Client {
         collector
     }
 
+    /// Receives a [`SessionEvent`] whenever a shard resumes, is invalidated or starts a fresh
+    /// session, so caches kept outside the client can be dropped after a non-resumable reconnect.
+    pub fn subscribe_session(&mut self) -> mpsc::UnboundedReceiver<SessionEvent> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.session_senders.push(tx);
+        rx
+    }
+
+    fn emit_session(&mut self, event: SessionEvent) {
+        self.session_senders.retain(|tx| tx.send(event).is_ok());
+    }
+
+    /// A cloneable handle for awaiting component interactions from inside event handlers.
+    pub fn component_awaiter(&self) -> ComponentAwaiter {
+        self.component_awaiter.clone()
+    }
+
+    /// Waits up to `time` for a button or select interaction on `message_id` that passes
+    /// `filter`, e.g. one restricted to the invoking user or a specific `custom_id`.
+    pub async fn await_component(
+        &self,
+        message_id: &str,
+        time: std::time::Duration,
+        filter: Option<ComponentFilter>,
+    ) -> Option<crate::structures::interaction::Interaction> {
+        self.component_awaiter
+            .await_component(message_id, time, filter)
+            .await
+    }
+
     pub fn create_reaction_collector(
         &mut self,
         options: ReactionCollectorOptions,
@@ -195,6 +410,17 @@ impl Client {
             return Err(crate::Error::AlreadyLoggedIn);
         }
 
+        if let Some(allowed) = self.options.privileged_intents_allowed {
+            let disallowed = fluxer_util::GatewayIntents::from_bits_retain(self.options.intents)
+                .disallowed(allowed);
+            if !disallowed.is_empty() {
+                return Err(crate::Error::DisallowedIntents {
+                    intents: disallowed,
+                    close_code: None,
+                });
+            }
+        }
+
         self.rest.set_token(token).await;
 
         let (ws_tx, mut ws_rx) = mpsc::unbounded_channel::<WsEvent>();
@@ -203,19 +429,30 @@ impl Client {
             token: token.to_string(),
             intents: self.options.intents,
             presence: self.options.presence.clone(),
-            shard_ids: None,
-            shard_count: None,
+            shard_ids: self.options.shard_ids.clone(),
+            shard_count: self.options.shard_count,
             version: self
                 .options
                 .gateway_version
                 .clone()
                 .unwrap_or("1".to_string()),
+            reconnect_max_delay: self
+                .options
+                .reconnect_max_delay
+                .unwrap_or(fluxer_ws::DEFAULT_RECONNECT_MAX_DELAY),
+            send_queue_capacity: self
+                .options
+                .send_queue_capacity
+                .unwrap_or(fluxer_ws::DEFAULT_SEND_QUEUE_CAPACITY),
+            send_overflow_policy: self.options.send_overflow_policy,
+            compression: self.options.compression,
         };
 
         let mut manager = WebSocketManager::new(ws_options, self.rest.clone(), ws_tx);
-        manager.connect().await.map_err(crate::Error::Rest)?;
+        manager.connect().await?;
 
         self.ws_manager = Some(Arc::new(RwLock::new(manager)));
+        self.member_chunker.set_gateway(self.ws_manager.clone());
 
         let ws_clone = self.ws_manager.as_ref().unwrap().clone();
         #[cfg(feature = "voice")]
@@ -230,7 +467,9 @@ impl Client {
 
         while let Some(event) = ws_rx.recv().await {
             match event {
-                WsEvent::ShardReady { data, .. } => {
+                WsEvent::ShardReady { shard_id, data } => {
+                    self.emit_session(SessionEvent::FreshReady { shard_id });
+
                     if let Some(user_data) = data.get("user")
                         && let Ok(api_user) =
                             serde_json::from_value::<fluxer_types::user::ApiUser>(user_data.clone())
@@ -240,6 +479,10 @@ impl Client {
                         self.users.insert(api_user.id.clone(), u);
                     }
 
+                    if let Some(flags) = ready_application_flags(&data) {
+                        self.application_flags = Some(flags);
+                    }
+
                     if let Some(guilds_arr) = data.get("guilds").and_then(|v| v.as_array()) {
                         for guild_val in guilds_arr {
                             if let Some(id) = guild_val.get("id").and_then(|v| v.as_str()) {
@@ -266,10 +509,63 @@ impl Client {
                         self.emit_event(event_name, data.clone()).await;
 
                         let typed = event_parser::parse_dispatch(event_name, &data);
-                        self.emit_typed_event(typed).await;
+                        self.emit_typed_event_keyed(typed, ordering_key(event_name, &data))
+                            .await;
                     }
                 }
 
+                WsEvent::ShardInvalidSession {
+                    shard_id,
+                    resumable,
+                } => {
+                    tracing::warn!(
+                        target: "fluxer_core::ws",
+                        "shard {shard_id} session invalidated (resumable: {resumable})"
+                    );
+                    self.emit_session(SessionEvent::Invalidated {
+                        shard_id,
+                        resumable,
+                    });
+                    self.emit_typed_event(DispatchEvent::InvalidSession {
+                        shard_id,
+                        resumable,
+                    })
+                    .await;
+                }
+
+                WsEvent::ShardClose {
+                    shard_id,
+                    code,
+                    reason,
+                } => {
+                    tracing::warn!(
+                        target: "fluxer_core::ws",
+                        "shard {shard_id} closed with {code}: {reason}"
+                    );
+                    self.emit_typed_event(DispatchEvent::ShardClose {
+                        shard_id,
+                        code,
+                        reason: reason.clone(),
+                    })
+                    .await;
+                    if fluxer_ws::is_fatal_close_code(code) {
+                        if let Some(ws) = self.ws_manager.take() {
+                            ws.write().await.shutdown();
+                        }
+                        self.destroy();
+                        return Err(self.fatal_close_error(shard_id, code, reason));
+                    }
+                }
+
+                WsEvent::ShardResumed { shard_id } => {
+                    self.emit_session(SessionEvent::Resumed { shard_id });
+                }
+
+                WsEvent::ShardReconnect { shard_id } => {
+                    self.emit_typed_event(DispatchEvent::Reconnect { shard_id })
+                        .await;
+                }
+
                 WsEvent::Error { error, shard_id: _ } => {
                     tracing::error!(target: "fluxer_core::ws", "{error}");
                     self.emit_event("ERROR", Value::String(error.clone())).await;
@@ -292,6 +588,11 @@ impl Client {
     }
 
     async fn handle_dispatch(&mut self, event: &str, data: &Value) {
+        if self.options.track_reaction_counts {
+            self.update_reaction_counts(event, data);
+            self.enforce_reaction_counts_limit();
+        }
+
         match event {
             "VOICE_SERVER_UPDATE" => {
                 tracing::info!("VOICE_SERVER_UPDATE received: {:?}", data);
@@ -299,7 +600,35 @@ impl Client {
                 self.voice.handle_voice_server_update(data.clone());
             }
 
+            "INTERACTION_CREATE" => {
+                if let Ok(api) = serde_json::from_value::<
+                    fluxer_types::interaction::ApiApplicationCommandInteraction,
+                >(data.clone())
+                {
+                    let interaction = crate::structures::interaction::Interaction::from_api(&api);
+                    self.component_awaiter.dispatch(&interaction);
+                }
+            }
+
+            "CHANNEL_PINS_UPDATE" => {
+                if let Ok(update) = serde_json::from_value::<
+                    fluxer_types::gateway::GatewayChannelPinsUpdateData,
+                >(data.clone())
+                    && let Some(mut ch) = self.channels.get_mut(&update.channel_id)
+                {
+                    ch.last_pin_timestamp = update.last_pin_timestamp;
+                }
+            }
+
             "MESSAGE_CREATE" => {
+                if let (Some(channel_id), Some(message_id)) = (
+                    data.get("channel_id").and_then(|v| v.as_str()),
+                    data.get("id").and_then(|v| v.as_str()),
+                ) && let Some(mut ch) = self.channels.get_mut(channel_id)
+                {
+                    ch.last_message_id = Some(message_id.to_string());
+                }
+
                 if let Ok(api_msg) = serde_json::from_value::<ApiMessage>(data.clone()) {
                     self.message_collector_senders.retain(|tx| !tx.is_closed());
                     for tx in &self.message_collector_senders {
@@ -400,6 +729,20 @@ impl Client {
                         guild.member_count = Some(mc);
                     }
 
+                    if let Some(states) = data.get("voice_states").and_then(|v| v.as_array()) {
+                        let guild_states = self.voice_states.entry(guild.id.clone()).or_default();
+                        guild_states.clear();
+                        for state_val in states {
+                            if let Ok(api_state) = serde_json::from_value::<
+                                fluxer_types::gateway::ApiVoiceState,
+                            >(state_val.clone())
+                                && let Some(state) = VoiceState::from_api(&api_state, &guild.id)
+                            {
+                                guild_states.insert(state.user_id.clone(), state);
+                            }
+                        }
+                    }
+
                     let gid = guild.id.clone();
                     self.guilds.insert(gid.clone(), guild);
 
@@ -436,6 +779,42 @@ impl Client {
                         }
                     }
                     self.members.remove(id);
+                    self.voice_states.remove(id);
+                }
+            }
+
+            "VOICE_STATE_UPDATE" => {
+                if let Ok(api_state) =
+                    serde_json::from_value::<fluxer_types::gateway::ApiVoiceState>(data.clone())
+                    && let Some(guild_id) = api_state.guild_id.as_deref()
+                {
+                    let guild_states = self.voice_states.entry(guild_id.to_string()).or_default();
+                    match VoiceState::from_api(&api_state, guild_id) {
+                        Some(state) => {
+                            guild_states.insert(state.user_id.clone(), state);
+                        }
+                        None => {
+                            guild_states.remove(&api_state.user_id);
+                        }
+                    }
+                }
+            }
+
+            "GUILD_MEMBERS_CHUNK" => {
+                if let Ok(chunk) = serde_json::from_value::<
+                    fluxer_types::gateway::GatewayGuildMembersChunkData,
+                >(data.clone())
+                {
+                    let guild_members = self.members.entry(chunk.guild_id.clone()).or_default();
+                    for api_m in &chunk.members {
+                        if let Some(ref u) = api_m.user {
+                            self.get_or_create_user(u);
+                        }
+                        let member = GuildMember::from_api(api_m, &chunk.guild_id);
+                        guild_members.insert(member.id.clone(), member);
+                    }
+                    drop(guild_members);
+                    self.member_chunker.handle_chunk(chunk);
                 }
             }
 
@@ -523,16 +902,12 @@ impl Client {
             }
 
             "GUILD_EMOJIS_UPDATE" => {
-                let guild_id = data.get("guild_id").and_then(|v| v.as_str()).unwrap_or("");
-                if let Some(mut g) = self.guilds.get_mut(guild_id) {
-                    g.emojis.clear();
-                    if let Some(arr) = data.get("emojis").and_then(|v| v.as_array()) {
-                        for e in arr {
-                            if let Some(id) = e.get("id").and_then(|v| v.as_str()) {
-                                g.emojis.push(id.to_string());
-                            }
-                        }
-                    }
+                if let Ok(update) = serde_json::from_value::<
+                    fluxer_types::gateway::GatewayGuildEmojisUpdateData,
+                >(data.clone())
+                    && let Some(mut g) = self.guilds.get_mut(&update.guild_id)
+                {
+                    g.emojis = update.emojis.into_iter().map(|e| e.id).collect();
                 }
             }
 
@@ -545,7 +920,7 @@ impl Client {
                 }
             }
 
-            "CHANNEL_CREATE" | "CHANNEL_UPDATE" => {
+            "CHANNEL_CREATE" | "CHANNEL_UPDATE" | "THREAD_CREATE" | "THREAD_UPDATE" => {
                 if let Ok(api_ch) =
                     serde_json::from_value::<fluxer_types::channel::ApiChannel>(data.clone())
                 {
@@ -560,7 +935,7 @@ impl Client {
                 }
             }
 
-            "CHANNEL_DELETE" => {
+            "CHANNEL_DELETE" | "THREAD_DELETE" => {
                 if let Some(id) = data.get("id").and_then(|v| v.as_str())
                     && let Some((_, ch)) = self.channels.remove(id)
                     && let Some(gid) = &ch.guild_id
@@ -634,20 +1009,114 @@ impl Client {
         }
     }
 
-    async fn emit_event(&self, event: &str, data: Value) {
-        if let Some(handlers) = self.handlers.get(event) {
-            for handler in handlers {
-                let fut = handler(data.clone());
+    fn update_reaction_counts(&mut self, event: &str, data: &Value) {
+        use fluxer_types::gateway::{
+            GatewayReactionAddData, GatewayReactionRemoveData, GatewayReactionRemoveEmojiData,
+        };
+
+        match event {
+            "MESSAGE_CREATE" | "MESSAGE_UPDATE" => {
+                let Some(id) = data.get("id").and_then(|v| v.as_str()) else {
+                    return;
+                };
+                if let Some(reactions) = data.get("reactions").and_then(|v| {
+                    serde_json::from_value::<Vec<fluxer_types::message::ApiMessageReaction>>(
+                        v.clone(),
+                    )
+                    .ok()
+                }) {
+                    let counts = reactions
+                        .iter()
+                        .map(|r| (ReactionKey::from_api(&r.emoji), r.count))
+                        .collect();
+                    self.reaction_counts.insert(id.to_string(), counts);
+                    self.touch_reaction_counts(id);
+                }
+            }
+            "MESSAGE_REACTION_ADD" => {
+                if let Ok(d) = serde_json::from_value::<GatewayReactionAddData>(data.clone()) {
+                    *self
+                        .reaction_counts
+                        .entry(d.message_id.clone())
+                        .or_default()
+                        .entry(ReactionKey::from_gateway(&d.emoji))
+                        .or_insert(0) += 1;
+                    self.touch_reaction_counts(&d.message_id);
+                }
+            }
+            "MESSAGE_REACTION_REMOVE" => {
+                if let Ok(d) = serde_json::from_value::<GatewayReactionRemoveData>(data.clone())
+                    && let Some(mut counts) = self.reaction_counts.get_mut(&d.message_id)
+                {
+                    let emoji = ReactionKey::from_gateway(&d.emoji);
+                    if let Some(count) = counts.get_mut(&emoji) {
+                        *count = count.saturating_sub(1);
+                        if *count == 0 {
+                            counts.remove(&emoji);
+                        }
+                    }
+                }
+            }
+            "MESSAGE_REACTION_REMOVE_EMOJI" => {
+                if let Ok(d) =
+                    serde_json::from_value::<GatewayReactionRemoveEmojiData>(data.clone())
+                    && let Some(mut counts) = self.reaction_counts.get_mut(&d.message_id)
+                {
+                    counts.remove(&ReactionKey::from_gateway(&d.emoji));
+                }
+            }
+            "MESSAGE_REACTION_REMOVE_ALL" | "MESSAGE_DELETE" => {
+                let key = if event == "MESSAGE_DELETE" {
+                    "id"
+                } else {
+                    "message_id"
+                };
+                if let Some(id) = data.get(key).and_then(|v| v.as_str()) {
+                    self.forget_reaction_counts(id);
+                }
+            }
+            "MESSAGE_DELETE_BULK" => {
+                if let Some(ids) = data.get("ids").and_then(|v| v.as_array()) {
+                    for id in ids.iter().filter_map(|v| v.as_str()) {
+                        self.forget_reaction_counts(id);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    async fn emit_event(&mut self, event: &str, data: Value) {
+        if let Some(handlers) = self.handlers.get_mut(event) {
+            handlers.retain(Registered::is_active);
+            for handler in handlers.iter() {
+                let fut = (handler.callback)(data.clone());
                 tokio::spawn(fut);
             }
         }
     }
 
-    async fn emit_typed_event(&self, event: DispatchEvent) {
-        for handler in &self.typed_handlers {
-            let fut = handler(event.clone());
+    async fn emit_typed_event(&mut self, event: DispatchEvent) {
+        self.emit_typed_event_keyed(event, None).await;
+    }
+
+    async fn emit_typed_event_keyed(&mut self, event: DispatchEvent, key: Option<&str>) {
+        self.typed_handlers.retain(Registered::is_active);
+        for handler in self.typed_handlers.iter() {
+            let fut = (handler.callback)(event.clone());
             tokio::spawn(fut);
         }
+        // Dropping an unregistered pool closes its channels, which stops the workers.
+        if self
+            .worker_pool
+            .as_ref()
+            .is_some_and(|pool| !pool.is_active())
+        {
+            self.worker_pool = None;
+        }
+        if let Some(pool) = &self.worker_pool {
+            pool.callback.dispatch(key, event);
+        }
     }
 
     fn enforce_cache_limits(&self) {
@@ -697,18 +1166,429 @@ impl Client {
         }
     }
 
+    /// Marks `message_id` as the most recently updated reaction count entry.
+    fn touch_reaction_counts(&mut self, message_id: &str) {
+        if let Some(i) = self
+            .reaction_counts_order
+            .iter()
+            .position(|id| id == message_id)
+        {
+            self.reaction_counts_order.remove(i);
+        }
+        self.reaction_counts_order.push_back(message_id.to_string());
+    }
+
+    fn forget_reaction_counts(&mut self, message_id: &str) {
+        self.reaction_counts.remove(message_id);
+        self.reaction_counts_order.retain(|id| id != message_id);
+    }
+
+    /// Evicts the least recently updated messages until the limit holds.
+    fn enforce_reaction_counts_limit(&mut self) {
+        let max = self
+            .options
+            .cache
+            .reaction_counts
+            .unwrap_or(DEFAULT_REACTION_COUNTS_LIMIT);
+        while self.reaction_counts.len() > max {
+            let Some(oldest) = self.reaction_counts_order.pop_front() else {
+                break;
+            };
+            self.reaction_counts.remove(&oldest);
+        }
+    }
+
+    fn fatal_close_error(&self, shard_id: u32, code: u16, reason: String) -> crate::Error {
+        match fluxer_ws::GatewayCloseCode::from_code(code) {
+            Some(fluxer_ws::GatewayCloseCode::AuthenticationFailed) => crate::Error::InvalidToken,
+            Some(fluxer_ws::GatewayCloseCode::DisallowedIntents) => {
+                let intents = fluxer_util::GatewayIntents::from_bits_retain(self.options.intents);
+                let allowed = self.options.privileged_intents_allowed.unwrap_or_default();
+                crate::Error::DisallowedIntents {
+                    intents: intents.disallowed(allowed),
+                    close_code: Some(code),
+                }
+            }
+            _ => crate::Error::GatewayClosed {
+                shard_id,
+                code,
+                reason,
+            },
+        }
+    }
+
     pub fn destroy(&mut self) {
         self.ready = false;
         self.ready_at = None;
         self.user = None;
+        self.application_flags = None;
         self.guilds.clear();
         self.channels.clear();
         self.users.clear();
         self.members.clear();
+        self.voice_states.clear();
+        self.reaction_counts.clear();
+        self.reaction_counts_order.clear();
         self.ws_manager = None;
+        self.member_chunker.set_gateway(None);
         self.expected_guilds.clear();
         self.received_guilds.clear();
         self.message_collector_senders.clear();
         self.reaction_collector_senders.clear();
+        self.session_senders.clear();
+    }
+}
+
+/// The guild a dispatch belongs to, used to keep per-guild ordering in the worker pool.
+fn ordering_key<'a>(event: &str, data: &'a Value) -> Option<&'a str> {
+    let field = if event.starts_with("GUILD_") && data.get("guild_id").is_none() {
+        "id"
+    } else {
+        "guild_id"
+    };
+    data.get(field).and_then(Value::as_str)
+}
+
+/// The `application.flags` carried by a READY payload.
+fn ready_application_flags(data: &Value) -> Option<fluxer_util::ApplicationFlags> {
+    let application = data.get("application")?;
+    let app =
+        serde_json::from_value::<fluxer_types::gateway::GatewayApplication>(application.clone())
+            .ok()?;
+    Some(fluxer_util::ApplicationFlags::from_bits_truncate(app.flags))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn client(options: ClientOptions) -> Client {
+        Client::new(options)
+    }
+
+    fn reaction(message_id: &str, name: &str) -> Value {
+        json!({
+            "message_id": message_id,
+            "channel_id": "1",
+            "user_id": "2",
+            "emoji": { "id": null, "name": name },
+        })
+    }
+
+    fn emoji(name: &str) -> ReactionKey {
+        ReactionKey {
+            id: None,
+            name: name.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn tallies_reaction_events() {
+        let mut client = client(ClientOptions {
+            track_reaction_counts: true,
+            ..Default::default()
+        });
+        for (event, name) in [
+            ("MESSAGE_REACTION_ADD", "a"),
+            ("MESSAGE_REACTION_ADD", "a"),
+            ("MESSAGE_REACTION_ADD", "b"),
+            ("MESSAGE_REACTION_REMOVE", "a"),
+            ("MESSAGE_REACTION_ADD", "c"),
+            ("MESSAGE_REACTION_REMOVE", "c"),
+        ] {
+            client.handle_dispatch(event, &reaction("10", name)).await;
+        }
+
+        let counts = client.reaction_counts("10").unwrap();
+        assert_eq!(counts.get(&emoji("a")), Some(&1));
+        assert_eq!(counts.get(&emoji("b")), Some(&1));
+        assert_eq!(counts.get(&emoji("c")), None);
+    }
+
+    #[tokio::test]
+    async fn resets_reaction_counts_on_remove_emoji_and_remove_all() {
+        let mut client = client(ClientOptions {
+            track_reaction_counts: true,
+            ..Default::default()
+        });
+        for name in ["a", "b"] {
+            client
+                .handle_dispatch("MESSAGE_REACTION_ADD", &reaction("10", name))
+                .await;
+        }
+
+        let remove_emoji = json!({
+            "message_id": "10",
+            "channel_id": "1",
+            "emoji": { "id": null, "name": "a" },
+        });
+        client
+            .handle_dispatch("MESSAGE_REACTION_REMOVE_EMOJI", &remove_emoji)
+            .await;
+        let counts = client.reaction_counts("10").unwrap();
+        assert_eq!(counts.get(&emoji("a")), None);
+        assert_eq!(counts.get(&emoji("b")), Some(&1));
+
+        let remove_all = json!({ "message_id": "10", "channel_id": "1" });
+        client
+            .handle_dispatch("MESSAGE_REACTION_REMOVE_ALL", &remove_all)
+            .await;
+        assert!(client.reaction_counts("10").is_none());
+    }
+
+    #[tokio::test]
+    async fn reaction_counts_are_opt_in_and_bounded() {
+        let mut untracked = client(ClientOptions::default());
+        untracked
+            .handle_dispatch("MESSAGE_REACTION_ADD", &reaction("10", "a"))
+            .await;
+        assert!(untracked.reaction_counts("10").is_none());
+
+        let mut bounded = client(ClientOptions {
+            track_reaction_counts: true,
+            cache: CacheSizeLimits {
+                reaction_counts: Some(2),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        for id in ["10", "11", "10", "12"] {
+            bounded
+                .handle_dispatch("MESSAGE_REACTION_ADD", &reaction(id, "a"))
+                .await;
+        }
+        assert_eq!(bounded.reaction_counts.len(), 2);
+        assert!(bounded.reaction_counts("11").is_none());
+        assert_eq!(
+            bounded.reaction_counts("10").unwrap().get(&emoji("a")),
+            Some(&2)
+        );
+        assert!(bounded.reaction_counts("12").is_some());
+
+        bounded
+            .handle_dispatch("MESSAGE_DELETE", &json!({ "id": "10", "channel_id": "1" }))
+            .await;
+        bounded
+            .handle_dispatch("MESSAGE_REACTION_ADD", &reaction("13", "a"))
+            .await;
+        assert!(bounded.reaction_counts("12").is_some());
+        assert!(bounded.reaction_counts("13").is_some());
+    }
+
+    #[tokio::test]
+    async fn current_application_uses_the_bot_token() {
+        let mock = fluxer_rest::MockTransport::new();
+        let mut client = client(ClientOptions::default());
+        client.rest = Rest::with_transport(RestOptions::default(), mock.clone());
+        client.rest.set_token("abc").await;
+        mock.push_json(
+            200,
+            &json!({
+                "id": "1",
+                "name": "App",
+                "bot": { "id": "3", "username": "bot", "discriminator": "0", "bot": true },
+                "redirect_uris": [],
+            }),
+        );
+
+        let app = client.current_application().await.unwrap();
+
+        assert_eq!(app.id, "1");
+        assert_eq!(app.bot.unwrap().bot, Some(true));
+        assert_eq!(app.redirect_uris, Some(vec![]));
+        let req = mock.last_request().unwrap();
+        assert!(req.url.ends_with("/applications/@me"));
+        assert_eq!(req.headers["authorization"], "Bot abc");
+    }
+
+    #[tokio::test]
+    async fn tracks_the_last_message_and_pin_on_cached_channels() {
+        let mut client = client(ClientOptions::default());
+        client
+            .handle_dispatch(
+                "CHANNEL_CREATE",
+                &json!({ "id": "1", "type": 0, "last_message_id": "5" }),
+            )
+            .await;
+        client
+            .handle_dispatch(
+                "MESSAGE_CREATE",
+                &json!({
+                    "id": "6",
+                    "channel_id": "1",
+                    "author": { "id": "2", "username": "u", "discriminator": "0" },
+                    "type": 0,
+                    "content": "hi",
+                    "timestamp": "2024-01-01T00:00:00.000Z",
+                    "pinned": false,
+                }),
+            )
+            .await;
+        client
+            .handle_dispatch(
+                "CHANNEL_PINS_UPDATE",
+                &json!({ "channel_id": "1", "last_pin_timestamp": "2024-01-02T00:00:00.000Z" }),
+            )
+            .await;
+
+        let channel = client.channels.get("1").unwrap();
+        assert_eq!(channel.last_message_id.as_deref(), Some("6"));
+        assert!(channel.last_pin_timestamp.is_some());
+    }
+
+    fn button_click(message_id: &str, custom_id: &str) -> Value {
+        json!({
+            "id": "70",
+            "application_id": "71",
+            "type": 3,
+            "token": "tok",
+            "channel_id": "1",
+            "user": { "id": "2", "username": "u", "discriminator": "0" },
+            "data": { "custom_id": custom_id, "component_type": 2 },
+            "message": { "id": message_id, "channel_id": "1" },
+        })
+    }
+
+    #[tokio::test]
+    async fn await_component_resolves_with_the_click_on_its_message() {
+        let mut client = client(ClientOptions::default());
+        let awaiter = client.component_awaiter();
+        let waiting = tokio::spawn(async move {
+            awaiter
+                .await_component(
+                    "50",
+                    std::time::Duration::from_secs(5),
+                    Some(Box::new(|i| {
+                        i.data.as_ref().and_then(|d| d.custom_id.as_deref()) == Some("yes")
+                    })),
+                )
+                .await
+        });
+        tokio::task::yield_now().await;
+
+        client
+            .handle_dispatch("INTERACTION_CREATE", &button_click("49", "yes"))
+            .await;
+        client
+            .handle_dispatch("INTERACTION_CREATE", &button_click("50", "no"))
+            .await;
+        client
+            .handle_dispatch("INTERACTION_CREATE", &button_click("50", "yes"))
+            .await;
+
+        let interaction = waiting.await.unwrap().unwrap();
+        assert_eq!(interaction.message_id.as_deref(), Some("50"));
+        assert_eq!(interaction.user.unwrap().id, "2");
+    }
+
+    #[tokio::test]
+    async fn await_component_times_out_without_a_click() {
+        let client = client(ClientOptions::default());
+        let result = client
+            .await_component("50", std::time::Duration::from_millis(10), None)
+            .await;
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn reads_application_flags_from_ready() {
+        let flags = fluxer_util::ApplicationFlags::GATEWAY_MESSAGE_CONTENT
+            | fluxer_util::ApplicationFlags::GATEWAY_GUILD_MEMBERS_LIMITED;
+        let ready = json!({
+            "v": 1,
+            "user": { "id": "1", "username": "bot", "discriminator": "0" },
+            "guilds": [],
+            "session_id": "abc",
+            "application": { "id": "2", "flags": flags.bits() },
+        });
+
+        let read = ready_application_flags(&ready).unwrap();
+        assert_eq!(read, flags);
+        assert!(read.has_message_content());
+        assert!(read.has_guild_members());
+        assert!(!read.has_presence());
+        assert!(ready_application_flags(&json!({ "v": 1 })).is_none());
+
+        let typed: fluxer_types::gateway::GatewayReadyData = serde_json::from_value(ready).unwrap();
+        assert_eq!(typed.application.flags, flags.bits());
+    }
+
+    #[test]
+    fn try_new_rejects_an_invalid_proxy() {
+        let options = ClientOptions {
+            rest: Some(RestOptions {
+                proxy: Some("not a proxy url".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert!(Client::try_new(options).is_err());
+    }
+
+    #[tokio::test]
+    async fn a_fatal_close_stops_every_shard() {
+        use futures_util::{SinkExt, StreamExt};
+        use tokio_tungstenite::tungstenite::Message as WsMessage;
+        use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+        use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mock = fluxer_rest::MockTransport::new();
+        mock.push_json(
+            200,
+            &json!({
+                "url": format!("ws://{}", listener.local_addr().unwrap()),
+                "shards": 2,
+                "session_start_limit": {
+                    "total": 1000,
+                    "remaining": 1000,
+                    "reset_after": 0,
+                    "max_concurrency": 2,
+                },
+            }),
+        );
+        let mut client = client(ClientOptions::default());
+        client.rest = Rest::with_transport(RestOptions::default(), mock);
+        let login = tokio::spawn(async move { client.login("token").await });
+
+        let mut sockets = Vec::new();
+        for _ in 0..2 {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+            let hello = json!({ "op": 10, "d": { "heartbeat_interval": u32::MAX } });
+            socket
+                .send(WsMessage::Text(hello.to_string()))
+                .await
+                .unwrap();
+            sockets.push(socket);
+        }
+        let close = |code: u16| {
+            Some(CloseFrame {
+                code: CloseCode::from(code),
+                reason: "".into(),
+            })
+        };
+
+        sockets[0].close(close(4014)).await.unwrap();
+        let result = tokio::time::timeout(std::time::Duration::from_secs(5), login)
+            .await
+            .expect("login did not return")
+            .unwrap();
+        assert!(result.is_err());
+
+        // With a session to resume, a live shard would reconnect within its first backoff (at
+        // most 1s) after this close.
+        let ready = json!({ "op": 0, "s": 1, "t": "READY", "d": { "session_id": "abc" } });
+        let _ = sockets[1].send(WsMessage::Text(ready.to_string())).await;
+        let _ = sockets[1].close(close(4000)).await;
+        while sockets[1].next().await.is_some() {}
+        let reconnect =
+            tokio::time::timeout(std::time::Duration::from_secs(2), listener.accept()).await;
+        assert!(
+            reconnect.is_err(),
+            "a shard reconnected after the fatal close"
+        );
     }
 }
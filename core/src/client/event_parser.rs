@@ -34,16 +34,34 @@ pub(crate) fn parse_dispatch(event_name: &str, data: &Value) -> DispatchEvent {
         "GUILD_ROLE_UPDATE" => parse_guild_role_update(data),
         "GUILD_ROLE_DELETE" => parse_guild_role_delete(data),
         "GUILD_EMOJIS_UPDATE" => parse_guild_emojis_update(data),
+        "GUILD_STICKERS_UPDATE" => parse_guild_stickers_update(data),
+        "GUILD_SCHEDULED_EVENT_CREATE"
+        | "GUILD_SCHEDULED_EVENT_UPDATE"
+        | "GUILD_SCHEDULED_EVENT_DELETE" => parse_scheduled_event(event_name, data),
+        "GUILD_SCHEDULED_EVENT_USER_ADD" | "GUILD_SCHEDULED_EVENT_USER_REMOVE" => {
+            parse_scheduled_event_user(event_name, data)
+        }
         "CHANNEL_CREATE" => parse_channel_create(data),
         "CHANNEL_UPDATE" => parse_channel_update(data),
         "CHANNEL_DELETE" => parse_channel_delete(data),
+        "THREAD_CREATE" => parse_thread_create(data),
+        "THREAD_UPDATE" => parse_thread_update(data),
+        "THREAD_DELETE" => parse_thread_delete(data),
+        "THREAD_LIST_SYNC" => parse_thread_list_sync(data),
+        "THREAD_MEMBER_UPDATE" => parse_thread_member_update(data),
+        "THREAD_MEMBERS_UPDATE" => parse_thread_members_update(data),
         "INVITE_CREATE" => parse_invite_create(data),
         "INVITE_DELETE" => parse_invite_delete(data),
         "USER_UPDATE" => parse_user_update(data),
         "TYPING_START" => parse_typing_start(data),
         "VOICE_STATE_UPDATE" => parse_voice_state_update(data),
         "VOICE_SERVER_UPDATE" => parse_voice_server_update(data),
+        "VOICE_CHANNEL_STATUS_UPDATE" => parse_voice_channel_status_update(data),
         "PRESENCE_UPDATE" => parse_presence_update(data),
+        "PRESENCES_REPLACE" => parse_presences_replace(data),
+        "GUILD_MEMBERS_CHUNK" => parse_guild_members_chunk(data),
+        "MESSAGE_POLL_VOTE_ADD" => parse_poll_vote(data, true),
+        "MESSAGE_POLL_VOTE_REMOVE" => parse_poll_vote(data, false),
         "INTERACTION_CREATE" => DispatchEvent::InteractionCreate { data: data.clone() },
         _ => DispatchEvent::Raw {
             event_name: event_name.to_string(),
@@ -274,20 +292,61 @@ fn parse_guild_role_delete(data: &Value) -> DispatchEvent {
 }
 
 fn parse_guild_emojis_update(data: &Value) -> DispatchEvent {
-    let guild_id = str_field(data, "guild_id").unwrap_or_default();
-    let emoji_ids = data
-        .get("emojis")
-        .and_then(|v| v.as_array())
-        .map(|arr| {
-            arr.iter()
-                .filter_map(|e| e.get("id").and_then(|v| v.as_str()).map(String::from))
-                .collect()
-        })
-        .unwrap_or_default();
+    match serde_json::from_value::<fluxer_types::gateway::GatewayGuildEmojisUpdateData>(
+        data.clone(),
+    ) {
+        Ok(d) => DispatchEvent::GuildEmojisUpdate {
+            guild_id: d.guild_id,
+            emoji_ids: d.emojis.iter().map(|e| e.id.clone()).collect(),
+            emojis: d.emojis,
+        },
+        Err(_) => raw("GUILD_EMOJIS_UPDATE", data),
+    }
+}
 
-    DispatchEvent::GuildEmojisUpdate {
-        guild_id,
-        emoji_ids,
+fn parse_guild_stickers_update(data: &Value) -> DispatchEvent {
+    match serde_json::from_value::<fluxer_types::gateway::GatewayGuildStickersUpdateData>(
+        data.clone(),
+    ) {
+        Ok(d) => DispatchEvent::GuildStickersUpdate {
+            guild_id: d.guild_id,
+            stickers: d.stickers,
+        },
+        Err(_) => raw("GUILD_STICKERS_UPDATE", data),
+    }
+}
+
+fn parse_scheduled_event(event_name: &str, data: &Value) -> DispatchEvent {
+    let Ok(event) = serde_json::from_value::<fluxer_types::scheduled_event::ApiGuildScheduledEvent>(
+        data.clone(),
+    ) else {
+        return raw(event_name, data);
+    };
+    match event_name {
+        "GUILD_SCHEDULED_EVENT_CREATE" => DispatchEvent::GuildScheduledEventCreate { event },
+        "GUILD_SCHEDULED_EVENT_UPDATE" => DispatchEvent::GuildScheduledEventUpdate { event },
+        _ => DispatchEvent::GuildScheduledEventDelete { event },
+    }
+}
+
+fn parse_scheduled_event_user(event_name: &str, data: &Value) -> DispatchEvent {
+    let Ok(d) = serde_json::from_value::<
+        fluxer_types::scheduled_event::GatewayScheduledEventUserData,
+    >(data.clone()) else {
+        return raw(event_name, data);
+    };
+    if event_name == "GUILD_SCHEDULED_EVENT_USER_ADD" {
+        DispatchEvent::GuildScheduledEventUserAdd {
+            guild_id: d.guild_id,
+            event_id: d.guild_scheduled_event_id,
+            user_id: d.user_id,
+        }
+    } else {
+        DispatchEvent::GuildScheduledEventUserRemove {
+            guild_id: d.guild_id,
+            event_id: d.guild_scheduled_event_id,
+            user_id: d.user_id,
+        }
     }
 }
 
@@ -318,6 +377,74 @@ fn parse_channel_delete(data: &Value) -> DispatchEvent {
     }
 }
 
+fn parse_thread_create(data: &Value) -> DispatchEvent {
+    match serde_json::from_value::<fluxer_types::channel::ApiChannel>(data.clone()) {
+        Ok(api_ch) => DispatchEvent::ThreadCreate {
+            channel: Channel::from_api(&api_ch),
+        },
+        Err(_) => raw("THREAD_CREATE", data),
+    }
+}
+
+fn parse_thread_update(data: &Value) -> DispatchEvent {
+    match serde_json::from_value::<fluxer_types::channel::ApiChannel>(data.clone()) {
+        Ok(api_ch) => DispatchEvent::ThreadUpdate {
+            channel: Channel::from_api(&api_ch),
+        },
+        Err(_) => raw("THREAD_UPDATE", data),
+    }
+}
+
+fn parse_thread_delete(data: &Value) -> DispatchEvent {
+    match serde_json::from_value::<fluxer_types::gateway::GatewayThreadDeleteData>(data.clone()) {
+        Ok(d) => DispatchEvent::ThreadDelete {
+            id: d.id,
+            guild_id: d.guild_id,
+            parent_id: d.parent_id,
+        },
+        Err(_) => raw("THREAD_DELETE", data),
+    }
+}
+
+fn parse_thread_list_sync(data: &Value) -> DispatchEvent {
+    match serde_json::from_value::<fluxer_types::gateway::GatewayThreadListSyncData>(data.clone()) {
+        Ok(d) => DispatchEvent::ThreadListSync {
+            guild_id: d.guild_id,
+            channel_ids: d.channel_ids,
+            threads: d.threads.iter().map(Channel::from_api).collect(),
+            members: d.members,
+        },
+        Err(_) => raw("THREAD_LIST_SYNC", data),
+    }
+}
+
+fn parse_thread_member_update(data: &Value) -> DispatchEvent {
+    match serde_json::from_value::<fluxer_types::gateway::GatewayThreadMemberUpdateData>(
+        data.clone(),
+    ) {
+        Ok(d) => DispatchEvent::ThreadMemberUpdate {
+            guild_id: d.guild_id,
+            member: d.member,
+        },
+        Err(_) => raw("THREAD_MEMBER_UPDATE", data),
+    }
+}
+
+fn parse_thread_members_update(data: &Value) -> DispatchEvent {
+    match serde_json::from_value::<fluxer_types::gateway::GatewayThreadMembersUpdateData>(
+        data.clone(),
+    ) {
+        Ok(d) => DispatchEvent::ThreadMembersUpdate {
+            id: d.id,
+            guild_id: d.guild_id,
+            member_count: d.member_count,
+            added_members: d.added_members.unwrap_or_default(),
+            removed_member_ids: d.removed_member_ids.unwrap_or_default(),
+        },
+        Err(_) => raw("THREAD_MEMBERS_UPDATE", data),
+    }
+}
+
 fn parse_invite_create(data: &Value) -> DispatchEvent {
     match serde_json::from_value::<fluxer_types::invite::ApiInvite>(data.clone()) {
         Ok(api_inv) => DispatchEvent::InviteCreate {
@@ -350,6 +477,11 @@ fn parse_user_update(data: &Value) -> DispatchEvent {
 fn parse_typing_start(data: &Value) -> DispatchEvent {
     match serde_json::from_value::<fluxer_types::gateway::GatewayTypingStartData>(data.clone()) {
         Ok(d) => DispatchEvent::TypingStart {
+            member: d
+                .member
+                .as_ref()
+                .zip(d.guild_id.as_deref())
+                .map(|(member, guild_id)| GuildMember::from_api(member, guild_id)),
             channel_id: d.channel_id,
             user_id: d.user_id,
             guild_id: d.guild_id,
@@ -376,6 +508,19 @@ fn parse_voice_server_update(data: &Value) -> DispatchEvent {
     }
 }
 
+fn parse_voice_channel_status_update(data: &Value) -> DispatchEvent {
+    match serde_json::from_value::<fluxer_types::gateway::GatewayVoiceChannelStatusUpdateData>(
+        data.clone(),
+    ) {
+        Ok(d) => DispatchEvent::VoiceChannelStatusUpdate {
+            id: d.id,
+            guild_id: d.guild_id,
+            status: d.status,
+        },
+        Err(_) => raw("VOICE_CHANNEL_STATUS_UPDATE", data),
+    }
+}
+
 fn parse_presence_update(data: &Value) -> DispatchEvent {
     match serde_json::from_value::<fluxer_types::gateway::GatewayPresenceUpdateData>(data.clone()) {
         Ok(d) => DispatchEvent::PresenceUpdate { data: d },
@@ -383,6 +528,64 @@ fn parse_presence_update(data: &Value) -> DispatchEvent {
     }
 }
 
+fn parse_presences_replace(data: &Value) -> DispatchEvent {
+    match serde_json::from_value::<Vec<fluxer_types::gateway::GatewayPresenceUpdateData>>(
+        data.clone(),
+    ) {
+        Ok(presences) => DispatchEvent::PresencesReplace { presences },
+        Err(_) => raw("PRESENCES_REPLACE", data),
+    }
+}
+
+fn parse_guild_members_chunk(data: &Value) -> DispatchEvent {
+    match serde_json::from_value::<fluxer_types::gateway::GatewayGuildMembersChunkData>(
+        data.clone(),
+    ) {
+        Ok(d) => DispatchEvent::GuildMembersChunk {
+            members: d
+                .members
+                .iter()
+                .map(|m| GuildMember::from_api(m, &d.guild_id))
+                .collect(),
+            guild_id: d.guild_id,
+            chunk_index: d.chunk_index,
+            chunk_count: d.chunk_count,
+            nonce: d.nonce,
+        },
+        Err(_) => raw("GUILD_MEMBERS_CHUNK", data),
+    }
+}
+
+fn parse_poll_vote(data: &Value, added: bool) -> DispatchEvent {
+    let event_name = if added {
+        "MESSAGE_POLL_VOTE_ADD"
+    } else {
+        "MESSAGE_POLL_VOTE_REMOVE"
+    };
+    let Ok(d) =
+        serde_json::from_value::<fluxer_types::gateway::GatewayMessagePollVoteData>(data.clone())
+    else {
+        return raw(event_name, data);
+    };
+    if added {
+        DispatchEvent::MessagePollVoteAdd {
+            channel_id: d.channel_id,
+            message_id: d.message_id,
+            user_id: d.user_id,
+            guild_id: d.guild_id,
+            answer_id: d.answer_id,
+        }
+    } else {
+        DispatchEvent::MessagePollVoteRemove {
+            channel_id: d.channel_id,
+            message_id: d.message_id,
+            user_id: d.user_id,
+            guild_id: d.guild_id,
+            answer_id: d.answer_id,
+        }
+    }
+}
+
 fn parse_embedded_member(data: &Value) -> Option<GuildMember> {
     let member_val = data.get("member")?;
     let guild_id = data.get("guild_id").and_then(|v| v.as_str())?;
@@ -406,3 +609,68 @@ fn raw(event_name: &str, data: &Value) -> DispatchEvent {
         data: data.clone(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn decodes_a_voice_channel_status_being_set() {
+        let event = parse_dispatch(
+            "VOICE_CHANNEL_STATUS_UPDATE",
+            &json!({ "id": "1", "guild_id": "2", "status": "Listening party" }),
+        );
+        let DispatchEvent::VoiceChannelStatusUpdate {
+            id,
+            guild_id,
+            status,
+        } = event
+        else {
+            panic!("expected VoiceChannelStatusUpdate, got {event:?}");
+        };
+        assert_eq!(id, "1");
+        assert_eq!(guild_id, "2");
+        assert_eq!(status.as_deref(), Some("Listening party"));
+    }
+
+    #[test]
+    fn decodes_a_voice_channel_status_being_cleared() {
+        let event = parse_dispatch(
+            "VOICE_CHANNEL_STATUS_UPDATE",
+            &json!({ "id": "1", "guild_id": "2", "status": null }),
+        );
+        assert!(matches!(
+            event,
+            DispatchEvent::VoiceChannelStatusUpdate { status: None, .. }
+        ));
+    }
+
+    #[test]
+    fn decodes_an_emojis_update_with_typed_emojis() {
+        let event = parse_dispatch(
+            "GUILD_EMOJIS_UPDATE",
+            &json!({
+                "guild_id": "1",
+                "emojis": [
+                    { "id": "10", "name": "wave" },
+                    { "id": "11", "name": "dance", "animated": true },
+                ],
+            }),
+        );
+        let DispatchEvent::GuildEmojisUpdate {
+            guild_id,
+            emoji_ids,
+            emojis,
+        } = event
+        else {
+            panic!("expected GuildEmojisUpdate, got {event:?}");
+        };
+        assert_eq!(guild_id, "1");
+        assert_eq!(emoji_ids, ["10", "11"]);
+        assert_eq!(emojis[0].name, "wave");
+        assert!(!emojis[0].animated);
+        assert!(emojis[1].animated);
+    }
+}
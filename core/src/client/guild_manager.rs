@@ -1,6 +1,6 @@
 use dashmap::DashMap;
 
-use fluxer_types::guild::ApiGuild;
+use fluxer_types::guild::{ApiGuild, GuildCreateRequest};
 
 use crate::structures::guild::Guild;
 
@@ -25,6 +25,28 @@ impl<'a> GuildManager<'a> {
         Ok(guild)
     }
 
+    /// Like [`GuildManager::fetch`], also filling in the approximate member and presence counts.
+    pub async fn fetch_with_counts(&self, id: &str) -> crate::Result<Guild> {
+        let mut query = fluxer_types::QueryValues::new();
+        query.insert("with_counts", true);
+        let route = format!("{}{query}", fluxer_types::Routes::guild(id));
+        let data: ApiGuild = self.rest.get(&route).await?;
+        let guild = Guild::from_api(&data);
+        self.cache.insert(guild.id.clone(), guild.clone());
+        Ok(guild)
+    }
+
+    /// Creates a guild owned by the current user, e.g. from a `GuildLayoutBuilder`.
+    pub async fn create(&self, body: &GuildCreateRequest) -> crate::Result<Guild> {
+        let data: ApiGuild = self
+            .rest
+            .post(fluxer_types::Routes::guilds(), Some(body))
+            .await?;
+        let guild = Guild::from_api(&data);
+        self.cache.insert(guild.id.clone(), guild.clone());
+        Ok(guild)
+    }
+
     pub async fn resolve(&self, id: &str) -> crate::Result<Guild> {
         if let Some(g) = self.get(id) {
             return Ok(g);
@@ -1,9 +1,15 @@
+use chrono::{DateTime, Duration, Utc};
 use dashmap::DashMap;
 
-use fluxer_types::Snowflake;
+use fluxer_types::ban::CreateBanRequest;
+use fluxer_types::user::{ApiGuildMember, EditMemberRequest};
+use fluxer_types::{Patch, Snowflake};
 
 use crate::structures::guild_member::GuildMember;
 
+/// The longest timeout the API accepts.
+pub const MAX_TIMEOUT: Duration = Duration::days(28);
+
 pub struct GuildMemberManager {
     pub cache: DashMap<Snowflake, GuildMember>,
     pub guild_id: Snowflake,
@@ -60,17 +66,13 @@ impl GuildMemberManager {
         limit: Option<u32>,
         after: Option<&str>,
     ) -> crate::Result<Vec<GuildMember>> {
-        let mut route = fluxer_types::Routes::guild_members(&self.guild_id);
-        let mut params = Vec::new();
-        if let Some(l) = limit {
-            params.push(format!("limit={l}"));
-        }
-        if let Some(a) = after {
-            params.push(format!("after={a}"));
-        }
-        if !params.is_empty() {
-            route = format!("{route}?{}", params.join("&"));
-        }
+        let mut query = fluxer_types::QueryValues::new();
+        query.insert_opt("limit", limit);
+        query.insert_opt("after", after);
+        let route = format!(
+            "{}{query}",
+            fluxer_types::Routes::guild_members(&self.guild_id)
+        );
         let data: Vec<fluxer_types::user::ApiGuildMember> = rest.get(&route).await?;
         let members: Vec<GuildMember> = data
             .iter()
@@ -82,4 +84,228 @@ impl GuildMemberManager {
             .collect();
         Ok(members)
     }
+
+    pub async fn edit(
+        &self,
+        rest: &fluxer_rest::Rest,
+        user_id: &str,
+        body: &EditMemberRequest,
+        reason: Option<&str>,
+    ) -> crate::Result<GuildMember> {
+        let data: ApiGuildMember = rest
+            .patch_with_reason(
+                &fluxer_types::Routes::guild_member(&self.guild_id, user_id),
+                Some(body),
+                reason,
+            )
+            .await?;
+        let member = GuildMember::from_api(&data, &self.guild_id);
+        self.cache.insert(member.id.clone(), member.clone());
+        Ok(member)
+    }
+
+    pub async fn add_role(
+        &self,
+        rest: &fluxer_rest::Rest,
+        user_id: &str,
+        role_id: &str,
+        reason: Option<&str>,
+    ) -> crate::Result<()> {
+        rest.put_empty_with_reason(
+            &fluxer_types::Routes::guild_member_role(&self.guild_id, user_id, role_id),
+            reason,
+        )
+        .await?;
+        if let Some(mut member) = self.cache.get_mut(user_id)
+            && !member.has_role(role_id)
+        {
+            member.role_ids.push(role_id.to_string());
+        }
+        Ok(())
+    }
+
+    pub async fn remove_role(
+        &self,
+        rest: &fluxer_rest::Rest,
+        user_id: &str,
+        role_id: &str,
+        reason: Option<&str>,
+    ) -> crate::Result<()> {
+        rest.delete_with_reason(
+            &fluxer_types::Routes::guild_member_role(&self.guild_id, user_id, role_id),
+            reason,
+        )
+        .await?;
+        if let Some(mut member) = self.cache.get_mut(user_id) {
+            member.role_ids.retain(|r| r != role_id);
+        }
+        Ok(())
+    }
+
+    pub async fn kick(
+        &self,
+        rest: &fluxer_rest::Rest,
+        user_id: &str,
+        reason: Option<&str>,
+    ) -> crate::Result<()> {
+        rest.delete_with_reason(
+            &fluxer_types::Routes::guild_member(&self.guild_id, user_id),
+            reason,
+        )
+        .await?;
+        self.cache.remove(user_id);
+        Ok(())
+    }
+
+    /// Bans a user; `body.reason` is also sent as the audit log reason.
+    pub async fn ban(
+        &self,
+        rest: &fluxer_rest::Rest,
+        user_id: &str,
+        body: &CreateBanRequest,
+    ) -> crate::Result<()> {
+        let _: serde_json::Value = rest
+            .put_with_reason(
+                &fluxer_types::Routes::guild_ban(&self.guild_id, user_id),
+                Some(body),
+                body.reason.as_deref(),
+            )
+            .await?;
+        self.cache.remove(user_id);
+        Ok(())
+    }
+
+    pub async fn unban(
+        &self,
+        rest: &fluxer_rest::Rest,
+        user_id: &str,
+        reason: Option<&str>,
+    ) -> crate::Result<()> {
+        rest.delete_with_reason(
+            &fluxer_types::Routes::guild_ban(&self.guild_id, user_id),
+            reason,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Times a member out until `until`, which must be at most [`MAX_TIMEOUT`] from now.
+    pub async fn timeout(
+        &self,
+        rest: &fluxer_rest::Rest,
+        user_id: &str,
+        until: DateTime<Utc>,
+        reason: Option<&str>,
+    ) -> crate::Result<GuildMember> {
+        if until - Utc::now() > MAX_TIMEOUT {
+            return Err(crate::Error::TimeoutTooLong);
+        }
+        let body = EditMemberRequest {
+            communication_disabled_until: Patch::Value(until),
+            ..Default::default()
+        };
+        self.edit(rest, user_id, &body, reason).await
+    }
+
+    pub async fn remove_timeout(
+        &self,
+        rest: &fluxer_rest::Rest,
+        user_id: &str,
+        reason: Option<&str>,
+    ) -> crate::Result<GuildMember> {
+        let body = EditMemberRequest {
+            communication_disabled_until: Patch::Null,
+            ..Default::default()
+        };
+        self.edit(rest, user_id, &body, reason).await
+    }
+
+    /// [`timeout`](Self::timeout) when `until` is set, [`remove_timeout`](Self::remove_timeout)
+    /// otherwise.
+    pub async fn timeout_member(
+        &self,
+        rest: &fluxer_rest::Rest,
+        user_id: &str,
+        until: Option<DateTime<Utc>>,
+        reason: Option<&str>,
+    ) -> crate::Result<GuildMember> {
+        match until {
+            Some(until) => self.timeout(rest, user_id, until, reason).await,
+            None => self.remove_timeout(rest, user_id, reason).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fluxer_rest::{MockTransport, Rest, RestOptions};
+    use serde_json::json;
+
+    use super::*;
+
+    fn member(until: Option<DateTime<Utc>>) -> serde_json::Value {
+        json!({
+            "user": { "id": "2", "username": "u", "discriminator": "0" },
+            "roles": [],
+            "communication_disabled_until": until.map(|t| t.to_rfc3339()),
+        })
+    }
+
+    #[tokio::test]
+    async fn sets_a_future_timeout() {
+        let mock = MockTransport::new();
+        let rest = Rest::with_transport(RestOptions::default(), mock.clone());
+        let until = Utc::now() + Duration::hours(1);
+        mock.push_json(200, &member(Some(until)));
+
+        let member = GuildMemberManager::new("1")
+            .timeout_member(&rest, "2", Some(until), Some("spam"))
+            .await
+            .unwrap();
+
+        assert!(member.is_timed_out());
+        let req = mock.last_request().unwrap();
+        assert_eq!(req.method.as_str(), "PATCH");
+        assert!(req.url.ends_with("/guilds/1/members/2"));
+        let sent: DateTime<Utc> =
+            serde_json::from_value(req.json().unwrap()["communication_disabled_until"].clone())
+                .unwrap();
+        assert_eq!(sent, until);
+    }
+
+    #[tokio::test]
+    async fn clears_a_timeout_with_an_explicit_null() {
+        let mock = MockTransport::new();
+        let rest = Rest::with_transport(RestOptions::default(), mock.clone());
+        mock.push_json(200, &member(None));
+
+        let member = GuildMemberManager::new("1")
+            .timeout_member(&rest, "2", None, None)
+            .await
+            .unwrap();
+
+        assert!(!member.is_timed_out());
+        assert_eq!(
+            mock.last_request().unwrap().json().unwrap(),
+            json!({ "communication_disabled_until": null })
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_a_timeout_past_the_maximum() {
+        let mock = MockTransport::new();
+        let rest = Rest::with_transport(RestOptions::default(), mock.clone());
+
+        let result = GuildMemberManager::new("1")
+            .timeout(
+                &rest,
+                "2",
+                Utc::now() + MAX_TIMEOUT + Duration::hours(1),
+                None,
+            )
+            .await;
+
+        assert!(matches!(result, Err(crate::Error::TimeoutTooLong)));
+        assert!(mock.requests().is_empty());
+    }
 }
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Returned by [`Client::on`](crate::Client::on), [`Client::on_typed`](crate::Client::on_typed)
+/// and [`Client::on_typed_ordered`](crate::Client::on_typed_ordered).
+///
+/// Cloneable, so a handler can be unregistered from anywhere, including from inside an event
+/// handler while [`Client::login`](crate::Client::login) is running.
+#[derive(Debug, Clone)]
+pub struct HandlerHandle {
+    active: Arc<AtomicBool>,
+}
+
+impl HandlerHandle {
+    /// Stops the handler from receiving further events.
+    pub fn unregister(&self) {
+        self.active.store(false, Ordering::Release);
+    }
+
+    pub fn is_registered(&self) -> bool {
+        self.active.load(Ordering::Acquire)
+    }
+}
+
+pub(crate) struct Registered<C> {
+    active: Arc<AtomicBool>,
+    pub(crate) callback: C,
+}
+
+impl<C> Registered<C> {
+    pub(crate) fn new(callback: C) -> (Self, HandlerHandle) {
+        let active = Arc::new(AtomicBool::new(true));
+        let handle = HandlerHandle {
+            active: active.clone(),
+        };
+        (Self { active, callback }, handle)
+    }
+
+    pub(crate) fn is_active(&self) -> bool {
+        self.active.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn unregister(&self) {
+        self.active.store(false, Ordering::Release);
+    }
+}
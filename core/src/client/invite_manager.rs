@@ -0,0 +1,31 @@
+use fluxer_types::invite::{ApiInvite, GetInviteQuery};
+
+use crate::structures::invite::Invite;
+
+pub struct InviteManager<'a> {
+    rest: &'a fluxer_rest::Rest,
+}
+
+impl<'a> InviteManager<'a> {
+    pub fn new(rest: &'a fluxer_rest::Rest) -> Self {
+        Self { rest }
+    }
+
+    /// Resolves an invite code; fails with a 404 API error if the invite is invalid or expired.
+    pub async fn fetch(&self, code: &str, query: &GetInviteQuery) -> crate::Result<Invite> {
+        let route = format!(
+            "{}{}",
+            fluxer_types::Routes::invite(code),
+            query.to_query_string()
+        );
+        let data: ApiInvite = self.rest.get(&route).await?;
+        Ok(Invite::from_api(&data))
+    }
+
+    pub async fn delete(&self, code: &str, reason: Option<&str>) -> crate::Result<()> {
+        self.rest
+            .delete_with_reason(&fluxer_types::Routes::invite(code), reason)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use dashmap::DashMap;
+use tokio::sync::{RwLock, mpsc};
+
+use fluxer_types::Snowflake;
+use fluxer_types::gateway::{
+    GatewayGuildMembersChunkData, GatewayOpcode, GatewayRequestGuildMembersData,
+};
+use fluxer_types::user::ApiGuildMember;
+use fluxer_ws::WebSocketManager;
+
+use crate::structures::guild_member::GuildMember;
+
+/// `GUILD_MEMBERS` gateway intent; member chunks are only sent when it was identified with.
+pub const GUILD_MEMBERS_INTENT: u64 = fluxer_util::GatewayIntents::GUILD_MEMBERS.bits();
+
+/// How long to wait for the next chunk before giving up on a request.
+pub const MEMBER_CHUNK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Most user ids a single member request may filter by; larger inputs are split.
+pub const MEMBER_REQUEST_USER_IDS_MAX: usize = 100;
+
+type GatewaySlot = Arc<std::sync::RwLock<Option<Arc<RwLock<WebSocketManager>>>>>;
+
+/// Requests guild members over the gateway (op 8) and waits for every chunk to arrive.
+///
+/// Chunks are cached by the client's event loop; this handle only tracks completion, so it can
+/// be cloned into event handlers while [`Client::login`](crate::Client::login) is running.
+#[derive(Clone)]
+pub struct MemberChunker {
+    intents: u64,
+    gateway: GatewaySlot,
+    waiters: Arc<DashMap<String, mpsc::UnboundedSender<GatewayGuildMembersChunkData>>>,
+}
+
+impl MemberChunker {
+    pub(crate) fn new(intents: u64) -> Self {
+        Self {
+            intents,
+            gateway: Arc::new(std::sync::RwLock::new(None)),
+            waiters: Arc::new(DashMap::new()),
+        }
+    }
+
+    pub(crate) fn set_gateway(&self, gateway: Option<Arc<RwLock<WebSocketManager>>>) {
+        *self.gateway.write().unwrap() = gateway;
+    }
+
+    /// Routes a `GUILD_MEMBERS_CHUNK` to the request that asked for it, if any.
+    pub(crate) fn handle_chunk(&self, chunk: GatewayGuildMembersChunkData) {
+        let Some(nonce) = chunk.nonce.clone() else {
+            return;
+        };
+        if let Some(tx) = self.waiters.get(&nonce) {
+            let _ = tx.send(chunk);
+        }
+    }
+
+    /// Requests every member of `guild_id` and resolves once the last chunk has been cached,
+    /// returning the number of members received.
+    pub async fn warm_guild(&self, guild_id: &str) -> crate::Result<usize> {
+        if self.intents & GUILD_MEMBERS_INTENT == 0 {
+            return Err(crate::Error::MissingIntent("GUILD_MEMBERS"));
+        }
+        let gateway = self
+            .gateway
+            .read()
+            .unwrap()
+            .clone()
+            .ok_or(crate::Error::ClientNotReady)?;
+
+        let members = self
+            .request(
+                &gateway,
+                GatewayRequestGuildMembersData {
+                    guild_id: guild_id.to_string(),
+                    query: Some(String::new()),
+                    limit: 0,
+                    user_ids: Vec::new(),
+                    nonce: None,
+                },
+            )
+            .await?;
+        Ok(members.len())
+    }
+
+    /// Resolves specific members of `guild_id` over the gateway, sending one request per
+    /// [`MEMBER_REQUEST_USER_IDS_MAX`] ids. Ids that are not members are left out of the result.
+    pub async fn fetch_members(
+        &self,
+        guild_id: &str,
+        user_ids: &[Snowflake],
+    ) -> crate::Result<Vec<GuildMember>> {
+        let gateway = self
+            .gateway
+            .read()
+            .unwrap()
+            .clone()
+            .ok_or(crate::Error::ClientNotReady)?;
+
+        let mut members = Vec::with_capacity(user_ids.len());
+        for ids in user_ids.chunks(MEMBER_REQUEST_USER_IDS_MAX) {
+            let request = GatewayRequestGuildMembersData {
+                guild_id: guild_id.to_string(),
+                query: None,
+                limit: 0,
+                user_ids: ids.to_vec(),
+                nonce: None,
+            };
+            let chunk = self.request(&gateway, request).await?;
+            members.extend(chunk.iter().map(|m| GuildMember::from_api(m, guild_id)));
+        }
+        Ok(members)
+    }
+
+    async fn request(
+        &self,
+        gateway: &RwLock<WebSocketManager>,
+        mut request: GatewayRequestGuildMembersData,
+    ) -> crate::Result<Vec<ApiGuildMember>> {
+        let nonce = fluxer_util::Nonce::generate();
+        request.nonce = Some(nonce.clone());
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        self.waiters.insert(nonce.clone(), tx);
+        let result = self.request_and_wait(gateway, request, &mut rx).await;
+        self.waiters.remove(&nonce);
+        result
+    }
+
+    async fn request_and_wait(
+        &self,
+        gateway: &RwLock<WebSocketManager>,
+        request: GatewayRequestGuildMembersData,
+        rx: &mut mpsc::UnboundedReceiver<GatewayGuildMembersChunkData>,
+    ) -> crate::Result<Vec<ApiGuildMember>> {
+        let guild_id = request.guild_id.clone();
+        let payload = serde_json::json!({
+            "op": GatewayOpcode::RequestGuildMembers as u8,
+            "d": request,
+        });
+        {
+            let manager = gateway.read().await;
+            let total = manager
+                .shard_identities()
+                .first()
+                .map(|identity| identity.total())
+                .unwrap_or(1);
+            let shard_id = guild_id
+                .parse::<u64>()
+                .map(|id| ((id >> 22) % total as u64) as u32)
+                .unwrap_or(0);
+            manager
+                .send(shard_id, payload)
+                .await
+                .map_err(crate::Error::WebSocket)?;
+        }
+
+        let mut received = Vec::new();
+        loop {
+            let chunk = tokio::time::timeout(MEMBER_CHUNK_TIMEOUT, rx.recv())
+                .await
+                .ok()
+                .flatten()
+                .ok_or_else(|| crate::Error::MemberChunkTimeout(guild_id.clone()))?;
+            received.extend(chunk.members);
+            if chunk.chunk_index + 1 >= chunk.chunk_count {
+                return Ok(received);
+            }
+        }
+    }
+}
@@ -1,13 +1,29 @@
+mod application_command_manager;
+mod auth_manager;
 mod channel_manager;
 mod client_impl;
 mod event_parser;
 mod guild_manager;
 mod guild_member_manager;
+mod handler;
+mod invite_manager;
+mod member_chunker;
+mod resolver;
+mod scheduled_event_manager;
 pub mod typed_events;
 mod users_manager;
+mod worker_pool;
 
+pub use application_command_manager::*;
+pub use auth_manager::*;
 pub use channel_manager::*;
 pub use client_impl::*;
 pub use guild_manager::*;
 pub use guild_member_manager::GuildMemberManager;
+pub use handler::HandlerHandle;
+pub use invite_manager::*;
+pub use member_chunker::*;
+pub use resolver::*;
+pub use scheduled_event_manager::*;
 pub use users_manager::*;
+pub use worker_pool::*;
@@ -0,0 +1,33 @@
+use crate::client::{ChannelManager, Client, GuildManager, UsersManager};
+use crate::structures::channel::Channel;
+use crate::structures::guild::Guild;
+use crate::structures::user::User;
+
+/// Cache-first lookups that fall back to the REST API and cache what they fetch.
+pub struct Resolver<'a> {
+    client: &'a Client,
+}
+
+impl<'a> Resolver<'a> {
+    pub fn new(client: &'a Client) -> Self {
+        Self { client }
+    }
+
+    pub async fn resolve_user(&self, id: &str) -> crate::Result<User> {
+        UsersManager::new(&self.client.users, &self.client.rest)
+            .resolve(id)
+            .await
+    }
+
+    pub async fn resolve_channel(&self, id: &str) -> crate::Result<Channel> {
+        ChannelManager::new(&self.client.channels, &self.client.rest)
+            .resolve(id)
+            .await
+    }
+
+    pub async fn resolve_guild(&self, id: &str) -> crate::Result<Guild> {
+        GuildManager::new(&self.client.guilds, &self.client.rest)
+            .resolve(id)
+            .await
+    }
+}
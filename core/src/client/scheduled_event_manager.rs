@@ -0,0 +1,105 @@
+use fluxer_types::scheduled_event::{
+    ApiGuildScheduledEvent, ApiScheduledEventUser, CreateScheduledEventRequest,
+    ScheduledEventUsersQuery, UpdateScheduledEventRequest,
+};
+
+pub struct ScheduledEventManager<'a> {
+    rest: &'a fluxer_rest::Rest,
+}
+
+impl<'a> ScheduledEventManager<'a> {
+    pub fn new(rest: &'a fluxer_rest::Rest) -> Self {
+        Self { rest }
+    }
+
+    pub async fn list(
+        &self,
+        guild_id: &str,
+        with_user_count: bool,
+    ) -> crate::Result<Vec<ApiGuildScheduledEvent>> {
+        let mut route = fluxer_types::Routes::guild_scheduled_events(guild_id);
+        route.push_str(&with_user_count_query(with_user_count).to_query_string());
+        let data: Vec<ApiGuildScheduledEvent> = self.rest.get(&route).await?;
+        Ok(data)
+    }
+
+    pub async fn fetch(
+        &self,
+        guild_id: &str,
+        event_id: &str,
+        with_user_count: bool,
+    ) -> crate::Result<ApiGuildScheduledEvent> {
+        let mut route = fluxer_types::Routes::guild_scheduled_event(guild_id, event_id);
+        route.push_str(&with_user_count_query(with_user_count).to_query_string());
+        let data: ApiGuildScheduledEvent = self.rest.get(&route).await?;
+        Ok(data)
+    }
+
+    pub async fn create(
+        &self,
+        guild_id: &str,
+        body: &CreateScheduledEventRequest,
+        reason: Option<&str>,
+    ) -> crate::Result<ApiGuildScheduledEvent> {
+        let data: ApiGuildScheduledEvent = self
+            .rest
+            .post_with_reason(
+                &fluxer_types::Routes::guild_scheduled_events(guild_id),
+                Some(body),
+                reason,
+            )
+            .await?;
+        Ok(data)
+    }
+
+    pub async fn edit(
+        &self,
+        guild_id: &str,
+        event_id: &str,
+        body: &UpdateScheduledEventRequest,
+        reason: Option<&str>,
+    ) -> crate::Result<ApiGuildScheduledEvent> {
+        let data: ApiGuildScheduledEvent = self
+            .rest
+            .patch_with_reason(
+                &fluxer_types::Routes::guild_scheduled_event(guild_id, event_id),
+                Some(body),
+                reason,
+            )
+            .await?;
+        Ok(data)
+    }
+
+    pub async fn delete(&self, guild_id: &str, event_id: &str) -> crate::Result<()> {
+        self.rest
+            .delete_route(&fluxer_types::Routes::guild_scheduled_event(
+                guild_id, event_id,
+            ))
+            .await?;
+        Ok(())
+    }
+
+    /// Users subscribed to the event.
+    pub async fn fetch_users(
+        &self,
+        guild_id: &str,
+        event_id: &str,
+        query: &ScheduledEventUsersQuery,
+    ) -> crate::Result<Vec<ApiScheduledEventUser>> {
+        let route = format!(
+            "{}{}",
+            fluxer_types::Routes::guild_scheduled_event_users(guild_id, event_id),
+            query.to_query_string()
+        );
+        let data: Vec<ApiScheduledEventUser> = self.rest.get(&route).await?;
+        Ok(data)
+    }
+}
+
+fn with_user_count_query(with_user_count: bool) -> fluxer_types::QueryValues {
+    let mut query = fluxer_types::QueryValues::new();
+    if with_user_count {
+        query.insert("with_user_count", true);
+    }
+    query
+}
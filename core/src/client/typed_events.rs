@@ -8,6 +8,7 @@ use crate::structures::message_reaction::MessageReaction;
 use crate::structures::role::Role;
 use crate::structures::user::User;
 
+use chrono::{DateTime, Utc};
 use fluxer_types::Snowflake;
 
 #[derive(Debug, Clone)]
@@ -122,6 +123,40 @@ pub enum DispatchEvent {
         channel: Channel,
     },
 
+    ThreadCreate {
+        channel: Channel,
+    },
+
+    ThreadUpdate {
+        channel: Channel,
+    },
+
+    ThreadDelete {
+        id: Snowflake,
+        guild_id: Option<Snowflake>,
+        parent_id: Option<Snowflake>,
+    },
+
+    ThreadListSync {
+        guild_id: Snowflake,
+        channel_ids: Option<Vec<Snowflake>>,
+        threads: Vec<Channel>,
+        members: Vec<fluxer_types::channel::ApiThreadMember>,
+    },
+
+    ThreadMemberUpdate {
+        guild_id: Option<Snowflake>,
+        member: fluxer_types::channel::ApiThreadMember,
+    },
+
+    ThreadMembersUpdate {
+        id: Snowflake,
+        guild_id: Snowflake,
+        member_count: u32,
+        added_members: Vec<fluxer_types::channel::ApiThreadMember>,
+        removed_member_ids: Vec<Snowflake>,
+    },
+
     InviteCreate {
         invite: Invite,
     },
@@ -140,7 +175,8 @@ pub enum DispatchEvent {
         channel_id: Snowflake,
         user_id: Snowflake,
         guild_id: Option<Snowflake>,
-        timestamp: u64,
+        timestamp: DateTime<Utc>,
+        member: Option<GuildMember>,
     },
 
     VoiceStateUpdate {
@@ -151,19 +187,101 @@ pub enum DispatchEvent {
         data: fluxer_types::gateway::GatewayVoiceServerUpdateData,
     },
 
+    VoiceChannelStatusUpdate {
+        id: Snowflake,
+        guild_id: Snowflake,
+        status: Option<String>,
+    },
+
     PresenceUpdate {
         data: fluxer_types::gateway::GatewayPresenceUpdateData,
     },
 
+    PresencesReplace {
+        presences: Vec<fluxer_types::gateway::GatewayPresenceUpdateData>,
+    },
+
+    GuildMembersChunk {
+        guild_id: Snowflake,
+        members: Vec<GuildMember>,
+        chunk_index: u32,
+        chunk_count: u32,
+        nonce: Option<String>,
+    },
+
+    MessagePollVoteAdd {
+        channel_id: Snowflake,
+        message_id: Snowflake,
+        user_id: Snowflake,
+        guild_id: Option<Snowflake>,
+        answer_id: u32,
+    },
+
+    MessagePollVoteRemove {
+        channel_id: Snowflake,
+        message_id: Snowflake,
+        user_id: Snowflake,
+        guild_id: Option<Snowflake>,
+        answer_id: u32,
+    },
+
     GuildEmojisUpdate {
         guild_id: Snowflake,
         emoji_ids: Vec<Snowflake>,
+        emojis: Vec<fluxer_types::emoji::ApiEmoji>,
+    },
+
+    GuildStickersUpdate {
+        guild_id: Snowflake,
+        stickers: Vec<fluxer_types::sticker::ApiSticker>,
+    },
+
+    GuildScheduledEventCreate {
+        event: fluxer_types::scheduled_event::ApiGuildScheduledEvent,
+    },
+
+    GuildScheduledEventUpdate {
+        event: fluxer_types::scheduled_event::ApiGuildScheduledEvent,
+    },
+
+    GuildScheduledEventDelete {
+        event: fluxer_types::scheduled_event::ApiGuildScheduledEvent,
+    },
+
+    GuildScheduledEventUserAdd {
+        guild_id: Snowflake,
+        event_id: Snowflake,
+        user_id: Snowflake,
+    },
+
+    GuildScheduledEventUserRemove {
+        guild_id: Snowflake,
+        event_id: Snowflake,
+        user_id: Snowflake,
     },
 
     InteractionCreate {
         data: serde_json::Value,
     },
 
+    /// The gateway invalidated a shard's session; a new IDENTIFY follows unless `resumable`.
+    InvalidSession {
+        shard_id: u32,
+        resumable: bool,
+    },
+
+    /// The gateway asked a shard to reconnect.
+    Reconnect {
+        shard_id: u32,
+    },
+
+    /// A shard's socket closed; `reason` is the text from the close frame, if any.
+    ShardClose {
+        shard_id: u32,
+        code: u16,
+        reason: String,
+    },
+
     Debug {
         message: String,
     },
@@ -177,3 +295,15 @@ pub enum DispatchEvent {
         data: serde_json::Value,
     },
 }
+
+/// Session lifecycle of a shard, delivered through [`Client::subscribe_session`](crate::Client::subscribe_session).
+///
+/// After a RESUME the gateway replays missed dispatches, so state derived from earlier events is
+/// still valid. After a [`SessionEvent::FreshReady`] nothing is replayed and that state may be
+/// stale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionEvent {
+    Resumed { shard_id: u32 },
+    Invalidated { shard_id: u32, resumable: bool },
+    FreshReady { shard_id: u32 },
+}
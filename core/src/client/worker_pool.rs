@@ -0,0 +1,121 @@
+use std::collections::hash_map::DefaultHasher;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+
+use super::typed_events::DispatchEvent;
+
+/// Runs a typed dispatch handler across a fixed set of worker tasks.
+///
+/// Events are routed by ordering key (usually the guild id), so events sharing a key are always
+/// handled by the same worker, one at a time and in arrival order. Events without a key go to
+/// the first worker.
+pub struct DispatchWorkerPool {
+    senders: Vec<mpsc::UnboundedSender<DispatchEvent>>,
+}
+
+impl DispatchWorkerPool {
+    /// Spawns `worker_count` workers (at least one); must be called within a Tokio runtime.
+    pub fn new<F, Fut>(worker_count: usize, handler: F) -> Self
+    where
+        F: Fn(DispatchEvent) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let handler = Arc::new(handler);
+        let senders = (0..worker_count.max(1))
+            .map(|_| {
+                let (tx, mut rx) = mpsc::unbounded_channel::<DispatchEvent>();
+                let handler = handler.clone();
+                tokio::spawn(async move {
+                    while let Some(event) = rx.recv().await {
+                        handler(event).await;
+                    }
+                });
+                tx
+            })
+            .collect();
+        Self { senders }
+    }
+
+    pub fn worker_count(&self) -> usize {
+        self.senders.len()
+    }
+
+    pub fn worker_for(&self, key: Option<&str>) -> usize {
+        match key {
+            Some(key) => {
+                let mut hasher = DefaultHasher::new();
+                key.hash(&mut hasher);
+                (hasher.finish() % self.senders.len() as u64) as usize
+            }
+            None => 0,
+        }
+    }
+
+    pub fn dispatch(&self, key: Option<&str>, event: DispatchEvent) {
+        let _ = self.senders[self.worker_for(key)].send(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use super::*;
+
+    fn bulk_delete(guild_id: &str, seq: usize) -> DispatchEvent {
+        DispatchEvent::MessageDeleteBulk {
+            ids: vec![seq.to_string()],
+            channel_id: "1".to_string(),
+            guild_id: Some(guild_id.to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn keeps_per_guild_order_across_workers() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let pool = DispatchWorkerPool::new(4, move |event| {
+            let tx = tx.clone();
+            async move {
+                if let DispatchEvent::MessageDeleteBulk { ids, guild_id, .. } = event {
+                    let seq: usize = ids[0].parse().unwrap();
+                    // Uneven handler latency so workers interleave.
+                    tokio::time::sleep(Duration::from_millis((seq % 3) as u64)).await;
+                    let _ = tx.send((guild_id.unwrap(), seq));
+                }
+            }
+        });
+        let guilds = ["10", "20", "30", "40"];
+        for seq in 0..20 {
+            for guild in guilds {
+                pool.dispatch(Some(guild), bulk_delete(guild, seq));
+            }
+        }
+
+        let mut seen: HashMap<String, Vec<usize>> = HashMap::new();
+        for _ in 0..guilds.len() * 20 {
+            let (guild, seq) = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            seen.entry(guild).or_default().push(seq);
+        }
+        for guild in guilds {
+            assert_eq!(seen[guild], (0..20).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn routes_a_key_to_one_worker() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let _guard = runtime.enter();
+        let pool = DispatchWorkerPool::new(4, |_| async {});
+        assert_eq!(pool.worker_count(), 4);
+        assert_eq!(pool.worker_for(Some("10")), pool.worker_for(Some("10")));
+        assert_eq!(pool.worker_for(None), 0);
+        assert_eq!(DispatchWorkerPool::new(0, |_| async {}).worker_count(), 1);
+    }
+}
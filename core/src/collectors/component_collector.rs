@@ -0,0 +1,62 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::oneshot;
+
+use fluxer_types::interaction::INTERACTION_TYPE_MESSAGE_COMPONENT;
+
+use crate::structures::interaction::Interaction;
+
+pub type ComponentFilter = Box<dyn Fn(&Interaction) -> bool + Send + Sync>;
+
+struct Waiter {
+    message_id: String,
+    filter: Option<ComponentFilter>,
+    tx: oneshot::Sender<Interaction>,
+}
+
+/// Hands component interactions to whoever is waiting on the message they were clicked on.
+///
+/// Clones share the same waiters, so a handle can be moved into event handlers.
+#[derive(Clone, Default)]
+pub struct ComponentAwaiter {
+    waiters: Arc<Mutex<Vec<Waiter>>>,
+}
+
+impl ComponentAwaiter {
+    /// Resolves with the first component interaction on `message_id` that passes `filter`, or
+    /// `None` once `time` elapses.
+    pub async fn await_component(
+        &self,
+        message_id: &str,
+        time: Duration,
+        filter: Option<ComponentFilter>,
+    ) -> Option<Interaction> {
+        let (tx, rx) = oneshot::channel();
+        self.waiters.lock().unwrap().push(Waiter {
+            message_id: message_id.to_string(),
+            filter,
+            tx,
+        });
+        tokio::time::timeout(time, rx).await.ok()?.ok()
+    }
+
+    /// Offers an interaction to the first matching waiter; returns whether one took it.
+    pub(crate) fn dispatch(&self, interaction: &Interaction) -> bool {
+        if interaction.kind != INTERACTION_TYPE_MESSAGE_COMPONENT {
+            return false;
+        }
+        let Some(message_id) = interaction.message_id.as_deref() else {
+            return false;
+        };
+        let mut waiters = self.waiters.lock().unwrap();
+        waiters.retain(|w| !w.tx.is_closed());
+        let position = waiters.iter().position(|w| {
+            w.message_id == message_id && w.filter.as_ref().is_none_or(|f| f(interaction))
+        });
+        match position {
+            Some(i) => waiters.remove(i).tx.send(interaction.clone()).is_ok(),
+            None => false,
+        }
+    }
+}
@@ -1,5 +1,7 @@
+pub mod component_collector;
 pub mod message_collector;
 pub mod reaction_collector;
 
+pub use component_collector::*;
 pub use message_collector::*;
 pub use reaction_collector::*;
@@ -34,6 +34,37 @@ pub enum Error {
     #[error("webhook token required to send")]
     WebhookTokenRequired,
 
+    #[error("timeouts cannot be longer than 28 days")]
+    TimeoutTooLong,
+
+    #[error("thread_name and thread_id cannot both be set")]
+    WebhookThreadConflict,
+
+    #[error("message {0} is older than 14 days and cannot be bulk deleted")]
+    MessageTooOld(String),
+
+    #[error("added {added} reactions before failing: {source}")]
+    ReactionsIncomplete {
+        added: usize,
+        #[source]
+        source: Box<Error>,
+    },
+
+    #[error("privileged intents {intents:?} are not enabled for this application")]
+    DisallowedIntents {
+        intents: fluxer_util::GatewayIntents,
+        close_code: Option<u16>,
+    },
+
+    #[error("the {0} intent is required")]
+    MissingIntent(&'static str),
+
+    #[error("timed out waiting for member chunks of guild {0}")]
+    MemberChunkTimeout(String),
+
+    #[error("deferred task failed: {0}")]
+    DeferredTask(Box<dyn std::error::Error + Send + Sync>),
+
     #[error("API error: {0}")]
     Api(#[from] fluxer_rest::FluxerApiError),
 
@@ -46,6 +77,16 @@ pub enum Error {
     #[error("REST error: {0}")]
     Rest(#[from] fluxer_rest::RestError),
 
+    #[error("gateway error: {0}")]
+    Gateway(#[from] fluxer_ws::GatewayError),
+
+    #[error("shard {shard_id} closed with {code} and won't reconnect: {reason}")]
+    GatewayClosed {
+        shard_id: u32,
+        code: u16,
+        reason: String,
+    },
+
     #[error("WebSocket error: {0}")]
     WebSocket(String),
 
@@ -54,3 +95,35 @@ pub enum Error {
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// Whether retrying the operation may succeed.
+    ///
+    /// True for retryable REST errors (see [`fluxer_rest::RestError::is_retryable`]: 429, 5xx
+    /// and transport failures), dropped WebSocket connections and member chunk timeouts.
+    /// Everything else, including fatal gateway closes, needs a change before retrying.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Rest(err) => err.is_retryable(),
+            Self::RateLimit(_) => true,
+            Self::Api(err) => err.status_code == 429 || err.status_code >= 500,
+            Self::Http(err) => err.status_code == 429 || err.status_code >= 500,
+            Self::Gateway(fluxer_ws::GatewayError::Rest(err)) => err.is_retryable(),
+            Self::WebSocket(_) | Self::MemberChunkTimeout(_) => true,
+            Self::ReactionsIncomplete { source, .. } => source.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// The delay a rate limit asked for, if this error is a 429.
+    pub fn retry_after(&self) -> Option<std::time::Duration> {
+        match self {
+            Self::Rest(err) => err.retry_after(),
+            Self::RateLimit(err) => std::time::Duration::try_from_secs_f64(err.retry_after).ok(),
+            Self::Http(err) => err.retry_after(),
+            Self::Gateway(fluxer_ws::GatewayError::Rest(err)) => err.retry_after(),
+            Self::ReactionsIncomplete { source, .. } => source.retry_after(),
+            _ => None,
+        }
+    }
+}
@@ -11,6 +11,8 @@ impl Events {
     pub const MESSAGE_REACTION_REMOVE: &str = "MESSAGE_REACTION_REMOVE";
     pub const MESSAGE_REACTION_REMOVE_ALL: &str = "MESSAGE_REACTION_REMOVE_ALL";
     pub const MESSAGE_REACTION_REMOVE_EMOJI: &str = "MESSAGE_REACTION_REMOVE_EMOJI";
+    pub const MESSAGE_POLL_VOTE_ADD: &str = "MESSAGE_POLL_VOTE_ADD";
+    pub const MESSAGE_POLL_VOTE_REMOVE: &str = "MESSAGE_POLL_VOTE_REMOVE";
     pub const GUILD_CREATE: &str = "GUILD_CREATE";
     pub const GUILD_UPDATE: &str = "GUILD_UPDATE";
     pub const GUILD_DELETE: &str = "GUILD_DELETE";
@@ -26,16 +28,29 @@ impl Events {
     pub const GUILD_EMOJIS_UPDATE: &str = "GUILD_EMOJIS_UPDATE";
     pub const GUILD_STICKERS_UPDATE: &str = "GUILD_STICKERS_UPDATE";
     pub const GUILD_INTEGRATIONS_UPDATE: &str = "GUILD_INTEGRATIONS_UPDATE";
+    pub const GUILD_SCHEDULED_EVENT_CREATE: &str = "GUILD_SCHEDULED_EVENT_CREATE";
+    pub const GUILD_SCHEDULED_EVENT_UPDATE: &str = "GUILD_SCHEDULED_EVENT_UPDATE";
+    pub const GUILD_SCHEDULED_EVENT_DELETE: &str = "GUILD_SCHEDULED_EVENT_DELETE";
+    pub const GUILD_SCHEDULED_EVENT_USER_ADD: &str = "GUILD_SCHEDULED_EVENT_USER_ADD";
+    pub const GUILD_SCHEDULED_EVENT_USER_REMOVE: &str = "GUILD_SCHEDULED_EVENT_USER_REMOVE";
     pub const CHANNEL_CREATE: &str = "CHANNEL_CREATE";
     pub const CHANNEL_UPDATE: &str = "CHANNEL_UPDATE";
     pub const CHANNEL_DELETE: &str = "CHANNEL_DELETE";
     pub const CHANNEL_PINS_UPDATE: &str = "CHANNEL_PINS_UPDATE";
+    pub const THREAD_CREATE: &str = "THREAD_CREATE";
+    pub const THREAD_UPDATE: &str = "THREAD_UPDATE";
+    pub const THREAD_DELETE: &str = "THREAD_DELETE";
+    pub const THREAD_LIST_SYNC: &str = "THREAD_LIST_SYNC";
+    pub const THREAD_MEMBER_UPDATE: &str = "THREAD_MEMBER_UPDATE";
+    pub const THREAD_MEMBERS_UPDATE: &str = "THREAD_MEMBERS_UPDATE";
     pub const INVITE_CREATE: &str = "INVITE_CREATE";
     pub const INVITE_DELETE: &str = "INVITE_DELETE";
     pub const TYPING_START: &str = "TYPING_START";
     pub const VOICE_STATE_UPDATE: &str = "VOICE_STATE_UPDATE";
     pub const VOICE_SERVER_UPDATE: &str = "VOICE_SERVER_UPDATE";
+    pub const VOICE_CHANNEL_STATUS_UPDATE: &str = "VOICE_CHANNEL_STATUS_UPDATE";
     pub const PRESENCE_UPDATE: &str = "PRESENCE_UPDATE";
+    pub const PRESENCES_REPLACE: &str = "PRESENCES_REPLACE";
     pub const WEBHOOKS_UPDATE: &str = "WEBHOOKS_UPDATE";
     pub const INTERACTION_CREATE: &str = "INTERACTION_CREATE";
     pub const USER_UPDATE: &str = "USER_UPDATE";
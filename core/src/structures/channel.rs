@@ -1,7 +1,25 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, Utc};
 use fluxer_types::Snowflake;
-use fluxer_types::channel::{ApiChannel, ChannelType};
+use fluxer_types::channel::{
+    ApiChannel, ApiForumThread, ApiThreadList, ApiThreadMember, ChannelType,
+    StartForumThreadRequest, StartThreadRequest,
+};
+use fluxer_types::message::{ApiMessage, GetMessagesQuery};
 
+use super::message::Message;
 use super::typed_channel::TypedChannel;
+use super::typing_indicator::TypingIndicator;
+
+/// Most message ids accepted by a single bulk delete request.
+pub const BULK_DELETE_MAX: usize = 100;
+
+/// Messages older than this can't be bulk deleted.
+pub const BULK_DELETE_MAX_AGE: Duration = Duration::from_secs(14 * 24 * 60 * 60);
+
+/// Largest page the message history endpoint returns.
+pub const MESSAGE_PAGE_LIMIT: u32 = 100;
 
 #[derive(Debug, Clone)]
 pub struct Channel {
@@ -19,9 +37,13 @@ pub struct Channel {
     pub user_limit: Option<u32>,
     pub rtc_region: Option<String>,
     pub last_message_id: Option<Snowflake>,
+    pub last_pin_timestamp: Option<DateTime<Utc>>,
     pub nsfw: bool,
     pub rate_limit_per_user: Option<u32>,
     pub permission_overwrites: Vec<fluxer_types::channel::ApiChannelOverwrite>,
+    pub thread_metadata: Option<fluxer_types::channel::ApiThreadMetadata>,
+    pub available_tags: Vec<fluxer_types::channel::ApiForumTag>,
+    pub default_reaction_emoji: Option<fluxer_types::channel::ApiDefaultReaction>,
 }
 
 impl Channel {
@@ -41,9 +63,13 @@ impl Channel {
             user_limit: data.user_limit,
             rtc_region: data.rtc_region.clone(),
             last_message_id: data.last_message_id.clone(),
+            last_pin_timestamp: data.last_pin_timestamp,
             nsfw: data.nsfw.unwrap_or(false),
             rate_limit_per_user: data.rate_limit_per_user,
             permission_overwrites: data.permission_overwrites.clone().unwrap_or_default(),
+            thread_metadata: data.thread_metadata.clone(),
+            available_tags: data.available_tags.clone().unwrap_or_default(),
+            default_reaction_emoji: data.default_reaction_emoji.clone(),
         }
     }
 
@@ -63,9 +89,13 @@ impl Channel {
             user_limit: None,
             rtc_region: None,
             last_message_id: None,
+            last_pin_timestamp: None,
             nsfw: false,
             rate_limit_per_user: None,
             permission_overwrites: Vec::new(),
+            thread_metadata: None,
+            available_tags: Vec::new(),
+            default_reaction_emoji: None,
         }
     }
 
@@ -85,6 +115,20 @@ impl Channel {
         self.kind == ChannelType::Dm as u16
     }
 
+    pub fn is_thread(&self) -> bool {
+        self.kind == ChannelType::AnnouncementThread as u16
+            || self.kind == ChannelType::PublicThread as u16
+            || self.kind == ChannelType::PrivateThread as u16
+    }
+
+    pub fn is_stage(&self) -> bool {
+        self.kind == ChannelType::GuildStageVoice as u16
+    }
+
+    pub fn is_forum(&self) -> bool {
+        self.kind == ChannelType::GuildForum as u16
+    }
+
     pub fn is_guild(&self) -> bool {
         self.guild_id.is_some()
     }
@@ -149,18 +193,45 @@ impl Channel {
         Ok(())
     }
 
+    /// Keeps the typing indicator up until the returned guard is dropped.
+    pub fn start_typing(&self, rest: &fluxer_rest::Rest) -> TypingIndicator {
+        TypingIndicator::start(rest.clone(), &self.id)
+    }
+
+    /// Deletes messages in batches of [`BULK_DELETE_MAX`], one request per batch. A lone id,
+    /// which the bulk endpoint rejects, is deleted with the single-message endpoint instead.
+    ///
+    /// Fails with [`Error::MessageTooOld`](crate::Error::MessageTooOld) before sending anything
+    /// if any id is older than [`BULK_DELETE_MAX_AGE`]. Batches are not atomic: if a later
+    /// request fails, messages deleted by earlier batches stay deleted.
     pub async fn bulk_delete_messages(
         &self,
         rest: &fluxer_rest::Rest,
         message_ids: &[String],
     ) -> crate::Result<()> {
-        let body = serde_json::json!({ "message_ids": message_ids });
-        let _: serde_json::Value = rest
-            .post(
-                &fluxer_types::Routes::channel_bulk_delete(&self.id),
-                Some(&body),
-            )
-            .await?;
+        let cutoff = SystemTime::now()
+            .checked_sub(BULK_DELETE_MAX_AGE)
+            .unwrap_or(UNIX_EPOCH);
+        if let Some(old) = message_ids.iter().find(|id| {
+            fluxer_util::SnowflakeUtil::date_from_snowflake(id).is_some_and(|date| date < cutoff)
+        }) {
+            return Err(crate::Error::MessageTooOld(old.clone()));
+        }
+
+        for batch in message_ids.chunks(BULK_DELETE_MAX) {
+            if let [id] = batch {
+                rest.delete_route(&fluxer_types::Routes::channel_message(&self.id, id))
+                    .await?;
+                continue;
+            }
+            let body = serde_json::json!({ "message_ids": batch });
+            let _: serde_json::Value = rest
+                .post(
+                    &fluxer_types::Routes::channel_bulk_delete(&self.id),
+                    Some(&body),
+                )
+                .await?;
+        }
         Ok(())
     }
 
@@ -170,14 +241,23 @@ impl Channel {
         name: &str,
         avatar: Option<&str>,
     ) -> crate::Result<fluxer_types::webhook::ApiWebhook> {
-        let mut body = serde_json::json!({ "name": name });
-        if let Some(a) = avatar {
-            body["avatar"] = serde_json::Value::String(a.to_string());
-        }
+        let body = fluxer_types::webhook::WebhookCreateRequest {
+            name: name.to_string(),
+            avatar: avatar.map(str::to_string),
+        };
+        self.create_webhook_with(rest, &body).await
+    }
+
+    /// Creates a webhook from a typed body, e.g. one from `WebhookCreateBuilder`.
+    pub async fn create_webhook_with(
+        &self,
+        rest: &fluxer_rest::Rest,
+        body: &fluxer_types::webhook::WebhookCreateRequest,
+    ) -> crate::Result<fluxer_types::webhook::ApiWebhook> {
         let wh: fluxer_types::webhook::ApiWebhook = rest
             .post(
                 &fluxer_types::Routes::channel_webhooks(&self.id),
-                Some(&body),
+                Some(body),
             )
             .await?;
         Ok(wh)
@@ -231,6 +311,18 @@ impl Channel {
         Ok(ch)
     }
 
+    pub async fn modify(
+        &self,
+        rest: &fluxer_rest::Rest,
+        body: &fluxer_types::channel::ModifyChannelRequest,
+        reason: Option<&str>,
+    ) -> crate::Result<Channel> {
+        let ch: fluxer_types::channel::ApiChannel = rest
+            .patch_with_reason(&fluxer_types::Routes::channel(&self.id), Some(body), reason)
+            .await?;
+        Ok(Channel::from_api(&ch))
+    }
+
     pub async fn delete(&self, rest: &fluxer_rest::Rest) -> crate::Result<()> {
         rest.delete_route(&fluxer_types::Routes::channel(&self.id))
             .await?;
@@ -286,6 +378,24 @@ impl Channel {
         Ok(())
     }
 
+    /// Creates or replaces the overwrite for a role or member.
+    pub async fn edit_permission_overwrite(
+        &self,
+        rest: &fluxer_rest::Rest,
+        overwrite_id: &str,
+        body: &fluxer_types::channel::EditPermissionsRequest,
+        reason: Option<&str>,
+    ) -> crate::Result<()> {
+        let _: serde_json::Value = rest
+            .put_with_reason(
+                &fluxer_types::Routes::channel_permission(&self.id, overwrite_id),
+                Some(body),
+                reason,
+            )
+            .await?;
+        Ok(())
+    }
+
     pub async fn delete_permission(
         &self,
         rest: &fluxer_rest::Rest,
@@ -316,24 +426,140 @@ impl Channel {
         before: Option<&str>,
         after: Option<&str>,
     ) -> crate::Result<Vec<fluxer_types::message::ApiMessage>> {
-        let mut route = fluxer_types::Routes::channel_messages(&self.id);
-        let mut params = Vec::new();
-        if let Some(l) = limit {
-            params.push(format!("limit={l}"));
-        }
-        if let Some(b) = before {
-            params.push(format!("before={b}"));
-        }
-        if let Some(a) = after {
-            params.push(format!("after={a}"));
-        }
-        if !params.is_empty() {
-            route = format!("{route}?{}", params.join("&"));
-        }
+        self.fetch_messages_with(
+            rest,
+            &GetMessagesQuery {
+                limit,
+                before: before.map(str::to_string),
+                after: after.map(str::to_string),
+                around: None,
+            },
+        )
+        .await
+    }
+
+    pub async fn fetch_messages_with(
+        &self,
+        rest: &fluxer_rest::Rest,
+        query: &GetMessagesQuery,
+    ) -> crate::Result<Vec<fluxer_types::message::ApiMessage>> {
+        let route = format!(
+            "{}{}",
+            fluxer_types::Routes::channel_messages(&self.id),
+            query.to_query_string()
+        );
         let msgs: Vec<fluxer_types::message::ApiMessage> = rest.get(&route).await?;
         Ok(msgs)
     }
 
+    /// Pages backward through the channel's history, newest first, starting before `before`
+    /// or at the latest message.
+    pub fn messages_iter<'a>(
+        &self,
+        rest: &'a fluxer_rest::Rest,
+        before: Option<&str>,
+    ) -> MessagePager<'a> {
+        MessagePager::new(rest, &self.id, before)
+    }
+
+    pub async fn start_thread_from_message(
+        &self,
+        rest: &fluxer_rest::Rest,
+        message_id: &str,
+        body: &StartThreadRequest,
+    ) -> crate::Result<Channel> {
+        let data: ApiChannel = rest
+            .post(
+                &fluxer_types::Routes::channel_message_threads(&self.id, message_id),
+                Some(body),
+            )
+            .await?;
+        Ok(Channel::from_api(&data))
+    }
+
+    pub async fn start_thread_without_message(
+        &self,
+        rest: &fluxer_rest::Rest,
+        body: &StartThreadRequest,
+    ) -> crate::Result<Channel> {
+        let data: ApiChannel = rest
+            .post(&fluxer_types::Routes::channel_threads(&self.id), Some(body))
+            .await?;
+        Ok(Channel::from_api(&data))
+    }
+
+    /// Creates a post in this forum channel, returning the thread and its first message.
+    pub async fn start_forum_thread(
+        &self,
+        rest: &fluxer_rest::Rest,
+        body: &StartForumThreadRequest,
+    ) -> crate::Result<(Channel, Option<ApiMessage>)> {
+        let data: ApiForumThread = rest
+            .post(&fluxer_types::Routes::channel_threads(&self.id), Some(body))
+            .await?;
+        Ok((Channel::from_api(&data.channel), data.message))
+    }
+
+    pub async fn join_thread(&self, rest: &fluxer_rest::Rest) -> crate::Result<()> {
+        rest.put_empty(&fluxer_types::Routes::thread_member(&self.id, "@me"))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn leave_thread(&self, rest: &fluxer_rest::Rest) -> crate::Result<()> {
+        rest.delete_route(&fluxer_types::Routes::thread_member(&self.id, "@me"))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn add_thread_member(
+        &self,
+        rest: &fluxer_rest::Rest,
+        user_id: &str,
+    ) -> crate::Result<()> {
+        rest.put_empty(&fluxer_types::Routes::thread_member(&self.id, user_id))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn remove_thread_member(
+        &self,
+        rest: &fluxer_rest::Rest,
+        user_id: &str,
+    ) -> crate::Result<()> {
+        rest.delete_route(&fluxer_types::Routes::thread_member(&self.id, user_id))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn list_thread_members(
+        &self,
+        rest: &fluxer_rest::Rest,
+    ) -> crate::Result<Vec<ApiThreadMember>> {
+        let members: Vec<ApiThreadMember> = rest
+            .get(&fluxer_types::Routes::thread_members(&self.id))
+            .await?;
+        Ok(members)
+    }
+
+    pub async fn fetch_archived_threads(
+        &self,
+        rest: &fluxer_rest::Rest,
+        private: bool,
+        before: Option<&str>,
+        limit: Option<u32>,
+    ) -> crate::Result<ApiThreadList> {
+        let mut query = fluxer_types::QueryValues::new();
+        query.insert_opt("before", before);
+        query.insert_opt("limit", limit);
+        let route = format!(
+            "{}{query}",
+            fluxer_types::Routes::channel_archived_threads(&self.id, private)
+        );
+        let list: ApiThreadList = rest.get(&route).await?;
+        Ok(list)
+    }
+
     pub async fn add_recipient(
         &self,
         rest: &fluxer_rest::Rest,
@@ -364,3 +590,61 @@ impl std::fmt::Display for Channel {
         write!(f, "<#{}>", self.id)
     }
 }
+
+/// Walks a channel's history backward, continuing before the oldest message of each page.
+pub struct MessagePager<'a> {
+    rest: &'a fluxer_rest::Rest,
+    channel_id: Snowflake,
+    before: Option<Snowflake>,
+    done: bool,
+}
+
+impl<'a> MessagePager<'a> {
+    pub fn new(rest: &'a fluxer_rest::Rest, channel_id: &str, before: Option<&str>) -> Self {
+        Self {
+            rest,
+            channel_id: channel_id.to_string(),
+            before: before.map(str::to_string),
+            done: false,
+        }
+    }
+
+    /// The next page, newest first, or `None` once the start of the channel is reached.
+    pub async fn next_page(&mut self) -> crate::Result<Option<Vec<Message>>> {
+        if self.done {
+            return Ok(None);
+        }
+        let query = GetMessagesQuery {
+            limit: Some(MESSAGE_PAGE_LIMIT),
+            before: self.before.clone(),
+            ..Default::default()
+        };
+        let route = format!(
+            "{}{}",
+            fluxer_types::Routes::channel_messages(&self.channel_id),
+            query.to_query_string()
+        );
+        let page: Vec<ApiMessage> = self.rest.get(&route).await?;
+        if page.len() < MESSAGE_PAGE_LIMIT as usize {
+            self.done = true;
+        }
+        match page.last() {
+            Some(oldest) => self.before = Some(oldest.id.clone()),
+            None => return Ok(None),
+        }
+        Ok(Some(page.iter().map(Message::from_api).collect()))
+    }
+
+    /// Collects up to `max` messages, newest first.
+    pub async fn take(mut self, max: usize) -> crate::Result<Vec<Message>> {
+        let mut messages = Vec::new();
+        while messages.len() < max {
+            let Some(page) = self.next_page().await? else {
+                break;
+            };
+            messages.extend(page);
+        }
+        messages.truncate(max);
+        Ok(messages)
+    }
+}
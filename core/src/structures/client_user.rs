@@ -1,3 +1,5 @@
+use fluxer_types::guild::ApiGuild;
+use fluxer_types::user::UserGuildsQuery;
 use serde_json::Value;
 
 use crate::structures::user::User;
@@ -22,6 +24,27 @@ impl ClientUser {
         Ok(guilds)
     }
 
+    pub async fn fetch_guilds_with(
+        &self,
+        rest: &fluxer_rest::Rest,
+        query: &UserGuildsQuery,
+    ) -> crate::Result<Vec<ApiGuild>> {
+        let route = format!(
+            "{}{}",
+            fluxer_types::Routes::current_user_guilds(),
+            query.to_query_string()
+        );
+        let guilds: Vec<ApiGuild> = rest.get(&route).await?;
+        Ok(guilds)
+    }
+
+    /// Pages through the current user's guilds starting from `query`.
+    ///
+    /// Walks backwards when `query.before` is set, forwards otherwise.
+    pub fn guilds_pager(&self, query: UserGuildsQuery) -> UserGuildsPager {
+        UserGuildsPager { query, done: false }
+    }
+
     pub async fn leave_guild(&self, rest: &fluxer_rest::Rest, guild_id: &str) -> crate::Result<()> {
         rest.delete_route(&fluxer_types::Routes::leave_guild(guild_id))
             .await?;
@@ -54,3 +77,136 @@ impl std::ops::Deref for ClientUser {
         &self.base
     }
 }
+
+const USER_GUILDS_PAGE_MAX: u32 = 200;
+
+pub struct UserGuildsPager {
+    query: UserGuildsQuery,
+    done: bool,
+}
+
+impl UserGuildsPager {
+    pub async fn next_page(
+        &mut self,
+        rest: &fluxer_rest::Rest,
+    ) -> crate::Result<Option<Vec<ApiGuild>>> {
+        if self.done {
+            return Ok(None);
+        }
+        let limit = self.query.limit.unwrap_or(USER_GUILDS_PAGE_MAX);
+        self.query.limit = Some(limit);
+
+        let route = format!(
+            "{}{}",
+            fluxer_types::Routes::current_user_guilds(),
+            self.query.to_query_string()
+        );
+        let page: Vec<ApiGuild> = rest.get(&route).await?;
+
+        if (page.len() as u32) < limit {
+            self.done = true;
+        }
+        if self.query.before.is_some() {
+            match page.first() {
+                Some(first) => self.query.before = Some(first.id.clone()),
+                None => self.done = true,
+            }
+        } else {
+            match page.last() {
+                Some(last) => self.query.after = Some(last.id.clone()),
+                None => self.done = true,
+            }
+        }
+
+        if page.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(page))
+        }
+    }
+
+    pub async fn collect_all(mut self, rest: &fluxer_rest::Rest) -> crate::Result<Vec<ApiGuild>> {
+        let mut all = Vec::new();
+        while let Some(page) = self.next_page(rest).await? {
+            all.extend(page);
+        }
+        Ok(all)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fluxer_rest::{MockTransport, Rest, RestOptions};
+    use serde_json::json;
+
+    use super::*;
+
+    fn guild(id: &str) -> Value {
+        json!({
+            "id": id,
+            "name": format!("Guild {id}"),
+            "icon": null,
+            "banner": null,
+            "owner_id": "1",
+            "verification_level": 0,
+            "mfa_level": 0,
+            "explicit_content_filter": 0,
+            "default_message_notifications": 0,
+            "approximate_member_count": 40,
+            "approximate_presence_count": 7,
+        })
+    }
+
+    fn client_user() -> ClientUser {
+        ClientUser::from_user(User::from_api(
+            &serde_json::from_value(json!({ "id": "1", "username": "me", "discriminator": "0" }))
+                .unwrap(),
+        ))
+    }
+
+    #[tokio::test]
+    async fn pages_through_guilds_with_counts() {
+        let mock = MockTransport::new();
+        let rest = Rest::with_transport(RestOptions::default(), mock.clone());
+        mock.push_json(200, &json!([guild("10"), guild("11")]))
+            .push_json(200, &json!([guild("12")]));
+
+        let guilds = client_user()
+            .guilds_pager(UserGuildsQuery {
+                limit: Some(2),
+                with_counts: true,
+                ..Default::default()
+            })
+            .collect_all(&rest)
+            .await
+            .unwrap();
+
+        let ids: Vec<_> = guilds.iter().map(|g| g.id.as_str()).collect();
+        assert_eq!(ids, ["10", "11", "12"]);
+        assert!(
+            guilds.iter().all(|g| g.approximate_member_count == Some(40)
+                && g.approximate_presence_count == Some(7))
+        );
+
+        let urls: Vec<_> = mock.requests().into_iter().map(|r| r.url).collect();
+        assert!(urls[0].ends_with("/users/@me/guilds?limit=2&with_counts=true"));
+        assert!(urls[1].ends_with("/users/@me/guilds?after=11&limit=2&with_counts=true"));
+    }
+
+    #[tokio::test]
+    async fn stops_on_an_empty_page() {
+        let mock = MockTransport::new();
+        let rest = Rest::with_transport(RestOptions::default(), mock.clone());
+        mock.push_json(200, &json!([guild("10"), guild("11")]))
+            .push_json(200, &json!([]));
+
+        let mut pager = client_user().guilds_pager(UserGuildsQuery {
+            limit: Some(2),
+            ..Default::default()
+        });
+        assert_eq!(pager.next_page(&rest).await.unwrap().unwrap().len(), 2);
+        assert!(pager.next_page(&rest).await.unwrap().is_none());
+        assert!(pager.next_page(&rest).await.unwrap().is_none());
+        assert_eq!(mock.requests().len(), 2);
+    }
+}
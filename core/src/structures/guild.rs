@@ -5,6 +5,7 @@ use fluxer_types::channel::ApiChannelOverwrite;
 use fluxer_types::guild::ApiGuild;
 use serde_json::Value;
 
+use crate::structures::guild_ban::BanPager;
 use crate::structures::role::Role;
 use crate::util::cdn::{self, CdnOptions};
 
@@ -33,6 +34,10 @@ pub struct Guild {
     pub channels: Vec<Snowflake>,
     pub emojis: Vec<Snowflake>,
     pub member_count: Option<u64>,
+    /// Only set when the guild was fetched with counts, see
+    /// [`GuildManager::fetch_with_counts`](crate::client::GuildManager::fetch_with_counts).
+    pub approximate_member_count: Option<u32>,
+    pub approximate_presence_count: Option<u32>,
 }
 
 impl Guild {
@@ -61,6 +66,8 @@ impl Guild {
             channels: Vec::new(),
             emojis: Vec::new(),
             member_count: None,
+            approximate_member_count: data.approximate_member_count,
+            approximate_presence_count: data.approximate_presence_count,
         }
     }
 
@@ -89,6 +96,8 @@ impl Guild {
             channels: Vec::new(),
             emojis: Vec::new(),
             member_count: None,
+            approximate_member_count: None,
+            approximate_presence_count: None,
         }
     }
 
@@ -111,6 +120,10 @@ impl Guild {
         self.afk_timeout = data.afk_timeout;
         self.vanity_url_code = data.vanity_url_code.clone();
         self.permissions = data.permissions.clone();
+        if data.approximate_member_count.is_some() {
+            self.approximate_member_count = data.approximate_member_count;
+            self.approximate_presence_count = data.approximate_presence_count;
+        }
     }
 
     pub fn icon_url(&self, opts: &CdnOptions) -> Option<String> {
@@ -166,17 +179,10 @@ impl Guild {
         limit: Option<u32>,
         after: Option<&str>,
     ) -> crate::Result<Vec<fluxer_types::user::ApiGuildMember>> {
-        let mut route = fluxer_types::Routes::guild_members(&self.id);
-        let mut params = Vec::new();
-        if let Some(l) = limit {
-            params.push(format!("limit={l}"));
-        }
-        if let Some(a) = after {
-            params.push(format!("after={a}"));
-        }
-        if !params.is_empty() {
-            route = format!("{route}?{}", params.join("&"));
-        }
+        let mut query = fluxer_types::QueryValues::new();
+        query.insert_opt("limit", limit);
+        query.insert_opt("after", after);
+        let route = format!("{}{query}", fluxer_types::Routes::guild_members(&self.id));
         let members: Vec<fluxer_types::user::ApiGuildMember> = rest.get(&route).await?;
         Ok(members)
     }
@@ -227,6 +233,34 @@ impl Guild {
         Ok(role)
     }
 
+    pub async fn edit_role(
+        &mut self,
+        rest: &fluxer_rest::Rest,
+        role_id: &str,
+        body: &fluxer_types::role::UpdateRoleBody,
+    ) -> crate::Result<Role> {
+        let data: fluxer_types::role::ApiRole = rest
+            .patch(
+                &fluxer_types::Routes::guild_role(&self.id, role_id),
+                Some(body),
+            )
+            .await?;
+        let role = Role::from_api(&data, &self.id);
+        self.roles.insert(role.id.clone(), role.clone());
+        Ok(role)
+    }
+
+    pub async fn delete_role(
+        &mut self,
+        rest: &fluxer_rest::Rest,
+        role_id: &str,
+    ) -> crate::Result<()> {
+        rest.delete_route(&fluxer_types::Routes::guild_role(&self.id, role_id))
+            .await?;
+        self.roles.remove(role_id);
+        Ok(())
+    }
+
     pub async fn add_role_to_member(
         &self,
         rest: &fluxer_rest::Rest,
@@ -291,6 +325,25 @@ impl Guild {
         Ok(bans)
     }
 
+    pub async fn fetch_bans_page(
+        &self,
+        rest: &fluxer_rest::Rest,
+        query: &fluxer_types::ban::BanListQuery,
+    ) -> crate::Result<Vec<fluxer_types::ban::ApiBan>> {
+        let route = format!(
+            "{}{}",
+            fluxer_types::Routes::guild_bans(&self.id),
+            query.to_query_string()
+        );
+        let bans: Vec<fluxer_types::ban::ApiBan> = rest.get(&route).await?;
+        Ok(bans)
+    }
+
+    /// Pages through every ban of the guild; large guilds need several requests.
+    pub fn bans_iter<'a>(&self, rest: &'a fluxer_rest::Rest) -> BanPager<'a> {
+        BanPager::new(rest, &self.id)
+    }
+
     pub async fn fetch_channels(
         &self,
         rest: &fluxer_rest::Rest,
@@ -301,6 +354,16 @@ impl Guild {
         Ok(channels)
     }
 
+    pub async fn fetch_active_threads(
+        &self,
+        rest: &fluxer_rest::Rest,
+    ) -> crate::Result<fluxer_types::channel::ApiThreadList> {
+        let list: fluxer_types::channel::ApiThreadList = rest
+            .get(&fluxer_types::Routes::guild_active_threads(&self.id))
+            .await?;
+        Ok(list)
+    }
+
     pub async fn fetch_invites(
         &self,
         rest: &fluxer_rest::Rest,
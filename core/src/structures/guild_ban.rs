@@ -1,14 +1,18 @@
+use chrono::{DateTime, Utc};
 use fluxer_types::Snowflake;
-use fluxer_types::ban::ApiBan;
+use fluxer_types::ban::{ApiBan, BanListQuery};
 
 use crate::structures::user::User;
 
+/// Largest page the ban list endpoint returns.
+pub const BAN_PAGE_LIMIT: u32 = 1000;
+
 #[derive(Debug, Clone)]
 pub struct GuildBan {
     pub guild_id: Snowflake,
     pub user: User,
     pub reason: Option<String>,
-    pub expires_at: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 impl GuildBan {
@@ -17,7 +21,7 @@ impl GuildBan {
             guild_id: guild_id.to_string(),
             user: User::from_api(&data.user),
             reason: data.reason.clone(),
-            expires_at: data.expires_at.clone(),
+            expires_at: data.expires_at,
         }
     }
 
@@ -30,3 +34,109 @@ impl GuildBan {
         Ok(())
     }
 }
+
+/// Walks a guild's bans page by page, continuing after the last user id of each page.
+pub struct BanPager<'a> {
+    rest: &'a fluxer_rest::Rest,
+    guild_id: Snowflake,
+    after: Option<Snowflake>,
+    done: bool,
+}
+
+impl<'a> BanPager<'a> {
+    pub fn new(rest: &'a fluxer_rest::Rest, guild_id: &str) -> Self {
+        Self {
+            rest,
+            guild_id: guild_id.to_string(),
+            after: None,
+            done: false,
+        }
+    }
+
+    /// The next page, or `None` once every ban has been returned.
+    pub async fn next_page(&mut self) -> crate::Result<Option<Vec<GuildBan>>> {
+        if self.done {
+            return Ok(None);
+        }
+        let query = BanListQuery {
+            limit: Some(BAN_PAGE_LIMIT),
+            after: self.after.clone(),
+            ..Default::default()
+        };
+        let route = format!(
+            "{}{}",
+            fluxer_types::Routes::guild_bans(&self.guild_id),
+            query.to_query_string()
+        );
+        let page: Vec<ApiBan> = self.rest.get(&route).await?;
+        if page.len() < BAN_PAGE_LIMIT as usize {
+            self.done = true;
+        }
+        match page.last() {
+            Some(last) => self.after = Some(last.user.id.clone()),
+            None => return Ok(None),
+        }
+        Ok(Some(
+            page.iter()
+                .map(|ban| GuildBan::from_api(ban, &self.guild_id))
+                .collect(),
+        ))
+    }
+
+    pub async fn collect_all(mut self) -> crate::Result<Vec<GuildBan>> {
+        let mut bans = Vec::new();
+        while let Some(page) = self.next_page().await? {
+            bans.extend(page);
+        }
+        Ok(bans)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fluxer_rest::{MockTransport, Rest, RestOptions};
+    use serde_json::{Value, json};
+
+    use super::*;
+
+    fn bans(ids: impl Iterator<Item = u64>) -> Value {
+        ids.map(|id| {
+            json!({
+                "user": { "id": id.to_string(), "username": "u", "discriminator": "0" },
+                "reason": null,
+            })
+        })
+        .collect()
+    }
+
+    #[tokio::test]
+    async fn pages_after_the_last_user_id() {
+        let mock = MockTransport::new();
+        let rest = Rest::with_transport(RestOptions::default(), mock.clone());
+        mock.push_json(200, &bans(1..=u64::from(BAN_PAGE_LIMIT)))
+            .push_json(200, &bans(1001..=1002));
+
+        let all = BanPager::new(&rest, "9").collect_all().await.unwrap();
+
+        assert_eq!(all.len(), 1002);
+        assert_eq!(all.last().unwrap().user.id, "1002");
+        assert!(all.iter().all(|ban| ban.guild_id == "9"));
+        let urls: Vec<_> = mock.requests().into_iter().map(|r| r.url).collect();
+        assert_eq!(urls.len(), 2);
+        assert!(urls[0].ends_with("/guilds/9/bans?limit=1000"));
+        assert!(urls[1].ends_with("/guilds/9/bans?limit=1000&after=1000"));
+    }
+
+    #[tokio::test]
+    async fn stops_on_an_empty_first_page() {
+        let mock = MockTransport::new();
+        let rest = Rest::with_transport(RestOptions::default(), mock.clone());
+        mock.push_json(200, &json!([]));
+
+        let mut pager = BanPager::new(&rest, "9");
+
+        assert!(pager.next_page().await.unwrap().is_none());
+        assert!(pager.next_page().await.unwrap().is_none());
+        assert_eq!(mock.requests().len(), 1);
+    }
+}
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use fluxer_types::Snowflake;
 use fluxer_types::channel::OverwriteType;
 use fluxer_types::user::ApiGuildMember;
@@ -13,15 +14,15 @@ pub struct GuildMember {
     pub guild_id: Snowflake,
     pub nick: Option<String>,
     pub role_ids: Vec<Snowflake>,
-    pub joined_at: String,
-    pub communication_disabled_until: Option<String>,
+    pub joined_at: Option<DateTime<Utc>>,
+    pub communication_disabled_until: Option<DateTime<Utc>>,
     pub mute: bool,
     pub deaf: bool,
     pub avatar: Option<String>,
     pub banner: Option<String>,
     pub accent_color: Option<u32>,
     pub profile_flags: Option<u32>,
-    pub premium_since: Option<String>,
+    pub premium_since: Option<DateTime<Utc>>,
 }
 
 impl GuildMember {
@@ -36,18 +37,24 @@ impl GuildMember {
             guild_id: guild_id.to_string(),
             nick: data.nick.clone(),
             role_ids: data.roles.clone(),
-            joined_at: data.joined_at.clone(),
-            communication_disabled_until: data.communication_disabled_until.clone(),
+            joined_at: data.joined_at,
+            communication_disabled_until: data.communication_disabled_until,
             mute: data.mute.unwrap_or(false),
             deaf: data.deaf.unwrap_or(false),
             avatar: data.avatar.clone(),
             banner: data.banner.clone(),
             accent_color: data.accent_color,
             profile_flags: data.profile_flags,
-            premium_since: data.premium_since.clone(),
+            premium_since: data.premium_since,
         }
     }
 
+    /// Whether a timeout is set and has not expired yet.
+    pub fn is_timed_out(&self) -> bool {
+        self.communication_disabled_until
+            .is_some_and(|until| until > Utc::now())
+    }
+
     pub fn display_name(&self) -> &str {
         self.nick
             .as_deref()
@@ -0,0 +1,230 @@
+use std::future::Future;
+
+use fluxer_types::Snowflake;
+use fluxer_types::interaction::{
+    ApiApplicationCommandInteraction, CommandData, InteractionCallbackData,
+    InteractionCallbackType, InteractionData, InteractionResponse,
+};
+use fluxer_types::message::ApiMessage;
+
+use crate::structures::user::User;
+
+const EPHEMERAL_FLAG: u32 = fluxer_util::MessageFlags::EPHEMERAL.bits();
+
+#[derive(Debug, Clone)]
+pub struct Interaction {
+    pub id: Snowflake,
+    pub application_id: Snowflake,
+    pub kind: u8,
+    pub token: String,
+    pub guild_id: Option<Snowflake>,
+    pub channel_id: Option<Snowflake>,
+    pub data: Option<CommandData>,
+    pub user: Option<User>,
+    pub message_id: Option<Snowflake>,
+}
+
+impl Interaction {
+    pub fn from_api(data: &ApiApplicationCommandInteraction) -> Self {
+        let user = data
+            .member
+            .as_ref()
+            .and_then(|m| m.member.user.as_ref())
+            .or(data.user.as_ref())
+            .map(User::from_api);
+
+        Self {
+            id: data.id.clone(),
+            application_id: data.application_id.clone(),
+            kind: data.kind,
+            token: data.token.clone(),
+            guild_id: data.guild_id.clone(),
+            channel_id: data.channel_id.clone(),
+            data: data.data.clone(),
+            user,
+            message_id: data.message.as_ref().map(|m| m.id.clone()),
+        }
+    }
+
+    pub fn interaction_data(&self) -> Option<InteractionData> {
+        self.data.as_ref()?.typed(self.kind)
+    }
+
+    pub async fn respond(
+        &self,
+        rest: &fluxer_rest::Rest,
+        response: &InteractionResponse,
+    ) -> crate::Result<()> {
+        rest.post::<serde_json::Value>(
+            &fluxer_types::Routes::interaction_callback(&self.id, &self.token),
+            Some(response),
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn reply(
+        &self,
+        rest: &fluxer_rest::Rest,
+        data: InteractionCallbackData,
+    ) -> crate::Result<()> {
+        self.respond(
+            rest,
+            &InteractionResponse {
+                kind: InteractionCallbackType::ChannelMessageWithSource,
+                data: Some(data),
+            },
+        )
+        .await
+    }
+
+    pub async fn defer(&self, rest: &fluxer_rest::Rest, ephemeral: bool) -> crate::Result<()> {
+        let data = ephemeral.then(|| InteractionCallbackData {
+            flags: Some(EPHEMERAL_FLAG),
+            ..Default::default()
+        });
+        self.respond(
+            rest,
+            &InteractionResponse {
+                kind: InteractionCallbackType::DeferredChannelMessageWithSource,
+                data,
+            },
+        )
+        .await
+    }
+
+    pub async fn edit_reply(
+        &self,
+        rest: &fluxer_rest::Rest,
+        body: &fluxer_builders::MessagePayloadData,
+    ) -> crate::Result<ApiMessage> {
+        let msg: ApiMessage = rest
+            .patch(
+                &fluxer_types::Routes::webhook_message(
+                    &self.application_id,
+                    &self.token,
+                    "@original",
+                ),
+                Some(body),
+            )
+            .await?;
+        Ok(msg)
+    }
+
+    /// Defers the response, awaits `task`, then edits the original response with its result.
+    ///
+    /// If `task` fails, the original response is edited with the error message and the task's
+    /// error is returned as [`Error::DeferredTask`](crate::Error::DeferredTask).
+    pub async fn defer_then<F, E>(
+        &self,
+        rest: &fluxer_rest::Rest,
+        ephemeral: bool,
+        task: F,
+    ) -> crate::Result<ApiMessage>
+    where
+        F: Future<Output = Result<fluxer_builders::MessagePayloadData, E>>,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        self.defer(rest, ephemeral).await?;
+        match task.await {
+            Ok(body) => self.edit_reply(rest, &body).await,
+            Err(err) => {
+                let err = err.into();
+                let body = fluxer_builders::MessagePayloadData {
+                    content: Some(format!("An error occurred: {err}")),
+                    ..Default::default()
+                };
+                self.edit_reply(rest, &body).await?;
+                Err(crate::Error::DeferredTask(err))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fluxer_rest::{MockTransport, Rest, RestOptions};
+    use serde_json::json;
+
+    use super::*;
+
+    fn interaction() -> Interaction {
+        Interaction {
+            id: "10".into(),
+            application_id: "20".into(),
+            kind: 2,
+            token: "tok".to_string(),
+            guild_id: None,
+            channel_id: Some("30".into()),
+            data: None,
+            user: None,
+            message_id: None,
+        }
+    }
+
+    fn message_json(content: &str) -> serde_json::Value {
+        json!({
+            "id": "40",
+            "channel_id": "30",
+            "author": { "id": "20", "username": "bot", "discriminator": "0000" },
+            "type": 0,
+            "content": content,
+            "pinned": false,
+        })
+    }
+
+    fn mock_rest() -> (Rest, MockTransport) {
+        let mock = MockTransport::new();
+        (
+            Rest::with_transport(RestOptions::default(), mock.clone()),
+            mock,
+        )
+    }
+
+    #[tokio::test]
+    async fn defer_then_defers_before_running_the_task() {
+        let (rest, mock) = mock_rest();
+        mock.push_response(204, Vec::new())
+            .push_json(200, &message_json("done"));
+
+        let msg = interaction()
+            .defer_then(&rest, true, async {
+                let sent = mock.requests();
+                assert_eq!(sent.len(), 1, "defer must be sent before the task runs");
+                assert!(sent[0].url.ends_with("/interactions/10/tok/callback"));
+                assert_eq!(
+                    sent[0].json().unwrap(),
+                    json!({ "type": 5, "data": { "flags": 64 } })
+                );
+                Ok::<_, std::io::Error>(fluxer_builders::MessagePayloadData {
+                    content: Some("done".to_string()),
+                    ..Default::default()
+                })
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(msg.content, "done");
+        let edit = mock.last_request().unwrap();
+        assert_eq!(edit.method.as_str(), "PATCH");
+        assert!(edit.url.ends_with("/webhooks/20/tok/messages/@original"));
+    }
+
+    #[tokio::test]
+    async fn defer_then_reports_and_returns_the_task_error() {
+        let (rest, mock) = mock_rest();
+        mock.push_response(204, Vec::new())
+            .push_json(200, &message_json("An error occurred: boom"));
+
+        let err = interaction()
+            .defer_then(&rest, false, async {
+                Err::<fluxer_builders::MessagePayloadData, _>("boom")
+            })
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, crate::Error::DeferredTask(ref e) if e.to_string() == "boom"));
+        let edit = mock.last_request().unwrap().json().unwrap();
+        assert_eq!(edit["content"], "An error occurred: boom");
+    }
+}
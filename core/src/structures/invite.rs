@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use fluxer_types::channel::ApiChannelPartial;
 use fluxer_types::invite::{ApiGuildPartial, ApiInvite};
 
@@ -12,9 +13,9 @@ pub struct Invite {
     pub inviter: Option<User>,
     pub member_count: Option<u32>,
     pub presence_count: Option<u32>,
-    pub expires_at: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
     pub temporary: Option<bool>,
-    pub created_at: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
     pub uses: Option<u32>,
     pub max_uses: Option<u32>,
     pub max_age: Option<u32>,
@@ -30,9 +31,9 @@ impl Invite {
             inviter: data.inviter.as_ref().map(User::from_api),
             member_count: data.member_count,
             presence_count: data.presence_count,
-            expires_at: data.expires_at.clone(),
+            expires_at: data.expires_at,
             temporary: data.temporary,
-            created_at: data.created_at.clone(),
+            created_at: data.created_at,
             uses: data.uses,
             max_uses: data.max_uses,
             max_age: data.max_age,
@@ -1,8 +1,9 @@
+use chrono::{DateTime, Utc};
 use fluxer_types::Snowflake;
 use fluxer_types::embed::ApiEmbed;
 use fluxer_types::message::{
-    ApiMessage, ApiMessageAttachment, ApiMessageReaction, ApiMessageReference, ApiMessageSticker,
-    MessageType,
+    ApiMessage, ApiMessageAttachment, ApiMessageInteraction, ApiMessageReaction,
+    ApiMessageReference, ApiMessageSticker, MessageType, ReactionKind,
 };
 use serde_json::Value;
 
@@ -15,8 +16,8 @@ pub struct Message {
     pub guild_id: Option<Snowflake>,
     pub author: User,
     pub content: String,
-    pub timestamp: String,
-    pub edited_timestamp: Option<String>,
+    pub timestamp: Option<DateTime<Utc>>,
+    pub edited_timestamp: Option<DateTime<Utc>>,
     pub pinned: bool,
     pub tts: bool,
     pub mention_everyone: bool,
@@ -28,11 +29,15 @@ pub struct Message {
     pub reactions: Vec<ApiMessageReaction>,
     pub message_reference: Option<ApiMessageReference>,
     pub referenced_message: Option<Box<Message>>,
+    pub referenced_message_deleted: bool,
     pub message_type: MessageType,
     pub flags: Option<u32>,
     pub nonce: Option<String>,
     pub webhook_id: Option<Snowflake>,
+    pub application_id: Option<Snowflake>,
+    pub interaction: Option<ApiMessageInteraction>,
     pub member_data: Option<Value>,
+    pub poll: Option<fluxer_types::poll::ApiPoll>,
 }
 
 impl Message {
@@ -51,8 +56,8 @@ impl Message {
             guild_id: data.guild_id.clone(),
             author: User::from_api(&data.author),
             content: data.content.clone(),
-            timestamp: data.timestamp.clone(),
-            edited_timestamp: data.edited_timestamp.clone(),
+            timestamp: data.timestamp,
+            edited_timestamp: data.edited_timestamp,
             pinned: data.pinned,
             tts: data.tts.unwrap_or(false),
             mention_everyone: data.mention_everyone.unwrap_or(false),
@@ -65,16 +70,25 @@ impl Message {
             message_reference: data.message_reference.clone(),
             referenced_message: data
                 .referenced_message
-                .as_ref()
+                .as_message()
                 .map(|m| Box::new(Message::from_api(m))),
+            referenced_message_deleted: data.referenced_message.is_deleted(),
             message_type: data.kind,
             flags: data.flags,
             nonce: data.nonce.clone(),
             webhook_id: data.webhook_id.clone(),
+            application_id: data.application_id.clone(),
+            interaction: data.interaction.clone(),
             member_data: None,
+            poll: data.poll.clone(),
         }
     }
 
+    /// Whether this message was authored by the given application, e.g. an interaction response.
+    pub fn is_from_application(&self, application_id: &str) -> bool {
+        self.application_id.as_deref() == Some(application_id)
+    }
+
     pub fn from_value(data: &Value) -> Option<Self> {
         let api: ApiMessage = serde_json::from_value(data.clone()).ok()?;
         let mut msg = Self::from_api(&api);
@@ -239,9 +253,36 @@ impl Message {
     }
 
     pub async fn add_reaction(&self, rest: &fluxer_rest::Rest, emoji: &str) -> crate::Result<()> {
-        let route = format!(
-            "{}/@me",
-            fluxer_types::Routes::channel_message_reaction(&self.channel_id, &self.id, emoji)
+        self.add_reaction_with_kind(rest, emoji, ReactionKind::Normal)
+            .await
+    }
+
+    /// Adds `emojis` in order, e.g. to set up a reaction menu. Requests are paced by the
+    /// reaction rate limit.
+    ///
+    /// Stops at the first failure with
+    /// [`Error::ReactionsIncomplete`](crate::Error::ReactionsIncomplete), which records how
+    /// many reactions were added.
+    pub async fn add_reactions(
+        &self,
+        rest: &fluxer_rest::Rest,
+        emojis: &[&str],
+    ) -> crate::Result<()> {
+        add_reactions(rest, &self.channel_id, &self.id, emojis).await
+    }
+
+    pub async fn add_reaction_with_kind(
+        &self,
+        rest: &fluxer_rest::Rest,
+        emoji: &str,
+        kind: ReactionKind,
+    ) -> crate::Result<()> {
+        let route = with_reaction_kind(
+            format!(
+                "{}/@me",
+                fluxer_types::Routes::channel_message_reaction(&self.channel_id, &self.id, emoji)
+            ),
+            kind,
         );
         let _: Value = rest.put(&route, Option::<&()>::None).await?;
         Ok(())
@@ -252,9 +293,22 @@ impl Message {
         rest: &fluxer_rest::Rest,
         emoji: &str,
     ) -> crate::Result<()> {
-        let route = format!(
-            "{}/@me",
-            fluxer_types::Routes::channel_message_reaction(&self.channel_id, &self.id, emoji)
+        self.remove_reaction_with_kind(rest, emoji, ReactionKind::Normal)
+            .await
+    }
+
+    pub async fn remove_reaction_with_kind(
+        &self,
+        rest: &fluxer_rest::Rest,
+        emoji: &str,
+        kind: ReactionKind,
+    ) -> crate::Result<()> {
+        let route = with_reaction_kind(
+            format!(
+                "{}/@me",
+                fluxer_types::Routes::channel_message_reaction(&self.channel_id, &self.id, emoji)
+            ),
+            kind,
         );
         rest.delete_route(&route).await?;
         Ok(())
@@ -305,22 +359,61 @@ impl Message {
         limit: Option<u32>,
         after: Option<&str>,
     ) -> crate::Result<Vec<fluxer_types::user::ApiUser>> {
-        let mut route =
-            fluxer_types::Routes::channel_message_reaction(&self.channel_id, &self.id, emoji);
-        let mut params = Vec::new();
-        if let Some(l) = limit {
-            params.push(format!("limit={l}"));
-        }
-        if let Some(a) = after {
-            params.push(format!("after={a}"));
-        }
-        if !params.is_empty() {
-            route = format!("{route}?{}", params.join("&"));
+        self.fetch_reaction_users_with_kind(rest, emoji, ReactionKind::Normal, limit, after)
+            .await
+    }
+
+    pub async fn fetch_reaction_users_with_kind(
+        &self,
+        rest: &fluxer_rest::Rest,
+        emoji: &str,
+        kind: ReactionKind,
+        limit: Option<u32>,
+        after: Option<&str>,
+    ) -> crate::Result<Vec<fluxer_types::user::ApiUser>> {
+        let mut query = fluxer_types::QueryValues::new();
+        if kind != ReactionKind::Normal {
+            query.insert("type", kind.as_u8());
         }
+        query.insert_opt("limit", limit);
+        query.insert_opt("after", after);
+        let route = format!(
+            "{}{query}",
+            fluxer_types::Routes::channel_message_reaction(&self.channel_id, &self.id, emoji)
+        );
         let users: Vec<fluxer_types::user::ApiUser> = rest.get(&route).await?;
         Ok(users)
     }
 
+    /// Closes the message's poll immediately; only its author can do this.
+    pub async fn end_poll(&self, rest: &fluxer_rest::Rest) -> crate::Result<ApiMessage> {
+        let msg: ApiMessage = rest
+            .post(
+                &fluxer_types::Routes::channel_poll_expire(&self.channel_id, &self.id),
+                Option::<&()>::None,
+            )
+            .await?;
+        Ok(msg)
+    }
+
+    pub async fn fetch_poll_answer_voters(
+        &self,
+        rest: &fluxer_rest::Rest,
+        answer_id: u32,
+        limit: Option<u32>,
+        after: Option<&str>,
+    ) -> crate::Result<Vec<fluxer_types::user::ApiUser>> {
+        let mut query = fluxer_types::QueryValues::new();
+        query.insert_opt("limit", limit);
+        query.insert_opt("after", after);
+        let route = format!(
+            "{}{query}",
+            fluxer_types::Routes::channel_poll_answer_voters(&self.channel_id, &self.id, answer_id)
+        );
+        let voters: fluxer_types::poll::ApiPollAnswerVoters = rest.get(&route).await?;
+        Ok(voters.users)
+    }
+
     pub async fn pin(&self, rest: &fluxer_rest::Rest) -> crate::Result<()> {
         let _: Value = rest
             .put(
@@ -366,6 +459,11 @@ pub struct PartialMessage {
     pub guild_id: Option<Snowflake>,
     pub content: Option<String>,
     pub author_id: Option<Snowflake>,
+    pub edited_timestamp: Option<DateTime<Utc>>,
+    /// `None` when the update did not touch the field, e.g. an embed-only unfurl update.
+    pub embeds: Option<Vec<ApiEmbed>>,
+    pub attachments: Option<Vec<ApiMessageAttachment>>,
+    pub mentions: Option<Vec<User>>,
 }
 
 impl PartialMessage {
@@ -386,6 +484,14 @@ impl PartialMessage {
                 .and_then(|a| a.get("id"))
                 .and_then(|v| v.as_str())
                 .map(String::from),
+            edited_timestamp: data
+                .get("edited_timestamp")
+                .and_then(|v| v.as_str())
+                .and_then(fluxer_types::ser::iso8601::parse),
+            embeds: typed_field(data, "embeds"),
+            attachments: typed_field(data, "attachments"),
+            mentions: typed_field::<Vec<fluxer_types::user::ApiUser>>(data, "mentions")
+                .map(|users| users.iter().map(User::from_api).collect()),
         })
     }
 }
@@ -395,3 +501,39 @@ impl std::fmt::Display for Message {
         write!(f, "{}", self.content)
     }
 }
+
+fn typed_field<T: serde::de::DeserializeOwned>(data: &Value, key: &str) -> Option<T> {
+    serde_json::from_value(data.get(key)?.clone()).ok()
+}
+
+pub(crate) async fn add_reactions(
+    rest: &fluxer_rest::Rest,
+    channel_id: &str,
+    message_id: &str,
+    emojis: &[&str],
+) -> crate::Result<()> {
+    for (added, emoji) in emojis.iter().enumerate() {
+        let route = format!(
+            "{}/@me",
+            fluxer_types::Routes::channel_message_reaction(channel_id, message_id, emoji)
+        );
+        if let Err(err) = rest.put::<Value>(&route, Option::<&()>::None).await {
+            return Err(crate::Error::ReactionsIncomplete {
+                added,
+                source: Box::new(err.into()),
+            });
+        }
+    }
+    Ok(())
+}
+
+fn with_reaction_kind(route: String, kind: ReactionKind) -> String {
+    match kind {
+        ReactionKind::Normal => route,
+        kind => {
+            let mut query = fluxer_types::QueryValues::new();
+            query.insert("type", kind.as_u8());
+            format!("{route}{query}")
+        }
+    }
+}
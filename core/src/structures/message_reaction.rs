@@ -1,4 +1,29 @@
 use fluxer_types::Snowflake;
+use fluxer_types::gateway::GatewayReactionEmoji;
+use fluxer_types::message::ApiReactionEmoji;
+
+/// Identifies one emoji in a message's reaction counts: the custom emoji id, or the unicode name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ReactionKey {
+    pub id: Option<Snowflake>,
+    pub name: String,
+}
+
+impl ReactionKey {
+    pub fn from_api(data: &ApiReactionEmoji) -> Self {
+        Self {
+            id: data.id.clone(),
+            name: data.name.clone(),
+        }
+    }
+
+    pub fn from_gateway(data: &GatewayReactionEmoji) -> Self {
+        Self {
+            id: data.id.clone(),
+            name: data.name.clone(),
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct MessageReaction {
@@ -5,25 +5,33 @@ pub mod guild_ban;
 pub mod guild_emoji;
 pub mod guild_member;
 pub mod guild_sticker;
+pub mod interaction;
 pub mod invite;
 pub mod message;
 pub mod message_reaction;
 pub mod role;
+pub mod sticker_pack;
 pub mod typed_channel;
+pub mod typing_indicator;
 pub mod user;
+pub mod voice_state;
 pub mod webhook;
 
 pub use channel::*;
-pub use client_user::ClientUser;
+pub use client_user::{ClientUser, UserGuildsPager};
 pub use guild::*;
 pub use guild_ban::*;
 pub use guild_emoji::*;
 pub use guild_member::*;
 pub use guild_sticker::*;
+pub use interaction::*;
 pub use invite::*;
 pub use message::*;
 pub use message_reaction::*;
 pub use role::*;
+pub use sticker_pack::*;
 pub use typed_channel::*;
+pub use typing_indicator::*;
 pub use user::*;
+pub use voice_state::*;
 pub use webhook::*;
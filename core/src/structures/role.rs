@@ -12,6 +12,7 @@ pub struct Role {
     pub permissions_raw: String,
     pub hoist: bool,
     pub mentionable: bool,
+    pub icon: Option<String>,
     pub unicode_emoji: Option<String>,
     pub hoist_position: Option<i32>,
 }
@@ -27,6 +28,7 @@ impl Role {
             permissions_raw: data.permissions.clone(),
             hoist: data.hoist,
             mentionable: data.mentionable,
+            icon: data.icon.clone(),
             unicode_emoji: data.unicode_emoji.clone(),
             hoist_position: data.hoist_position,
         }
@@ -52,6 +54,7 @@ impl Role {
         self.permissions_raw.clone_from(&data.permissions);
         self.hoist = data.hoist;
         self.mentionable = data.mentionable;
+        self.icon.clone_from(&data.icon);
         self.unicode_emoji.clone_from(&data.unicode_emoji);
         self.hoist_position = data.hoist_position;
     }
@@ -0,0 +1,86 @@
+use fluxer_types::Snowflake;
+use fluxer_types::sticker::{ApiSticker, ApiStickerPack, ApiStickerPackList};
+
+#[derive(Debug, Clone)]
+pub struct StickerPack {
+    pub id: Snowflake,
+    pub name: String,
+    pub description: String,
+    pub stickers: Vec<ApiSticker>,
+    pub cover_sticker_id: Option<Snowflake>,
+    pub banner_asset_id: Option<Snowflake>,
+}
+
+impl StickerPack {
+    pub fn from_api(data: &ApiStickerPack) -> Self {
+        Self {
+            id: data.id.clone(),
+            name: data.name.clone(),
+            description: data.description.clone(),
+            stickers: data.stickers.clone(),
+            cover_sticker_id: data.cover_sticker_id.clone(),
+            banner_asset_id: data.banner_asset_id.clone(),
+        }
+    }
+
+    pub fn cover_sticker(&self) -> Option<&ApiSticker> {
+        let id = self.cover_sticker_id.as_deref()?;
+        self.stickers.iter().find(|s| s.id == id)
+    }
+
+    pub async fn fetch(rest: &fluxer_rest::Rest, pack_id: &str) -> crate::Result<StickerPack> {
+        let data: ApiStickerPack = rest
+            .get(&fluxer_types::Routes::sticker_pack(pack_id))
+            .await?;
+        Ok(StickerPack::from_api(&data))
+    }
+
+    pub async fn fetch_all(rest: &fluxer_rest::Rest) -> crate::Result<Vec<StickerPack>> {
+        let data: ApiStickerPackList = rest.get(fluxer_types::Routes::sticker_packs()).await?;
+        Ok(data
+            .sticker_packs
+            .iter()
+            .map(StickerPack::from_api)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fluxer_rest::{MockTransport, Rest, RestOptions};
+    use serde_json::json;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn fetches_a_single_pack() {
+        let mock = MockTransport::new();
+        let rest = Rest::with_transport(RestOptions::default(), mock.clone());
+        mock.push_json(
+            200,
+            &json!({
+                "id": "5",
+                "name": "Starter",
+                "description": "Basics",
+                "stickers": [
+                    { "id": "6", "name": "wave", "description": "", "tags": ["hi"] },
+                    { "id": "7", "name": "nod", "description": "", "animated": true },
+                ],
+                "sku_id": "8",
+                "cover_sticker_id": "7",
+                "banner_asset_id": "9",
+            }),
+        );
+
+        let pack = StickerPack::fetch(&rest, "5").await.unwrap();
+
+        assert_eq!(pack.name, "Starter");
+        assert_eq!(pack.stickers.len(), 2);
+        assert_eq!(pack.stickers[0].tags, ["hi"]);
+        assert_eq!(pack.cover_sticker().map(|s| s.name.as_str()), Some("nod"));
+        assert_eq!(pack.banner_asset_id.as_deref(), Some("9"));
+        let request = mock.last_request().unwrap();
+        assert_eq!(request.method.as_str(), "GET");
+        assert!(request.url.ends_with("/sticker-packs/5"));
+    }
+}
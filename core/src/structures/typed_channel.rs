@@ -1,4 +1,8 @@
+use fluxer_types::ChannelType;
+use fluxer_types::channel::{ApiDefaultReaction, ApiForumTag, ApiThreadMetadata};
+
 use super::channel::Channel;
+use super::typing_indicator::TypingIndicator;
 
 #[derive(Debug)]
 pub enum TypedChannel<'a> {
@@ -6,21 +10,29 @@ pub enum TypedChannel<'a> {
     Voice(VoiceChannel<'a>),
     Category(CategoryChannel<'a>),
     Dm(DmChannel<'a>),
+    Stage(StageChannel<'a>),
+    Forum(ForumChannel<'a>),
+    PublicThread(ThreadChannel<'a>),
+    PrivateThread(ThreadChannel<'a>),
+    AnnouncementThread(ThreadChannel<'a>),
     Unknown(&'a Channel),
 }
 
 impl<'a> From<&'a Channel> for TypedChannel<'a> {
     fn from(ch: &'a Channel) -> Self {
-        if ch.is_voice() {
-            TypedChannel::Voice(VoiceChannel(ch))
-        } else if ch.is_category() {
-            TypedChannel::Category(CategoryChannel(ch))
-        } else if ch.is_dm() {
-            TypedChannel::Dm(DmChannel(ch))
-        } else if ch.is_text() {
-            TypedChannel::Text(TextChannel(ch))
-        } else {
-            TypedChannel::Unknown(ch)
+        match ChannelType::from_code(ch.kind) {
+            Some(ChannelType::GuildStageVoice) => TypedChannel::Stage(StageChannel(ch)),
+            Some(ChannelType::GuildForum) => TypedChannel::Forum(ForumChannel(ch)),
+            Some(ChannelType::PublicThread) => TypedChannel::PublicThread(ThreadChannel(ch)),
+            Some(ChannelType::PrivateThread) => TypedChannel::PrivateThread(ThreadChannel(ch)),
+            Some(ChannelType::AnnouncementThread) => {
+                TypedChannel::AnnouncementThread(ThreadChannel(ch))
+            }
+            _ if ch.is_voice() => TypedChannel::Voice(VoiceChannel(ch)),
+            _ if ch.is_category() => TypedChannel::Category(CategoryChannel(ch)),
+            _ if ch.is_dm() => TypedChannel::Dm(DmChannel(ch)),
+            _ if ch.is_text() => TypedChannel::Text(TextChannel(ch)),
+            _ => TypedChannel::Unknown(ch),
         }
     }
 }
@@ -49,6 +61,10 @@ impl<'a> TextChannel<'a> {
         self.0.last_message_id.as_deref()
     }
 
+    pub fn last_pin_timestamp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.0.last_pin_timestamp
+    }
+
     pub fn parent_id(&self) -> Option<&str> {
         self.0.parent_id.as_deref()
     }
@@ -74,6 +90,10 @@ impl<'a> TextChannel<'a> {
         self.0.send_typing(rest).await
     }
 
+    pub fn start_typing(&self, rest: &fluxer_rest::Rest) -> TypingIndicator {
+        self.0.start_typing(rest)
+    }
+
     pub async fn bulk_delete_messages(
         &self,
         rest: &fluxer_rest::Rest,
@@ -183,6 +203,10 @@ impl<'a> DmChannel<'a> {
         self.0.last_message_id.as_deref()
     }
 
+    pub fn last_pin_timestamp(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.0.last_pin_timestamp
+    }
+
     pub async fn send(
         &self,
         rest: &fluxer_rest::Rest,
@@ -223,3 +247,161 @@ impl std::ops::Deref for DmChannel<'_> {
         self.0
     }
 }
+
+#[derive(Debug)]
+pub struct StageChannel<'a>(pub &'a Channel);
+
+impl<'a> StageChannel<'a> {
+    pub fn inner(&self) -> &Channel {
+        self.0
+    }
+
+    pub fn topic(&self) -> Option<&str> {
+        self.0.topic.as_deref()
+    }
+
+    pub fn bitrate(&self) -> Option<u32> {
+        self.0.bitrate
+    }
+
+    pub fn user_limit(&self) -> Option<u32> {
+        self.0.user_limit
+    }
+
+    pub fn rtc_region(&self) -> Option<&str> {
+        self.0.rtc_region.as_deref()
+    }
+
+    pub fn parent_id(&self) -> Option<&str> {
+        self.0.parent_id.as_deref()
+    }
+}
+
+impl std::ops::Deref for StageChannel<'_> {
+    type Target = Channel;
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+#[derive(Debug)]
+pub struct ForumChannel<'a>(pub &'a Channel);
+
+impl<'a> ForumChannel<'a> {
+    pub fn inner(&self) -> &Channel {
+        self.0
+    }
+
+    /// Guidelines shown to users when creating a post.
+    pub fn topic(&self) -> Option<&str> {
+        self.0.topic.as_deref()
+    }
+
+    pub fn nsfw(&self) -> bool {
+        self.0.nsfw
+    }
+
+    pub fn available_tags(&self) -> &[ApiForumTag] {
+        &self.0.available_tags
+    }
+
+    pub fn default_reaction_emoji(&self) -> Option<&ApiDefaultReaction> {
+        self.0.default_reaction_emoji.as_ref()
+    }
+
+    pub async fn start_thread(
+        &self,
+        rest: &fluxer_rest::Rest,
+        body: &fluxer_types::channel::StartForumThreadRequest,
+    ) -> crate::Result<(Channel, Option<fluxer_types::message::ApiMessage>)> {
+        self.0.start_forum_thread(rest, body).await
+    }
+
+    pub fn parent_id(&self) -> Option<&str> {
+        self.0.parent_id.as_deref()
+    }
+}
+
+impl std::ops::Deref for ForumChannel<'_> {
+    type Target = Channel;
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+/// A public, private or announcement thread.
+#[derive(Debug)]
+pub struct ThreadChannel<'a>(pub &'a Channel);
+
+impl<'a> ThreadChannel<'a> {
+    pub fn inner(&self) -> &Channel {
+        self.0
+    }
+
+    /// The channel the thread was started in.
+    pub fn parent_id(&self) -> Option<&str> {
+        self.0.parent_id.as_deref()
+    }
+
+    pub fn owner_id(&self) -> Option<&str> {
+        self.0.owner_id.as_deref()
+    }
+
+    pub fn thread_metadata(&self) -> Option<&ApiThreadMetadata> {
+        self.0.thread_metadata.as_ref()
+    }
+
+    pub fn archived(&self) -> bool {
+        self.0.thread_metadata.as_ref().is_some_and(|m| m.archived)
+    }
+
+    pub fn locked(&self) -> bool {
+        self.0.thread_metadata.as_ref().is_some_and(|m| m.locked)
+    }
+
+    pub fn last_message_id(&self) -> Option<&str> {
+        self.0.last_message_id.as_deref()
+    }
+
+    pub fn rate_limit_per_user(&self) -> Option<u32> {
+        self.0.rate_limit_per_user
+    }
+
+    pub async fn send(
+        &self,
+        rest: &fluxer_rest::Rest,
+        body: &fluxer_builders::MessagePayloadData,
+    ) -> crate::Result<fluxer_types::message::ApiMessage> {
+        self.0.send(rest, body).await
+    }
+
+    pub async fn send_files(
+        &self,
+        rest: &fluxer_rest::Rest,
+        payload: &fluxer_builders::MessagePayloadData,
+        files: &[fluxer_builders::FileAttachment],
+    ) -> crate::Result<fluxer_types::message::ApiMessage> {
+        self.0.send_files(rest, payload, files).await
+    }
+
+    pub async fn send_typing(&self, rest: &fluxer_rest::Rest) -> crate::Result<()> {
+        self.0.send_typing(rest).await
+    }
+
+    pub async fn fetch_messages(
+        &self,
+        rest: &fluxer_rest::Rest,
+        limit: Option<u32>,
+        before: Option<&str>,
+        after: Option<&str>,
+    ) -> crate::Result<Vec<fluxer_types::message::ApiMessage>> {
+        self.0.fetch_messages(rest, limit, before, after).await
+    }
+}
+
+impl std::ops::Deref for ThreadChannel<'_> {
+    type Target = Channel;
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
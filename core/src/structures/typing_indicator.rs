@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+
+/// How often the typing indicator is re-sent; it expires on its own after about ten seconds.
+pub const TYPING_REFRESH_INTERVAL: Duration = Duration::from_secs(8);
+
+/// Shows the typing indicator in a channel for as long as the guard is held.
+///
+/// Typing is triggered immediately and again every [`TYPING_REFRESH_INTERVAL`] from a spawned
+/// task, which is aborted when the guard is dropped. Must be created inside a Tokio runtime.
+#[derive(Debug)]
+pub struct TypingIndicator {
+    task: JoinHandle<()>,
+}
+
+impl TypingIndicator {
+    pub fn start(rest: fluxer_rest::Rest, channel_id: &str) -> Self {
+        let route = fluxer_types::Routes::channel_typing(channel_id);
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(TYPING_REFRESH_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(err) = rest
+                    .post::<serde_json::Value>(&route, Option::<&()>::None)
+                    .await
+                {
+                    tracing::warn!("failed to trigger typing in {route}: {err}");
+                }
+            }
+        });
+        Self { task }
+    }
+
+    /// Stops the indicator; same as dropping the guard.
+    pub fn stop(self) {}
+}
+
+impl Drop for TypingIndicator {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
@@ -1,5 +1,6 @@
 use fluxer_types::Snowflake;
-use fluxer_types::user::ApiUser;
+use fluxer_types::user::{ApiUser, PremiumType};
+use fluxer_util::{Badge, UserFlags};
 
 use crate::util::cdn::{self, CdnOptions};
 
@@ -15,6 +16,7 @@ pub struct User {
     pub flags: Option<u32>,
     pub system: bool,
     pub banner: Option<String>,
+    pub premium_type: Option<PremiumType>,
 }
 
 impl User {
@@ -30,6 +32,7 @@ impl User {
             flags: None,
             system: false,
             banner: None,
+            premium_type: None,
         }
     }
 
@@ -45,6 +48,7 @@ impl User {
             flags: data.flags.or(data.public_flags),
             system: data.system.unwrap_or(false),
             banner: data.banner.clone(),
+            premium_type: data.premium_type,
         }
     }
 
@@ -62,6 +66,17 @@ impl User {
         if let Some(b) = &data.banner {
             self.banner = Some(b.clone());
         }
+        if let Some(p) = data.premium_type {
+            self.premium_type = Some(p);
+        }
+    }
+
+    pub fn user_flags(&self) -> UserFlags {
+        UserFlags::from_bits_truncate(self.flags.unwrap_or(0) as u64)
+    }
+
+    pub fn badges(&self) -> Vec<Badge> {
+        self.user_flags().badges()
     }
 
     pub fn avatar_url(&self, opts: &CdnOptions) -> Option<String> {
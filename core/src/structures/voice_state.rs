@@ -0,0 +1,37 @@
+use fluxer_types::Snowflake;
+use fluxer_types::gateway::ApiVoiceState;
+
+/// A user's connection to a guild voice channel.
+#[derive(Debug, Clone)]
+pub struct VoiceState {
+    pub guild_id: Snowflake,
+    pub channel_id: Snowflake,
+    pub user_id: Snowflake,
+    pub session_id: String,
+    pub mute: bool,
+    pub deaf: bool,
+    pub self_mute: bool,
+    pub self_deaf: bool,
+    pub self_video: bool,
+    pub self_stream: bool,
+    pub suppress: bool,
+}
+
+impl VoiceState {
+    /// `None` when the user is not in a channel, i.e. the update is a disconnect.
+    pub fn from_api(data: &ApiVoiceState, guild_id: &str) -> Option<Self> {
+        Some(Self {
+            guild_id: guild_id.to_string(),
+            channel_id: data.channel_id.clone()?,
+            user_id: data.user_id.clone(),
+            session_id: data.session_id.clone(),
+            mute: data.mute.unwrap_or(false),
+            deaf: data.deaf.unwrap_or(false),
+            self_mute: data.self_mute.unwrap_or(false),
+            self_deaf: data.self_deaf.unwrap_or(false),
+            self_video: data.self_video.unwrap_or(false),
+            self_stream: data.self_stream.unwrap_or(false),
+            suppress: data.suppress.unwrap_or(false),
+        })
+    }
+}
@@ -1,5 +1,5 @@
 use fluxer_types::Snowflake;
-use fluxer_types::webhook::ApiWebhook;
+use fluxer_types::webhook::{ApiWebhook, ExecuteWebhookRequest};
 
 use crate::structures::user::User;
 use crate::util::cdn::{self, CdnOptions};
@@ -47,6 +47,7 @@ impl Webhook {
                 flags: None,
                 system: false,
                 banner: None,
+                premium_type: None,
             },
         }
     }
@@ -81,18 +82,7 @@ impl Webhook {
         body: &serde_json::Value,
         wait: bool,
     ) -> crate::Result<Option<fluxer_types::message::ApiMessage>> {
-        let token = self
-            .token
-            .as_deref()
-            .ok_or(crate::Error::WebhookTokenRequired)?;
-        let route = if wait {
-            format!(
-                "{}?wait=true",
-                fluxer_types::Routes::webhook_execute(&self.id, token)
-            )
-        } else {
-            fluxer_types::Routes::webhook_execute(&self.id, token)
-        };
+        let route = self.execute_route(wait, None)?;
         if wait {
             let msg: fluxer_types::message::ApiMessage = rest.post(&route, Some(body)).await?;
             Ok(Some(msg))
@@ -102,6 +92,47 @@ impl Webhook {
         }
     }
 
+    /// Executes the webhook with a typed body, optionally targeting an existing thread, without
+    /// waiting for the created message.
+    pub async fn execute(
+        &self,
+        rest: &fluxer_rest::Rest,
+        body: &ExecuteWebhookRequest,
+        thread_id: Option<&str>,
+    ) -> crate::Result<()> {
+        let route = self.typed_execute_route(body, false, thread_id)?;
+        let _: serde_json::Value = rest.post(&route, Some(body)).await?;
+        Ok(())
+    }
+
+    /// Like [`execute`](Self::execute), but waits for the server to create the message and
+    /// returns it.
+    pub async fn execute_and_wait(
+        &self,
+        rest: &fluxer_rest::Rest,
+        body: &ExecuteWebhookRequest,
+        thread_id: Option<&str>,
+    ) -> crate::Result<fluxer_types::message::ApiMessage> {
+        let route = self.typed_execute_route(body, true, thread_id)?;
+        let msg: fluxer_types::message::ApiMessage = rest.post(&route, Some(body)).await?;
+        Ok(msg)
+    }
+
+    #[deprecated(note = "use `execute` or `execute_and_wait`")]
+    pub async fn execute_with_wait(
+        &self,
+        rest: &fluxer_rest::Rest,
+        body: &ExecuteWebhookRequest,
+        wait: bool,
+        thread_id: Option<&str>,
+    ) -> crate::Result<Option<fluxer_types::message::ApiMessage>> {
+        if wait {
+            self.execute_and_wait(rest, body, thread_id).await.map(Some)
+        } else {
+            self.execute(rest, body, thread_id).await.map(|()| None)
+        }
+    }
+
     pub async fn send_files(
         &self,
         rest: &fluxer_rest::Rest,
@@ -109,18 +140,22 @@ impl Webhook {
         files: &[fluxer_builders::FileAttachment],
         wait: bool,
     ) -> crate::Result<Option<fluxer_types::message::ApiMessage>> {
-        let token = self
-            .token
-            .as_deref()
-            .ok_or(crate::Error::WebhookTokenRequired)?;
-        let route = if wait {
-            format!(
-                "{}?wait=true",
-                fluxer_types::Routes::webhook_execute(&self.id, token)
-            )
-        } else {
-            fluxer_types::Routes::webhook_execute(&self.id, token)
-        };
+        self.execute_with_files(rest, payload, files, wait, None)
+            .await
+    }
+
+    /// Executes the webhook as a multipart request with `payload_json` and `files[n]` parts.
+    ///
+    /// When `thread_id` is set the message is posted into that thread of the webhook's channel.
+    pub async fn execute_with_files(
+        &self,
+        rest: &fluxer_rest::Rest,
+        payload: &fluxer_builders::MessagePayloadData,
+        files: &[fluxer_builders::FileAttachment],
+        wait: bool,
+        thread_id: Option<&str>,
+    ) -> crate::Result<Option<fluxer_types::message::ApiMessage>> {
+        let route = self.execute_route(wait, thread_id)?;
         let form = fluxer_builders::build_multipart_form(payload, files);
         if wait {
             let msg: fluxer_types::message::ApiMessage = rest.post_multipart(&route, form).await?;
@@ -131,8 +166,114 @@ impl Webhook {
         }
     }
 
+    fn typed_execute_route(
+        &self,
+        body: &ExecuteWebhookRequest,
+        wait: bool,
+        thread_id: Option<&str>,
+    ) -> crate::Result<String> {
+        if body.thread_name.is_some() && thread_id.is_some() {
+            return Err(crate::Error::WebhookThreadConflict);
+        }
+        self.execute_route(wait, thread_id)
+    }
+
+    fn execute_route(&self, wait: bool, thread_id: Option<&str>) -> crate::Result<String> {
+        let token = self
+            .token
+            .as_deref()
+            .ok_or(crate::Error::WebhookTokenRequired)?;
+        let mut query = fluxer_types::QueryValues::new();
+        if wait {
+            query.insert("wait", true);
+        }
+        query.insert_opt("thread_id", thread_id);
+        Ok(format!(
+            "{}{query}",
+            fluxer_types::Routes::webhook_execute(&self.id, token)
+        ))
+    }
+
     pub async fn fetch(rest: &fluxer_rest::Rest, webhook_id: &str) -> crate::Result<Webhook> {
         let data: ApiWebhook = rest.get(&fluxer_types::Routes::webhook(webhook_id)).await?;
         Ok(Webhook::from_api(&data))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use fluxer_rest::{MockTransport, Rest, RestOptions};
+    use serde_json::json;
+
+    use super::*;
+
+    fn body() -> ExecuteWebhookRequest {
+        ExecuteWebhookRequest {
+            content: Some("hi".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn execute_does_not_wait() {
+        let mock = MockTransport::new();
+        let rest = Rest::with_transport(RestOptions::default(), mock.clone());
+        mock.push_response(204, Vec::new());
+
+        Webhook::from_token("1", "tok")
+            .execute(&rest, &body(), None)
+            .await
+            .unwrap();
+
+        let req = mock.last_request().unwrap();
+        assert_eq!(req.method.as_str(), "POST");
+        assert!(req.url.ends_with("/webhooks/1/tok"));
+        assert_eq!(req.json().unwrap(), json!({ "content": "hi" }));
+    }
+
+    #[tokio::test]
+    async fn execute_and_wait_returns_the_message() {
+        let mock = MockTransport::new();
+        let rest = Rest::with_transport(RestOptions::default(), mock.clone());
+        mock.push_json(
+            200,
+            &json!({
+                "id": "5",
+                "channel_id": "2",
+                "author": { "id": "1", "username": "hook", "discriminator": "0" },
+                "type": 0,
+                "content": "hi",
+                "timestamp": "2024-01-01T00:00:00.000Z",
+                "pinned": false,
+                "webhook_id": "1",
+            }),
+        );
+
+        let msg = Webhook::from_token("1", "tok")
+            .execute_and_wait(&rest, &body(), Some("3"))
+            .await
+            .unwrap();
+
+        assert_eq!(msg.id, "5");
+        assert_eq!(msg.webhook_id.as_deref(), Some("1"));
+        let url = mock.last_request().unwrap().url;
+        assert!(url.ends_with("/webhooks/1/tok?wait=true&thread_id=3"));
+    }
+
+    #[tokio::test]
+    async fn rejects_a_thread_name_with_a_thread_id() {
+        let mock = MockTransport::new();
+        let rest = Rest::with_transport(RestOptions::default(), mock.clone());
+        let body = ExecuteWebhookRequest {
+            thread_name: Some("t".to_string()),
+            ..body()
+        };
+
+        let result = Webhook::from_token("1", "tok")
+            .execute(&rest, &body, Some("3"))
+            .await;
+
+        assert!(matches!(result, Err(crate::Error::WebhookThreadConflict)));
+        assert!(mock.requests().is_empty());
+    }
+}
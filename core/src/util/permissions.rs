@@ -1,6 +1,33 @@
 use fluxer_types::channel::{ApiChannelOverwrite, OverwriteType};
+use fluxer_types::role::ApiRole;
 use fluxer_util::Permissions;
 
+/// Guild-level permissions before channel overwrites: `@everyone` (whose id is the guild id)
+/// plus every role the member has. Feed the result into [`compute_permissions`].
+pub fn base_permissions(
+    member_roles: &[String],
+    everyone_role_id: &str,
+    roles: &[ApiRole],
+    is_owner: bool,
+) -> Permissions {
+    if is_owner {
+        return Permissions::all();
+    }
+
+    let mut perms = Permissions::empty();
+    for role in roles {
+        if role.id == everyone_role_id || member_roles.iter().any(|r| r == &role.id) {
+            perms |= fluxer_util::parse_permissions(&role.permissions);
+        }
+    }
+
+    if perms.contains(Permissions::ADMINISTRATOR) {
+        Permissions::all()
+    } else {
+        perms
+    }
+}
+
 pub fn compute_permissions(
     base_permissions: Permissions,
     overwrites: &[ApiChannelOverwrite],
@@ -40,3 +67,62 @@ pub fn has_permission(bitfield: Permissions, permission: Permissions) -> bool {
     }
     bitfield.contains(permission)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn role(id: &str, permissions: Permissions) -> ApiRole {
+        ApiRole {
+            id: id.to_string(),
+            name: id.to_string(),
+            color: 0,
+            position: 0,
+            hoist_position: None,
+            permissions: permissions.bits().to_string(),
+            hoist: false,
+            mentionable: false,
+            icon: None,
+            unicode_emoji: None,
+        }
+    }
+
+    fn roles() -> Vec<ApiRole> {
+        vec![
+            role("1", Permissions::VIEW_CHANNEL),
+            role("2", Permissions::SEND_MESSAGES),
+            role("3", Permissions::KICK_MEMBERS),
+            role("4", Permissions::ADMINISTRATOR),
+        ]
+    }
+
+    #[test]
+    fn owner_gets_everything() {
+        assert_eq!(
+            base_permissions(&[], "1", &roles(), true),
+            Permissions::all()
+        );
+    }
+
+    #[test]
+    fn administrator_role_grants_everything() {
+        let member_roles = ["4".to_string()];
+        assert_eq!(
+            base_permissions(&member_roles, "1", &roles(), false),
+            Permissions::all()
+        );
+    }
+
+    #[test]
+    fn combines_everyone_and_member_roles() {
+        let member_roles = ["2".to_string()];
+        assert_eq!(
+            base_permissions(&member_roles, "1", &roles(), false),
+            Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES
+        );
+        assert_eq!(
+            base_permissions(&[], "1", &roles(), false),
+            Permissions::VIEW_CHANNEL
+        );
+    }
+}
@@ -1,17 +1,28 @@
+use std::fmt;
+use std::str::FromStr;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderValue, USER_AGENT};
+use base64::Engine;
+use reqwest::header::{
+    AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue, USER_AGENT,
+};
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 
+use fluxer_types::gateway::ApiGatewayBotResponse;
+
 use crate::error::{FieldError, FluxerApiError, HttpError, RateLimitError, RestError};
 use crate::rate_limit::RateLimitManager;
+use crate::transport::{ReqwestTransport, Transport, TransportBody, TransportRequest};
 
 const DEFAULT_API_URL: &str = "https://api.fluxer.app/v1";
 const DEFAULT_USER_AGENT: &str = "FluxerBot (Rust, 0.1)";
 const DEFAULT_TIMEOUT_SECS: u64 = 15;
 const MAX_RETRIES: u32 = 3;
+const DEFAULT_GATEWAY_CACHE_TTL_SECS: u64 = 60;
+const AUDIT_LOG_REASON: HeaderName = HeaderName::from_static("x-audit-log-reason");
+const SUPER_PROPERTIES: HeaderName = HeaderName::from_static("x-super-properties");
 
 #[derive(Debug, Clone)]
 pub struct RestOptions {
@@ -19,6 +30,20 @@ pub struct RestOptions {
     pub user_agent: String,
     pub timeout: Duration,
     pub max_retries: u32,
+    pub gateway_cache_ttl: Duration,
+    /// Client identity sent base64-encoded as `X-Super-Properties` on every request. Only
+    /// needed by user-account clients; leave unset for bots.
+    pub super_properties: Option<serde_json::Value>,
+    /// Sent with every request. Headers the client sets itself (user agent, content type,
+    /// authorization, audit log reason) take precedence.
+    pub default_headers: HeaderMap,
+    /// Routes every request through this `http://`, `https://` or `socks5://` proxy.
+    /// Credentials may be given in the URL as `user:pass@host`.
+    pub proxy: Option<String>,
+    /// Skips TLS certificate validation, e.g. behind an intercepting debug proxy. Never enable
+    /// this in production.
+    #[cfg(feature = "danger-accept-invalid-certs")]
+    pub danger_accept_invalid_certs: bool,
 }
 
 impl Default for RestOptions {
@@ -28,29 +53,120 @@ impl Default for RestOptions {
             user_agent: DEFAULT_USER_AGENT.to_string(),
             timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
             max_retries: MAX_RETRIES,
+            gateway_cache_ttl: Duration::from_secs(DEFAULT_GATEWAY_CACHE_TTL_SECS),
+            super_properties: None,
+            default_headers: HeaderMap::new(),
+            proxy: None,
+            #[cfg(feature = "danger-accept-invalid-certs")]
+            danger_accept_invalid_certs: false,
         }
     }
 }
 
+/// Method and absolute URL of a request, as returned by [`Rest::preview`]. Displays and parses
+/// as `"{METHOD} {url}"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestPreview {
+    pub method: reqwest::Method,
+    pub url: String,
+}
+
+impl RequestPreview {
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+impl fmt::Display for RequestPreview {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.url)
+    }
+}
+
+impl FromStr for RequestPreview {
+    type Err = RestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (method, url) = s
+            .split_once(' ')
+            .ok_or_else(|| RestError::InvalidRequest(format!("expected \"METHOD url\": {s}")))?;
+        let method = reqwest::Method::from_bytes(method.as_bytes())
+            .map_err(|_| RestError::InvalidRequest(format!("invalid method: {method}")))?;
+        Ok(Self {
+            method,
+            url: url.to_string(),
+        })
+    }
+}
+
+/// Which credential a request is authorized with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AuthPolicy {
+    /// The token from [`Rest::set_token`], if any.
+    #[default]
+    Bot,
+    /// Endpoints bot tokens can't call. Uses the token from [`Rest::set_bearer_token`], or the
+    /// client token when that is itself a bearer token; fails if neither is available.
+    NoBot,
+    /// No `Authorization` header.
+    None,
+}
+
+/// The credential for one request: a policy over the client's tokens, or an explicit bearer
+/// token supplied by the caller.
+#[derive(Debug, Clone, Copy)]
+enum Credentials<'a> {
+    Policy(AuthPolicy),
+    Bearer(&'a str),
+}
+
+impl From<AuthPolicy> for Credentials<'_> {
+    fn from(policy: AuthPolicy) -> Self {
+        Self::Policy(policy)
+    }
+}
+
 #[derive(Clone)]
 pub struct Rest {
-    http: reqwest::Client,
+    transport: Arc<dyn Transport>,
     options: RestOptions,
     token: Arc<tokio::sync::RwLock<Option<String>>>,
+    bearer: Arc<tokio::sync::RwLock<Option<String>>>,
     rate_limiter: Arc<RateLimitManager>,
+    gateway_cache: Arc<tokio::sync::Mutex<Option<(Instant, ApiGatewayBotResponse)>>>,
+    super_properties: Option<HeaderValue>,
 }
 
 impl Rest {
+    /// # Panics
+    ///
+    /// If `options.proxy` is not a valid proxy URL; use [`Rest::try_new`] to handle that.
     pub fn new(options: RestOptions) -> Self {
-        let http = reqwest::Client::builder()
-            .timeout(options.timeout)
-            .build()
-            .expect("TLS backend available");
+        Self::try_new(options).expect("invalid REST options")
+    }
+
+    /// Builds a client over `reqwest` with the timeout, proxy and TLS settings of `options`.
+    ///
+    /// Fails with [`RestError::InvalidRequest`] if `options.proxy` is not a valid proxy URL.
+    pub fn try_new(options: RestOptions) -> Result<Self, RestError> {
+        let transport = ReqwestTransport::from_options(&options)?;
+        Ok(Self::with_transport(options, transport))
+    }
+
+    /// Builds a client that sends every request through `transport` instead of `reqwest`.
+    pub fn with_transport(options: RestOptions, transport: impl Transport) -> Self {
+        let super_properties = options.super_properties.as_ref().map(|props| {
+            let encoded = base64::engine::general_purpose::STANDARD.encode(props.to_string());
+            HeaderValue::from_str(&encoded).expect("base64 is a valid header value")
+        });
         Self {
-            http,
+            transport: Arc::new(transport),
             options,
             token: Arc::new(tokio::sync::RwLock::new(None)),
+            bearer: Arc::new(tokio::sync::RwLock::new(None)),
             rate_limiter: Arc::new(RateLimitManager::new()),
+            gateway_cache: Arc::new(tokio::sync::Mutex::new(None)),
+            super_properties,
         }
     }
 
@@ -65,17 +181,66 @@ impl Rest {
         *guard = Some(normalized);
     }
 
-    pub async fn get<T: DeserializeOwned>(&self, route: &str) -> Result<T, RestError> {
-        self.request(reqwest::Method::GET, route, Option::<&()>::None)
+    /// Stores an OAuth2 access token for [`AuthPolicy::NoBot`] requests; `None` clears it.
+    pub async fn set_bearer_token(&self, token: Option<String>) {
+        *self.bearer.write().await = token;
+    }
+
+    /// Sends a JSON request authorized according to `auth`.
+    pub async fn request_with_auth<T: DeserializeOwned>(
+        &self,
+        method: reqwest::Method,
+        route: &str,
+        body: Option<&(impl Serialize + Sync)>,
+        auth: AuthPolicy,
+    ) -> Result<T, RestError> {
+        self.request(method, route, body, None, auth).await
+    }
+
+    /// Sends a JSON request authorized with an OAuth2 `bearer_token` instead of the client's
+    /// credentials.
+    pub async fn request_with_bearer<T: DeserializeOwned>(
+        &self,
+        method: reqwest::Method,
+        route: &str,
+        body: Option<&(impl Serialize + Sync)>,
+        bearer_token: &str,
+    ) -> Result<T, RestError> {
+        self.request(method, route, body, None, Credentials::Bearer(bearer_token))
             .await
     }
 
+    /// Fetches `/gateway/bot`, reusing the last response until `gateway_cache_ttl` elapses.
+    pub async fn gateway_bot(&self) -> Result<ApiGatewayBotResponse, RestError> {
+        let mut cache = self.gateway_cache.lock().await;
+        if let Some((fetched_at, gateway)) = cache.as_ref()
+            && fetched_at.elapsed() < self.options.gateway_cache_ttl
+        {
+            return Ok(gateway.clone());
+        }
+        let gateway: ApiGatewayBotResponse = self.get(fluxer_types::Routes::gateway_bot()).await?;
+        *cache = Some((Instant::now(), gateway.clone()));
+        Ok(gateway)
+    }
+
+    pub async fn get<T: DeserializeOwned>(&self, route: &str) -> Result<T, RestError> {
+        self.request(
+            reqwest::Method::GET,
+            route,
+            Option::<&()>::None,
+            None,
+            AuthPolicy::Bot,
+        )
+        .await
+    }
+
     pub async fn post<T: DeserializeOwned>(
         &self,
         route: &str,
         body: Option<&(impl Serialize + Sync)>,
     ) -> Result<T, RestError> {
-        self.request(reqwest::Method::POST, route, body).await
+        self.request(reqwest::Method::POST, route, body, None, AuthPolicy::Bot)
+            .await
     }
 
     pub async fn patch<T: DeserializeOwned>(
@@ -83,7 +248,8 @@ impl Rest {
         route: &str,
         body: Option<&(impl Serialize + Sync)>,
     ) -> Result<T, RestError> {
-        self.request(reqwest::Method::PATCH, route, body).await
+        self.request(reqwest::Method::PATCH, route, body, None, AuthPolicy::Bot)
+            .await
     }
 
     pub async fn put<T: DeserializeOwned>(
@@ -91,15 +257,68 @@ impl Rest {
         route: &str,
         body: Option<&(impl Serialize + Sync)>,
     ) -> Result<T, RestError> {
-        self.request(reqwest::Method::PUT, route, body).await
+        self.request(reqwest::Method::PUT, route, body, None, AuthPolicy::Bot)
+            .await
     }
 
     pub async fn delete_route(&self, route: &str) -> Result<(), RestError> {
-        self.request_empty(reqwest::Method::DELETE, route).await
+        self.request_empty(reqwest::Method::DELETE, route, None, AuthPolicy::Bot)
+            .await
     }
 
     pub async fn put_empty(&self, route: &str) -> Result<(), RestError> {
-        self.request_empty(reqwest::Method::PUT, route).await
+        self.request_empty(reqwest::Method::PUT, route, None, AuthPolicy::Bot)
+            .await
+    }
+
+    /// Like [`Rest::post`], recording `reason` in the guild audit log.
+    pub async fn post_with_reason<T: DeserializeOwned>(
+        &self,
+        route: &str,
+        body: Option<&(impl Serialize + Sync)>,
+        reason: Option<&str>,
+    ) -> Result<T, RestError> {
+        self.request(reqwest::Method::POST, route, body, reason, AuthPolicy::Bot)
+            .await
+    }
+
+    /// Like [`Rest::patch`], recording `reason` in the guild audit log.
+    pub async fn patch_with_reason<T: DeserializeOwned>(
+        &self,
+        route: &str,
+        body: Option<&(impl Serialize + Sync)>,
+        reason: Option<&str>,
+    ) -> Result<T, RestError> {
+        self.request(reqwest::Method::PATCH, route, body, reason, AuthPolicy::Bot)
+            .await
+    }
+
+    pub async fn put_with_reason<T: DeserializeOwned>(
+        &self,
+        route: &str,
+        body: Option<&(impl Serialize + Sync)>,
+        reason: Option<&str>,
+    ) -> Result<T, RestError> {
+        self.request(reqwest::Method::PUT, route, body, reason, AuthPolicy::Bot)
+            .await
+    }
+
+    pub async fn put_empty_with_reason(
+        &self,
+        route: &str,
+        reason: Option<&str>,
+    ) -> Result<(), RestError> {
+        self.request_empty(reqwest::Method::PUT, route, reason, AuthPolicy::Bot)
+            .await
+    }
+
+    pub async fn delete_with_reason(
+        &self,
+        route: &str,
+        reason: Option<&str>,
+    ) -> Result<(), RestError> {
+        self.request_empty(reqwest::Method::DELETE, route, reason, AuthPolicy::Bot)
+            .await
     }
 
     pub async fn post_multipart<T: DeserializeOwned>(
@@ -107,7 +326,7 @@ impl Rest {
         route: &str,
         form: reqwest::multipart::Form,
     ) -> Result<T, RestError> {
-        self.request_multipart(reqwest::Method::POST, route, form)
+        self.request_multipart(reqwest::Method::POST, route, form, AuthPolicy::Bot)
             .await
     }
 
@@ -116,33 +335,99 @@ impl Rest {
         route: &str,
         form: reqwest::multipart::Form,
     ) -> Result<T, RestError> {
-        self.request_multipart(reqwest::Method::PATCH, route, form)
+        self.request_multipart(reqwest::Method::PATCH, route, form, AuthPolicy::Bot)
             .await
     }
 
+    /// Fetches a route (or an absolute URL, e.g. a CDN asset) as raw bytes without JSON parsing.
+    pub async fn get_bytes(&self, route: &str) -> Result<Vec<u8>, RestError> {
+        self.request_bytes(route, AuthPolicy::Bot.into()).await
+    }
+
+    /// Like [`Rest::get_bytes`], but authorizes with the given OAuth2 bearer token instead of
+    /// the client's token. Like the client's token, it is never sent to absolute URLs.
+    pub async fn get_bytes_with_auth(
+        &self,
+        route: &str,
+        bearer_token: &str,
+    ) -> Result<Vec<u8>, RestError> {
+        self.request_bytes(route, Credentials::Bearer(bearer_token))
+            .await
+    }
+
+    /// The absolute URL a request to `route` is sent to.
+    pub fn url(&self, route: &str) -> String {
+        format!("{}{}", self.options.api_url, route)
+    }
+
+    /// Resolves a request without sending it, e.g. for logging or assertions.
+    pub fn preview(
+        &self,
+        method: reqwest::Method,
+        route: &str,
+        query: &fluxer_types::QueryValues,
+    ) -> RequestPreview {
+        RequestPreview {
+            method,
+            url: format!("{}{query}", self.url(route)),
+        }
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "http_request",
+            level = "debug",
+            skip_all,
+            fields(
+                method = %method,
+                route = %crate::rate_limit::route_template(route),
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            ),
+        )
+    )]
     async fn request<T: DeserializeOwned>(
         &self,
         method: reqwest::Method,
         route: &str,
         body: Option<&(impl Serialize + Sync)>,
+        reason: Option<&str>,
+        auth: impl Into<Credentials<'_>>,
     ) -> Result<T, RestError> {
-        let url = format!("{}{}", self.options.api_url, route);
+        let auth = auth.into();
+        let url = self.url(route);
         let mut attempt = 0u32;
 
         loop {
-            self.rate_limiter.wait_if_needed(route).await;
-
-            let mut req = self.http.request(method.clone(), &url);
-            req = req.headers(self.build_headers().await);
-
-            if let Some(b) = body {
-                req = req.json(b);
+            self.rate_limiter
+                .wait_if_needed(method.as_str(), route)
+                .await;
+
+            let mut headers = self.build_headers().await;
+            self.apply_auth(&mut headers, auth).await?;
+            if let Some(value) = audit_log_reason(reason) {
+                headers.insert(AUDIT_LOG_REASON, value);
             }
-
-            let res = req.send().await?;
-            let status = res.status().as_u16();
-            self.read_rate_limit_headers_from(route, res.headers());
-            let text = res.text().await.unwrap_or_default();
+            let body = match body {
+                Some(b) => TransportBody::Json(serde_json::to_vec(b)?),
+                None => TransportBody::Empty,
+            };
+
+            let started = Instant::now();
+            let res = self
+                .transport
+                .send(TransportRequest {
+                    method: method.clone(),
+                    url: url.clone(),
+                    headers,
+                    body,
+                })
+                .await?;
+            let status = res.status;
+            trace_request(status, started);
+            self.read_rate_limit_headers_from(method.as_str(), route, &res.headers);
+            let text = String::from_utf8_lossy(&res.body);
 
             if status == 429
                 && let Ok(rl) = serde_json::from_str::<fluxer_types::RateLimitErrorBody>(&text)
@@ -175,18 +460,134 @@ impl Rest {
         }
     }
 
-    async fn request_empty(&self, method: reqwest::Method, route: &str) -> Result<(), RestError> {
-        let url = format!("{}{}", self.options.api_url, route);
-        self.rate_limiter.wait_if_needed(route).await;
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "http_request",
+            level = "debug",
+            skip_all,
+            fields(
+                method = "GET",
+                route = %crate::rate_limit::route_template(route),
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            ),
+        )
+    )]
+    async fn request_bytes(
+        &self,
+        route: &str,
+        auth: Credentials<'_>,
+    ) -> Result<Vec<u8>, RestError> {
+        let absolute = route.starts_with("http://") || route.starts_with("https://");
+        let url = if absolute {
+            route.to_string()
+        } else {
+            self.url(route)
+        };
+        let method = reqwest::Method::GET;
+        let mut attempt = 0u32;
+
+        loop {
+            self.rate_limiter
+                .wait_if_needed(method.as_str(), route)
+                .await;
+
+            let mut headers = self.build_headers().await;
+            headers.remove(CONTENT_TYPE);
+            self.apply_auth(&mut headers, auth).await?;
+            if absolute {
+                // Never forward a token to hosts outside the API.
+                headers.remove(AUTHORIZATION);
+            }
+
+            let started = Instant::now();
+            let res = self
+                .transport
+                .send(TransportRequest {
+                    method: method.clone(),
+                    url: url.clone(),
+                    headers,
+                    body: TransportBody::Empty,
+                })
+                .await?;
+            let status = res.status;
+            trace_request(status, started);
+            self.read_rate_limit_headers_from(method.as_str(), route, &res.headers);
+
+            if status == 429 || status >= 400 {
+                let text = String::from_utf8_lossy(&res.body);
+                if status == 429
+                    && let Ok(rl) = serde_json::from_str::<fluxer_types::RateLimitErrorBody>(&text)
+                {
+                    let global = rl.global.unwrap_or(false);
+                    if global {
+                        self.rate_limiter.set_global(rl.retry_after);
+                    }
+                    attempt += 1;
+                    if attempt < self.options.max_retries {
+                        tokio::time::sleep(Duration::from_secs_f64(rl.retry_after)).await;
+                        continue;
+                    }
+                    return Err(RateLimitError {
+                        retry_after: rl.retry_after,
+                        global,
+                        message: rl.message,
+                    }
+                    .into());
+                }
+                return Err(self.parse_error(status, &text));
+            }
+
+            return Ok(res.body);
+        }
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "http_request",
+            level = "debug",
+            skip_all,
+            fields(
+                method = %method,
+                route = %crate::rate_limit::route_template(route),
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            ),
+        )
+    )]
+    async fn request_empty(
+        &self,
+        method: reqwest::Method,
+        route: &str,
+        reason: Option<&str>,
+        auth: AuthPolicy,
+    ) -> Result<(), RestError> {
+        let url = self.url(route);
+        self.rate_limiter
+            .wait_if_needed(method.as_str(), route)
+            .await;
 
-        let req = self
-            .http
-            .request(method, &url)
-            .headers(self.build_headers().await);
-        let res = req.send().await?;
-        let status = res.status().as_u16();
-        self.read_rate_limit_headers_from(route, res.headers());
-        let text = res.text().await.unwrap_or_default();
+        let mut headers = self.build_headers().await;
+        self.apply_auth(&mut headers, auth.into()).await?;
+        if let Some(value) = audit_log_reason(reason) {
+            headers.insert(AUDIT_LOG_REASON, value);
+        }
+        let started = Instant::now();
+        let res = self
+            .transport
+            .send(TransportRequest {
+                method: method.clone(),
+                url,
+                headers,
+                body: TransportBody::Empty,
+            })
+            .await?;
+        let status = res.status;
+        trace_request(status, started);
+        self.read_rate_limit_headers_from(method.as_str(), route, &res.headers);
+        let text = String::from_utf8_lossy(&res.body);
 
         if status == 429
             && let Ok(rl) = serde_json::from_str::<fluxer_types::RateLimitErrorBody>(&text)
@@ -206,29 +607,51 @@ impl Rest {
         Ok(())
     }
 
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "http_request",
+            level = "debug",
+            skip_all,
+            fields(
+                method = %method,
+                route = %crate::rate_limit::route_template(route),
+                status = tracing::field::Empty,
+                latency_ms = tracing::field::Empty,
+            ),
+        )
+    )]
     async fn request_multipart<T: DeserializeOwned>(
         &self,
         method: reqwest::Method,
         route: &str,
         form: reqwest::multipart::Form,
+        auth: AuthPolicy,
     ) -> Result<T, RestError> {
-        let url = format!("{}{}", self.options.api_url, route);
-        self.rate_limiter.wait_if_needed(route).await;
+        let url = self.url(route);
+        self.rate_limiter
+            .wait_if_needed(method.as_str(), route)
+            .await;
 
         let mut headers = self.build_headers().await;
         headers.remove(CONTENT_TYPE);
+        self.apply_auth(&mut headers, auth.into()).await?;
 
+        let started = Instant::now();
         let res = self
-            .http
-            .request(method, &url)
-            .headers(headers)
-            .multipart(form)
-            .send()
+            .transport
+            .send(TransportRequest {
+                method: method.clone(),
+                url,
+                headers,
+                body: TransportBody::Multipart(form),
+            })
             .await?;
 
-        let status = res.status().as_u16();
-        self.read_rate_limit_headers_from(route, res.headers());
-        let text = res.text().await.unwrap_or_default();
+        let status = res.status;
+        trace_request(status, started);
+        self.read_rate_limit_headers_from(method.as_str(), route, &res.headers);
+        let text = String::from_utf8_lossy(&res.body);
 
         if status >= 400 {
             return Err(self.parse_error(status, &text));
@@ -279,12 +702,15 @@ impl Rest {
     }
 
     async fn build_headers(&self) -> HeaderMap {
-        let mut headers = HeaderMap::new();
+        let mut headers = self.options.default_headers.clone();
         headers.insert(
             USER_AGENT,
             HeaderValue::from_str(&self.options.user_agent).expect("valid user agent"),
         );
         headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        if let Some(props) = &self.super_properties {
+            headers.insert(SUPER_PROPERTIES, props.clone());
+        }
         let token = self.token.read().await;
         if let Some(ref t) = *token
             && let Ok(val) = HeaderValue::from_str(t)
@@ -294,7 +720,39 @@ impl Rest {
         headers
     }
 
-    fn read_rate_limit_headers_from(&self, route: &str, headers: &HeaderMap) {
+    async fn apply_auth(
+        &self,
+        headers: &mut HeaderMap,
+        auth: Credentials<'_>,
+    ) -> Result<(), RestError> {
+        match auth {
+            Credentials::Bearer(token) => {
+                headers.insert(AUTHORIZATION, bearer_header(token)?);
+            }
+            Credentials::Policy(AuthPolicy::Bot) => {}
+            Credentials::Policy(AuthPolicy::None) => {
+                headers.remove(AUTHORIZATION);
+            }
+            Credentials::Policy(AuthPolicy::NoBot) => {
+                if let Some(token) = self.bearer.read().await.as_deref() {
+                    headers.insert(AUTHORIZATION, bearer_header(token)?);
+                } else if !self
+                    .token
+                    .read()
+                    .await
+                    .as_deref()
+                    .is_some_and(|t| t.starts_with("Bearer "))
+                {
+                    return Err(RestError::InvalidRequest(
+                        "this endpoint requires a bearer token".to_string(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn read_rate_limit_headers_from(&self, method: &str, route: &str, headers: &HeaderMap) {
         let remaining = headers
             .get("x-ratelimit-remaining")
             .and_then(|v| v.to_str().ok())
@@ -310,12 +768,221 @@ impl Rest {
             .unwrap_or(false);
 
         self.rate_limiter
-            .update(route, remaining, reset_after, is_global);
+            .update(method, route, remaining, reset_after, is_global);
     }
 }
 
+/// Records one attempt on the enclosing `http_request` span, which is opened before the
+/// rate-limit wait and so also covers queueing and retries.
+#[cfg(feature = "tracing")]
+fn trace_request(status: u16, started: Instant) {
+    let latency_ms = started.elapsed().as_millis() as u64;
+    let span = tracing::Span::current();
+    span.record("status", status);
+    span.record("latency_ms", latency_ms);
+    tracing::debug!(status, latency_ms, "request completed");
+}
+
+#[cfg(not(feature = "tracing"))]
+#[inline(always)]
+fn trace_request(_status: u16, _started: Instant) {}
+
+fn bearer_header(token: &str) -> Result<HeaderValue, RestError> {
+    HeaderValue::from_str(&format!("Bearer {token}"))
+        .map_err(|_| RestError::InvalidRequest("invalid bearer token".to_string()))
+}
+
+/// Percent-encodes an audit log reason so non-ASCII text survives as a header value.
+fn audit_log_reason(reason: Option<&str>) -> Option<HeaderValue> {
+    let reason = reason?;
+    let mut encoded = String::with_capacity(reason.len());
+    for byte in reason.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char);
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    HeaderValue::from_str(&encoded).ok()
+}
+
 impl Default for Rest {
     fn default() -> Self {
         Self::new(RestOptions::default())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::transport::MockTransport;
+
+    fn mock_rest() -> (Rest, MockTransport) {
+        let mock = MockTransport::new();
+        let options = RestOptions {
+            api_url: "https://api.test/v1".to_string(),
+            max_retries: 2,
+            ..Default::default()
+        };
+        (Rest::with_transport(options, mock.clone()), mock)
+    }
+
+    #[tokio::test]
+    async fn sends_json_with_the_bot_token() {
+        let (rest, mock) = mock_rest();
+        rest.set_token("abc").await;
+        mock.push_json(200, &json!({ "id": "1" }));
+
+        let res: serde_json::Value = rest
+            .post("/channels/1/messages", Some(&json!({ "content": "hi" })))
+            .await
+            .unwrap();
+
+        assert_eq!(res["id"], "1");
+        let req = mock.last_request().unwrap();
+        assert_eq!(req.method, reqwest::Method::POST);
+        assert_eq!(req.url, "https://api.test/v1/channels/1/messages");
+        assert_eq!(req.headers[AUTHORIZATION], "Bot abc");
+        assert_eq!(req.json().unwrap(), json!({ "content": "hi" }));
+    }
+
+    #[tokio::test]
+    async fn parses_api_errors() {
+        let (rest, mock) = mock_rest();
+        mock.push_json(
+            400,
+            &json!({
+                "code": "INVALID_FORM_BODY",
+                "message": "Invalid form body",
+                "errors": [{ "path": "content", "message": "Too long" }],
+            }),
+        );
+
+        let err = rest.get::<serde_json::Value>("/x").await.unwrap_err();
+        let RestError::Api(err) = err else {
+            panic!("expected an API error, got {err:?}");
+        };
+        assert_eq!(err.status_code, 400);
+        assert_eq!(err.code, "INVALID_FORM_BODY");
+        assert_eq!(err.errors[0].path, "content");
+    }
+
+    #[tokio::test]
+    async fn retries_a_rate_limited_request() {
+        let (rest, mock) = mock_rest();
+        mock.push_json(
+            429,
+            &json!({ "code": "RATE_LIMITED", "message": "slow down", "retry_after": 0.01 }),
+        )
+        .push_json(200, &json!({ "ok": true }));
+
+        let res: serde_json::Value = rest.get("/x").await.unwrap();
+
+        assert_eq!(res["ok"], true);
+        assert_eq!(mock.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn surfaces_the_rate_limit_once_retries_run_out() {
+        let (rest, mock) = mock_rest();
+        for _ in 0..2 {
+            mock.push_json(
+                429,
+                &json!({ "code": "RATE_LIMITED", "message": "slow down", "retry_after": 0.01 }),
+            );
+        }
+
+        let err = rest.get::<serde_json::Value>("/x").await.unwrap_err();
+
+        assert!(matches!(err, RestError::RateLimit(_)));
+        assert!(err.is_retryable());
+        assert_eq!(err.retry_after(), Some(Duration::from_millis(10)));
+    }
+
+    fn gateway_bot_json() -> serde_json::Value {
+        json!({
+            "url": "wss://gateway.test",
+            "shards": 1,
+            "session_start_limit": {
+                "total": 1000,
+                "remaining": 999,
+                "reset_after": 0,
+                "max_concurrency": 1,
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn reuses_the_gateway_response_within_its_ttl() {
+        let (rest, mock) = mock_rest();
+        mock.push_json(200, &gateway_bot_json());
+
+        let first = rest.gateway_bot().await.unwrap();
+        let second = rest.gateway_bot().await.unwrap();
+
+        assert_eq!(first.url, "wss://gateway.test");
+        assert_eq!(second.url, first.url);
+        assert_eq!(mock.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn refetches_the_gateway_once_the_ttl_elapses() {
+        let mock = MockTransport::new();
+        let options = RestOptions {
+            gateway_cache_ttl: Duration::ZERO,
+            ..Default::default()
+        };
+        let rest = Rest::with_transport(options, mock.clone());
+        mock.push_json(200, &gateway_bot_json())
+            .push_json(200, &gateway_bot_json());
+
+        rest.gateway_bot().await.unwrap();
+        rest.gateway_bot().await.unwrap();
+
+        assert_eq!(mock.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn sends_super_properties_base64_encoded_when_configured() {
+        let props = json!({ "os": "Linux", "browser": "Fluxer", "release_channel": "stable" });
+        let mock = MockTransport::new();
+        let options = RestOptions {
+            api_url: "https://api.test/v1".to_string(),
+            super_properties: Some(props.clone()),
+            ..Default::default()
+        };
+        let rest = Rest::with_transport(options, mock.clone());
+        mock.push_json(200, &json!({}));
+
+        let _: serde_json::Value = rest.get("/users/@me").await.unwrap();
+
+        let req = mock.last_request().unwrap();
+        let header = req.headers["x-super-properties"].to_str().unwrap();
+        let decoded = base64::engine::general_purpose::STANDARD
+            .decode(header)
+            .unwrap();
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&decoded).unwrap(),
+            props
+        );
+    }
+
+    #[tokio::test]
+    async fn omits_super_properties_by_default() {
+        let (rest, mock) = mock_rest();
+        mock.push_json(200, &json!({}));
+
+        let _: serde_json::Value = rest.get("/users/@me").await.unwrap();
+
+        assert!(
+            mock.last_request()
+                .unwrap()
+                .headers
+                .get("x-super-properties")
+                .is_none()
+        );
+    }
+}
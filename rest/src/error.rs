@@ -36,6 +36,17 @@ impl fmt::Display for HttpError {
 
 impl std::error::Error for HttpError {}
 
+impl HttpError {
+    /// The `retry_after` of a 429 whose body has one but didn't parse as a full rate limit body.
+    pub fn retry_after(&self) -> Option<std::time::Duration> {
+        if self.status_code != 429 {
+            return None;
+        }
+        let body: serde_json::Value = serde_json::from_str(&self.body).ok()?;
+        std::time::Duration::try_from_secs_f64(body.get("retry_after")?.as_f64()?).ok()
+    }
+}
+
 #[derive(Debug)]
 pub struct RateLimitError {
     pub retry_after: f64,
@@ -67,4 +78,33 @@ pub enum RestError {
     Reqwest(#[from] reqwest::Error),
     #[error("json error: {0}")]
     Json(#[from] serde_json::Error),
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
+}
+
+impl RestError {
+    /// Whether the same request may succeed if sent again: rate limits (429), server errors
+    /// (5xx) and transport failures such as timeouts or dropped connections. Client errors
+    /// (other 4xx), invalid requests and JSON errors are not retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::RateLimit(_) => true,
+            Self::Api(err) => err.status_code == 429 || err.status_code >= 500,
+            Self::Http(err) => err.status_code == 429 || err.status_code >= 500,
+            Self::Reqwest(err) => err.is_timeout() || err.is_connect(),
+            Self::Json(_) | Self::InvalidRequest(_) => false,
+        }
+    }
+
+    /// How long to wait before retrying a rate-limited (429) request.
+    ///
+    /// A 429 surfaced as [`RestError::Api`] carries no delay, since its body was an API error
+    /// rather than a rate limit; back off on your own schedule in that case.
+    pub fn retry_after(&self) -> Option<std::time::Duration> {
+        match self {
+            Self::RateLimit(err) => std::time::Duration::try_from_secs_f64(err.retry_after).ok(),
+            Self::Http(err) => err.retry_after(),
+            _ => None,
+        }
+    }
 }
@@ -1,7 +1,13 @@
 pub mod client;
 pub mod error;
+pub mod oauth2;
 pub mod rate_limit;
+pub mod transport;
 
 pub use client::*;
 pub use error::*;
+pub use oauth2::*;
 pub use rate_limit::*;
+pub use transport::*;
+
+pub use reqwest::header;
@@ -0,0 +1,272 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use fluxer_types::QueryValues;
+use fluxer_types::application::ApiApplication;
+use fluxer_types::oauth2::{AuthorizeParams, OAuth2TokenResponse, scopes_space_delimited};
+
+use tokio::sync::Mutex;
+
+use crate::client::{Rest, RestOptions};
+use crate::error::{HttpError, RestError};
+use crate::transport::ReqwestTransport;
+
+const DEFAULT_API_URL: &str = "https://api.fluxer.app/v1";
+const DEFAULT_AUTHORIZE_URL: &str = "https://web.fluxer.app/oauth2/authorize";
+const DEFAULT_TIMEOUT_SECS: u64 = 15;
+const REFRESH_SKEW: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone)]
+pub struct OAuth2Options {
+    pub api_url: String,
+    pub authorize_url: String,
+    pub timeout: Duration,
+    /// See [`RestOptions::proxy`](crate::RestOptions::proxy).
+    pub proxy: Option<String>,
+    /// See [`RestOptions::danger_accept_invalid_certs`](crate::RestOptions).
+    #[cfg(feature = "danger-accept-invalid-certs")]
+    pub danger_accept_invalid_certs: bool,
+}
+
+impl Default for OAuth2Options {
+    fn default() -> Self {
+        Self {
+            api_url: DEFAULT_API_URL.to_string(),
+            authorize_url: DEFAULT_AUTHORIZE_URL.to_string(),
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
+            proxy: None,
+            #[cfg(feature = "danger-accept-invalid-certs")]
+            danger_accept_invalid_certs: false,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct OAuth2Client {
+    http: reqwest::Client,
+    rest: Rest,
+    options: OAuth2Options,
+    client_id: String,
+    client_secret: String,
+}
+
+impl OAuth2Client {
+    pub fn new(client_id: impl Into<String>, client_secret: impl Into<String>) -> Self {
+        Self::with_options(client_id, client_secret, OAuth2Options::default())
+    }
+
+    /// # Panics
+    ///
+    /// If `options.proxy` is not a valid proxy URL; use [`OAuth2Client::try_with_options`] to
+    /// handle that.
+    pub fn with_options(
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+        options: OAuth2Options,
+    ) -> Self {
+        Self::try_with_options(client_id, client_secret, options).expect("invalid OAuth2 options")
+    }
+
+    /// Builds the client with the timeout, proxy and TLS settings of `options`.
+    pub fn try_with_options(
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+        options: OAuth2Options,
+    ) -> Result<Self, RestError> {
+        let rest_options = RestOptions {
+            api_url: options.api_url.clone(),
+            timeout: options.timeout,
+            proxy: options.proxy.clone(),
+            #[cfg(feature = "danger-accept-invalid-certs")]
+            danger_accept_invalid_certs: options.danger_accept_invalid_certs,
+            ..Default::default()
+        };
+        let http = ReqwestTransport::build_client(&rest_options)?;
+        let rest = Rest::with_transport(rest_options, ReqwestTransport::from_client(http.clone()));
+        Ok(Self {
+            http,
+            rest,
+            options,
+            client_id: client_id.into(),
+            client_secret: client_secret.into(),
+        })
+    }
+
+    pub fn client_id(&self) -> &str {
+        &self.client_id
+    }
+
+    pub fn authorize_url(&self, params: &AuthorizeParams) -> Result<String, RestError> {
+        if self.client_id.is_empty() {
+            return Err(RestError::InvalidRequest(
+                "client_id must not be empty".to_string(),
+            ));
+        }
+        if params.redirect_uri.is_empty() {
+            return Err(RestError::InvalidRequest(
+                "redirect_uri must not be empty".to_string(),
+            ));
+        }
+        if params.scopes.is_empty() {
+            return Err(RestError::InvalidRequest(
+                "at least one scope is required".to_string(),
+            ));
+        }
+
+        let mut query = QueryValues::new();
+        query
+            .insert("client_id", &self.client_id)
+            .insert("response_type", "code")
+            .insert("redirect_uri", &params.redirect_uri)
+            .insert("scope", scopes_space_delimited::join(&params.scopes))
+            .insert_opt("state", params.state.as_ref())
+            .insert_opt("prompt", params.prompt.map(|p| p.as_str()))
+            .insert_opt("permissions", params.permissions)
+            .insert_opt("guild_id", params.guild_id.as_ref());
+
+        Ok(format!("{}{query}", self.options.authorize_url))
+    }
+
+    pub async fn exchange_code(
+        &self,
+        code: &str,
+        redirect_uri: &str,
+    ) -> Result<OAuth2TokenResponse, RestError> {
+        self.token(&[
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", redirect_uri),
+        ])
+        .await
+    }
+
+    pub async fn refresh_token(
+        &self,
+        refresh_token: &str,
+    ) -> Result<OAuth2TokenResponse, RestError> {
+        self.token(&[
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token),
+        ])
+        .await
+    }
+
+    /// Fetches the application the bearer token was issued for.
+    pub async fn current_application(
+        &self,
+        access_token: &str,
+    ) -> Result<ApiApplication, RestError> {
+        self.rest
+            .request_with_bearer(
+                reqwest::Method::GET,
+                fluxer_types::Routes::current_application(),
+                Option::<&()>::None,
+                access_token,
+            )
+            .await
+    }
+
+    async fn token(&self, form: &[(&str, &str)]) -> Result<OAuth2TokenResponse, RestError> {
+        let url = format!("{}/oauth2/token", self.options.api_url);
+        let res = self
+            .http
+            .post(&url)
+            .basic_auth(&self.client_id, Some(&self.client_secret))
+            .form(form)
+            .send()
+            .await?;
+        let status = res.status().as_u16();
+        let text = res.text().await.unwrap_or_default();
+        if status >= 400 {
+            return Err(HttpError {
+                status_code: status,
+                body: text,
+            }
+            .into());
+        }
+        serde_json::from_str(&text).map_err(Into::into)
+    }
+}
+
+struct TokenState {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_at: Instant,
+}
+
+impl TokenState {
+    fn from_response(response: OAuth2TokenResponse, previous_refresh: Option<String>) -> Self {
+        Self {
+            access_token: response.access_token,
+            refresh_token: response.refresh_token.or(previous_refresh),
+            expires_at: Instant::now() + Duration::from_secs(response.expires_in),
+        }
+    }
+}
+
+/// A bearer token that refreshes itself through its [`OAuth2Client`] shortly before it expires.
+#[derive(Clone)]
+pub struct RefreshingToken {
+    client: OAuth2Client,
+    state: Arc<Mutex<TokenState>>,
+}
+
+impl RefreshingToken {
+    pub fn new(client: OAuth2Client, response: OAuth2TokenResponse) -> Self {
+        Self {
+            client,
+            state: Arc::new(Mutex::new(TokenState::from_response(response, None))),
+        }
+    }
+
+    pub async fn access_token(&self) -> Result<String, RestError> {
+        let mut state = self.state.lock().await;
+        if Instant::now() + REFRESH_SKEW < state.expires_at {
+            return Ok(state.access_token.clone());
+        }
+
+        let refresh_token = state.refresh_token.clone().ok_or_else(|| {
+            RestError::InvalidRequest("token expired and no refresh_token is available".to_string())
+        })?;
+        let response = self.client.refresh_token(&refresh_token).await?;
+        *state = TokenState::from_response(response, Some(refresh_token));
+        Ok(state.access_token.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::AUTHORIZATION;
+    use serde_json::json;
+
+    use super::*;
+    use crate::transport::MockTransport;
+
+    #[tokio::test]
+    async fn current_application_uses_the_bearer_token() {
+        let mock = MockTransport::new();
+        let mut client = OAuth2Client::new("1", "secret");
+        client.rest = Rest::with_transport(RestOptions::default(), mock.clone());
+        client.rest.set_token("bot-token").await;
+        mock.push_json(
+            200,
+            &json!({
+                "id": "1",
+                "name": "App",
+                "bot_public": true,
+                "owner": { "id": "2", "username": "owner", "discriminator": "0" },
+                "flags": 8388608,
+            }),
+        );
+
+        let app = client.current_application("access").await.unwrap();
+
+        assert_eq!(app.id, "1");
+        assert_eq!(app.name, "App");
+        assert_eq!(app.owner.unwrap().id, "2");
+        assert_eq!(app.flags, Some(1 << 23));
+        let req = mock.last_request().unwrap();
+        assert!(req.url.ends_with("/applications/@me"));
+        assert_eq!(req.headers[AUTHORIZATION], "Bearer access");
+    }
+}
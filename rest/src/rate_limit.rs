@@ -2,6 +2,9 @@ use std::collections::HashMap;
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
+const MAJOR_PARAMETERS: [&str; 3] = ["channels", "guilds", "webhooks"];
+const REACTION_INTERVAL: Duration = Duration::from_millis(250);
+
 struct BucketState {
     remaining: u32,
     reset_at: Instant,
@@ -9,6 +12,7 @@ struct BucketState {
 
 pub struct RateLimitManager {
     buckets: Mutex<HashMap<String, BucketState>>,
+    reaction_slots: Mutex<HashMap<String, Instant>>,
     global_reset: Mutex<Option<Instant>>,
 }
 
@@ -16,21 +20,29 @@ impl RateLimitManager {
     pub fn new() -> Self {
         Self {
             buckets: Mutex::new(HashMap::new()),
+            reaction_slots: Mutex::new(HashMap::new()),
             global_reset: Mutex::new(None),
         }
     }
 
-    pub async fn wait_if_needed(&self, route: &str) {
+    pub async fn wait_if_needed(&self, method: &str, route: &str) {
+        let key = Self::bucket_key(method, route);
         if let Some(wait) = self.global_wait() {
             tokio::time::sleep(wait).await;
         }
-        if let Some(wait) = self.bucket_wait(route) {
+        if let Some(wait) = self.bucket_wait(&key) {
+            tokio::time::sleep(wait).await;
+        }
+        if Self::is_reaction_key(&key)
+            && let Some(wait) = self.reserve_reaction_slot(&key)
+        {
             tokio::time::sleep(wait).await;
         }
     }
 
     pub fn update(
         &self,
+        method: &str,
         route: &str,
         remaining: Option<u32>,
         reset_after_secs: Option<f64>,
@@ -47,7 +59,7 @@ impl RateLimitManager {
         }
 
         if let (Some(rem), Some(secs)) = (remaining, reset_after_secs) {
-            let key = Self::bucket_key(route);
+            let key = Self::bucket_key(method, route);
             let mut buckets = self.buckets.lock().expect("lock not poisoned");
             buckets.insert(
                 key,
@@ -71,10 +83,9 @@ impl RateLimitManager {
             .and_then(|reset| reset.checked_duration_since(Instant::now()))
     }
 
-    fn bucket_wait(&self, route: &str) -> Option<Duration> {
-        let key = Self::bucket_key(route);
+    fn bucket_wait(&self, key: &str) -> Option<Duration> {
         let buckets = self.buckets.lock().expect("lock not poisoned");
-        buckets.get(&key).and_then(|state| {
+        buckets.get(key).and_then(|state| {
             if state.remaining == 0 {
                 state.reset_at.checked_duration_since(Instant::now())
             } else {
@@ -83,26 +94,165 @@ impl RateLimitManager {
         })
     }
 
-    fn bucket_key(route: &str) -> String {
-        let mut key = String::with_capacity(route.len());
-        let mut prev_was_slash = false;
-        for part in route.split('/') {
-            if !key.is_empty() || prev_was_slash {
+    fn reserve_reaction_slot(&self, key: &str) -> Option<Duration> {
+        let now = Instant::now();
+        let mut slots = self.reaction_slots.lock().expect("lock not poisoned");
+        // Drop buckets whose spacing has elapsed so one-off channels don't accumulate.
+        slots.retain(|_, last| *last + REACTION_INTERVAL > now);
+        let slot = slots
+            .get(key)
+            .map(|last| (*last + REACTION_INTERVAL).max(now))
+            .unwrap_or(now);
+        slots.insert(key.to_string(), slot);
+        slot.checked_duration_since(now)
+            .filter(|wait| !wait.is_zero())
+    }
+
+    fn is_reaction_key(key: &str) -> bool {
+        key.starts_with("REACTION ")
+    }
+
+    /// Builds the bucket key from the method, the route template and its major parameter.
+    ///
+    /// Reaction add/remove routes share one bucket per message channel regardless of emoji.
+    fn bucket_key(method: &str, route: &str) -> String {
+        let path = route.split('?').next().unwrap_or(route);
+        let parts: Vec<&str> = path.split('/').collect();
+        let reaction = matches!(method, "PUT" | "DELETE") && parts.contains(&"reactions");
+
+        let mut key = String::with_capacity(path.len() + method.len() + 1);
+        key.push_str(if reaction { "REACTION" } else { method });
+        key.push(' ');
+        for (i, part) in parts.iter().enumerate() {
+            if i > 0 {
                 key.push('/');
             }
-            prev_was_slash = true;
-            if part.chars().all(|c| c.is_ascii_digit()) && !part.is_empty() {
+            let is_major = i == 2 && MAJOR_PARAMETERS.contains(&parts[1]);
+            if !is_major && !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()) {
                 key.push_str(":id");
             } else {
                 key.push_str(part);
             }
+            if reaction && *part == "reactions" {
+                break;
+            }
         }
         key
     }
 }
 
+/// Reduces a route to its template so ids and tokens do not end up in log fields.
+#[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+pub(crate) fn route_template(route: &str) -> String {
+    let path = route.split('?').next().unwrap_or(route);
+    let path = path
+        .find("://")
+        .and_then(|i| path[i + 3..].find('/').map(|j| &path[i + 3 + j..]))
+        .unwrap_or(path);
+    let parts: Vec<&str> = path.split('/').collect();
+    let mut template = String::with_capacity(path.len());
+    for (i, part) in parts.iter().enumerate() {
+        if i > 0 {
+            template.push('/');
+        }
+        let is_token = i >= 2 && matches!(parts[i - 2], "webhooks" | "interactions");
+        if !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()) {
+            template.push_str(":id");
+        } else if is_token {
+            template.push_str(":token");
+        } else {
+            template.push_str(part);
+        }
+    }
+    template
+}
+
 impl Default for RateLimitManager {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(method: &str, route: &str) -> String {
+        RateLimitManager::bucket_key(method, route)
+    }
+
+    #[test]
+    fn reactions_on_one_message_share_a_bucket() {
+        assert_eq!(
+            key("PUT", "/channels/1/messages/2/reactions/%F0%9F%91%8D/@me"),
+            key("DELETE", "/channels/1/messages/2/reactions/custom:3/@me"),
+        );
+    }
+
+    #[test]
+    fn reactions_in_different_channels_do_not_share_a_bucket() {
+        assert_ne!(
+            key("PUT", "/channels/1/messages/2/reactions/x/@me"),
+            key("PUT", "/channels/9/messages/2/reactions/x/@me"),
+        );
+    }
+
+    #[test]
+    fn major_parameters_are_kept_and_minor_ids_collapsed() {
+        assert_eq!(
+            key("GET", "/channels/1/messages/2"),
+            "GET /channels/1/messages/:id"
+        );
+        assert_eq!(
+            key("GET", "/users/5/profile?with_mutual_guilds=true"),
+            "GET /users/:id/profile"
+        );
+        assert!(!RateLimitManager::is_reaction_key(&key(
+            "GET",
+            "/channels/1/messages/2/reactions/x"
+        )));
+    }
+
+    #[tokio::test]
+    async fn spaces_out_reactions_in_one_bucket() {
+        let limiter = RateLimitManager::new();
+        let route = "/channels/1/messages/2/reactions/x/@me";
+        let started = Instant::now();
+
+        limiter.wait_if_needed("PUT", route).await;
+        limiter.wait_if_needed("PUT", route).await;
+
+        assert!(started.elapsed() >= REACTION_INTERVAL);
+    }
+
+    #[test]
+    fn route_template_hides_ids_and_tokens() {
+        assert_eq!(
+            route_template("/webhooks/1/secret/messages/2?wait=true"),
+            "/webhooks/:id/:token/messages/:id"
+        );
+        assert_eq!(
+            route_template("https://cdn.test/avatars/1/abc.png"),
+            "/avatars/:id/abc.png"
+        );
+    }
+
+    #[test]
+    fn forgets_reaction_buckets_once_their_spacing_elapses() {
+        let limiter = RateLimitManager::new();
+        limiter.reserve_reaction_slot("REACTION /channels/1");
+        limiter.reserve_reaction_slot("REACTION /channels/2");
+        assert_eq!(limiter.reaction_slots.lock().unwrap().len(), 2);
+
+        std::thread::sleep(REACTION_INTERVAL + Duration::from_millis(20));
+        assert!(
+            limiter
+                .reserve_reaction_slot("REACTION /channels/3")
+                .is_none()
+        );
+
+        let slots = limiter.reaction_slots.lock().unwrap();
+        assert_eq!(slots.len(), 1);
+        assert!(slots.contains_key("REACTION /channels/3"));
+    }
+}
@@ -0,0 +1,203 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use reqwest::Method;
+use reqwest::header::HeaderMap;
+
+use crate::error::RestError;
+
+pub enum TransportBody {
+    Empty,
+    Json(Vec<u8>),
+    Multipart(reqwest::multipart::Form),
+}
+
+pub struct TransportRequest {
+    pub method: Method,
+    pub url: String,
+    pub headers: HeaderMap,
+    pub body: TransportBody,
+}
+
+#[derive(Debug, Clone)]
+pub struct TransportResponse {
+    pub status: u16,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+pub type TransportFuture<'a> =
+    Pin<Box<dyn Future<Output = Result<TransportResponse, RestError>> + Send + 'a>>;
+
+/// Sends a fully built request and returns the raw response. [`Rest`](crate::Rest) handles
+/// rate limits, retries and error parsing on top of it.
+pub trait Transport: Send + Sync + 'static {
+    fn send(&self, request: TransportRequest) -> TransportFuture<'_>;
+}
+
+pub struct ReqwestTransport {
+    http: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(timeout: Duration) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .expect("TLS backend available");
+        Self { http }
+    }
+
+    /// Applies the timeout, proxy and TLS settings of `options`.
+    pub fn from_options(options: &crate::RestOptions) -> Result<Self, RestError> {
+        Ok(Self {
+            http: Self::build_client(options)?,
+        })
+    }
+
+    pub(crate) fn build_client(options: &crate::RestOptions) -> Result<reqwest::Client, RestError> {
+        let mut builder = reqwest::Client::builder().timeout(options.timeout);
+        if let Some(proxy) = &options.proxy {
+            let proxy = reqwest::Proxy::all(proxy)
+                .map_err(|e| RestError::InvalidRequest(format!("invalid proxy URL: {e}")))?;
+            builder = builder.proxy(proxy);
+        }
+        #[cfg(feature = "danger-accept-invalid-certs")]
+        {
+            builder = builder.danger_accept_invalid_certs(options.danger_accept_invalid_certs);
+        }
+        Ok(builder.build()?)
+    }
+
+    pub fn from_client(http: reqwest::Client) -> Self {
+        Self { http }
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: TransportRequest) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let mut req = self
+                .http
+                .request(request.method, &request.url)
+                .headers(request.headers);
+            req = match request.body {
+                TransportBody::Empty => req,
+                TransportBody::Json(bytes) => req.body(bytes),
+                TransportBody::Multipart(form) => req.multipart(form),
+            };
+            let res = req.send().await?;
+            let status = res.status().as_u16();
+            let headers = res.headers().clone();
+            let body = res.bytes().await?.to_vec();
+            Ok(TransportResponse {
+                status,
+                headers,
+                body,
+            })
+        })
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+pub use mock::{MockTransport, RecordedRequest};
+
+#[cfg(any(test, feature = "test-util"))]
+mod mock {
+    use std::collections::VecDeque;
+    use std::sync::{Arc, Mutex};
+
+    use reqwest::Method;
+    use reqwest::header::HeaderMap;
+    use serde::Serialize;
+
+    use super::{Transport, TransportBody, TransportFuture, TransportRequest, TransportResponse};
+    use crate::error::RestError;
+
+    /// An outgoing request as seen by [`MockTransport`]. Multipart bodies are opaque and
+    /// recorded as `None`.
+    #[derive(Debug, Clone)]
+    pub struct RecordedRequest {
+        pub method: Method,
+        pub url: String,
+        pub headers: HeaderMap,
+        pub body: Option<Vec<u8>>,
+    }
+
+    impl RecordedRequest {
+        pub fn json(&self) -> Option<serde_json::Value> {
+            self.body
+                .as_deref()
+                .and_then(|b| serde_json::from_slice(b).ok())
+        }
+    }
+
+    #[derive(Default)]
+    struct MockState {
+        responses: VecDeque<TransportResponse>,
+        requests: Vec<RecordedRequest>,
+    }
+
+    /// Replays queued responses in order and records every request it receives.
+    ///
+    /// Clones share state, so keep one handle for assertions and pass another to
+    /// [`Rest::with_transport`](crate::Rest::with_transport).
+    #[derive(Clone, Default)]
+    pub struct MockTransport {
+        state: Arc<Mutex<MockState>>,
+    }
+
+    impl MockTransport {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn push_response(&self, status: u16, body: impl Into<Vec<u8>>) -> &Self {
+            self.state
+                .lock()
+                .unwrap()
+                .responses
+                .push_back(TransportResponse {
+                    status,
+                    headers: HeaderMap::new(),
+                    body: body.into(),
+                });
+            self
+        }
+
+        pub fn push_json(&self, status: u16, body: &impl Serialize) -> &Self {
+            let body = serde_json::to_vec(body).expect("serializable mock body");
+            self.push_response(status, body)
+        }
+
+        pub fn requests(&self) -> Vec<RecordedRequest> {
+            self.state.lock().unwrap().requests.clone()
+        }
+
+        pub fn last_request(&self) -> Option<RecordedRequest> {
+            self.state.lock().unwrap().requests.last().cloned()
+        }
+    }
+
+    impl Transport for MockTransport {
+        fn send(&self, request: TransportRequest) -> TransportFuture<'_> {
+            let mut state = self.state.lock().unwrap();
+            let body = match request.body {
+                TransportBody::Empty => Some(Vec::new()),
+                TransportBody::Json(bytes) => Some(bytes),
+                TransportBody::Multipart(_) => None,
+            };
+            state.requests.push(RecordedRequest {
+                method: request.method,
+                url: request.url.clone(),
+                headers: request.headers,
+                body,
+            });
+            let response = state.responses.pop_front().ok_or_else(|| {
+                RestError::InvalidRequest(format!("no mock response queued for {}", request.url))
+            });
+            Box::pin(async move { response })
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+use crate::Snowflake;
+use crate::user::ApiUser;
+
+/// The application behind the current credentials, as returned by `/applications/@me` for both
+/// bot tokens and OAuth2 bearer tokens.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiApplication {
+    pub id: Snowflake,
+    pub name: String,
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub bot_public: Option<bool>,
+    #[serde(default)]
+    pub bot_require_code_grant: Option<bool>,
+    #[serde(default)]
+    pub owner: Option<ApiUser>,
+    #[serde(default)]
+    pub bot: Option<ApiUser>,
+    #[serde(default)]
+    pub flags: Option<u64>,
+    #[serde(default)]
+    pub redirect_uris: Option<Vec<String>>,
+}
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+use crate::Snowflake;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LoginRequest {
+    /// Email address or phone number.
+    pub login: String,
+    pub password: String,
+    /// Restores an account that was scheduled for deletion.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub undelete: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub captcha_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RegisterRequest {
+    pub email: String,
+    pub username: String,
+    pub password: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub global_name: Option<String>,
+    /// `YYYY-MM-DD`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_of_birth: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consent: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invite: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub captcha_key: Option<String>,
+}
+
+/// Completes a login that answered with `mfa: true`, using the `ticket` from that response.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MfaRequest {
+    pub code: String,
+    pub ticket: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VerifyEmailRequest {
+    pub token: String,
+}
+
+/// Result of a login, registration or MFA step. `token` is absent while MFA is still pending.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuthResponse {
+    #[serde(default)]
+    pub token: Option<String>,
+    #[serde(default)]
+    pub user_id: Option<Snowflake>,
+    #[serde(default)]
+    pub mfa: bool,
+    #[serde(default)]
+    pub ticket: Option<String>,
+}
+
+impl AuthResponse {
+    pub fn requires_mfa(&self) -> bool {
+        self.mfa && self.token.is_none()
+    }
+}
@@ -1,11 +1,41 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::user::ApiUser;
+use crate::{QueryValues, Snowflake};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiBan {
     pub user: ApiUser,
     pub reason: Option<String>,
-    #[serde(default)]
-    pub expires_at: Option<String>,
+    #[serde(default, with = "crate::ser::iso8601::opt")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateBanRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete_message_seconds: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Ban list page; bans are ordered by user id, so `after` continues from the last one seen.
+#[derive(Debug, Clone, Default)]
+pub struct BanListQuery {
+    /// 1-1000.
+    pub limit: Option<u32>,
+    pub before: Option<Snowflake>,
+    pub after: Option<Snowflake>,
+}
+
+impl BanListQuery {
+    pub fn to_query_string(&self) -> String {
+        let mut query = QueryValues::new();
+        query
+            .insert_opt("limit", self.limit)
+            .insert_opt("before", self.before.as_ref())
+            .insert_opt("after", self.after.as_ref());
+        query.to_query_string()
+    }
 }
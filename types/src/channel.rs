@@ -1,8 +1,12 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
-use crate::Snowflake;
-use crate::user::ApiUser;
+use crate::embed::ApiEmbed;
+use crate::enums::code_enum;
+use crate::message::ApiMessage;
+use crate::user::{ApiGuildMember, ApiUser};
+use crate::{Patch, Snowflake};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize_repr, Deserialize_repr)]
 #[repr(u16)]
@@ -13,9 +17,29 @@ pub enum ChannelType {
     GroupDm = 3,
     GuildCategory = 4,
     GuildLink = 5,
+    AnnouncementThread = 10,
+    PublicThread = 11,
+    PrivateThread = 12,
+    GuildStageVoice = 13,
+    GuildForum = 15,
     GuildLinkExtended = 998,
 }
 
+code_enum!(ChannelType: u16 {
+    GuildText => "guild_text",
+    Dm => "dm",
+    GuildVoice => "guild_voice",
+    GroupDm => "group_dm",
+    GuildCategory => "guild_category",
+    GuildLink => "guild_link",
+    AnnouncementThread => "announcement_thread",
+    PublicThread => "public_thread",
+    PrivateThread => "private_thread",
+    GuildStageVoice => "guild_stage_voice",
+    GuildForum => "guild_forum",
+    GuildLinkExtended => "guild_link_extended",
+});
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize_repr, Deserialize_repr)]
 #[repr(u8)]
 pub enum OverwriteType {
@@ -23,6 +47,36 @@ pub enum OverwriteType {
     Member = 1,
 }
 
+code_enum!(OverwriteType: u8 {
+    Role => "role",
+    Member => "member",
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize_repr, Deserialize_repr)]
+#[repr(i32)]
+pub enum AutoArchiveDuration {
+    OneHour = 60,
+    OneDay = 1440,
+    ThreeDays = 4320,
+    OneWeek = 10080,
+}
+
+impl AutoArchiveDuration {
+    pub fn minutes(self) -> i32 {
+        self as i32
+    }
+
+    pub fn from_minutes(minutes: i32) -> Option<Self> {
+        match minutes {
+            60 => Some(Self::OneHour),
+            1440 => Some(Self::OneDay),
+            4320 => Some(Self::ThreeDays),
+            10080 => Some(Self::OneWeek),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiChannelOverwrite {
     pub id: Snowflake,
@@ -32,6 +86,16 @@ pub struct ApiChannelOverwrite {
     pub deny: String,
 }
 
+/// Body for `PUT /channels/{id}/permissions/{overwrite_id}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditPermissionsRequest {
+    /// Decimal permission bitsets, as in [`ApiChannelOverwrite`].
+    pub allow: String,
+    pub deny: String,
+    #[serde(rename = "type")]
+    pub kind: OverwriteType,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiChannelPartial {
     pub id: Snowflake,
@@ -74,8 +138,8 @@ pub struct ApiChannel {
     pub rtc_region: Option<String>,
     #[serde(default)]
     pub last_message_id: Option<Snowflake>,
-    #[serde(default)]
-    pub last_pin_timestamp: Option<String>,
+    #[serde(default, with = "crate::ser::iso8601::opt")]
+    pub last_pin_timestamp: Option<DateTime<Utc>>,
     #[serde(default)]
     pub permission_overwrites: Option<Vec<ApiChannelOverwrite>>,
     #[serde(default)]
@@ -84,4 +148,216 @@ pub struct ApiChannel {
     pub nsfw: Option<bool>,
     #[serde(default)]
     pub rate_limit_per_user: Option<u32>,
+    #[serde(default)]
+    pub thread_metadata: Option<ApiThreadMetadata>,
+    #[serde(default)]
+    pub message_count: Option<u32>,
+    #[serde(default)]
+    pub member_count: Option<u32>,
+    #[serde(default)]
+    pub available_tags: Option<Vec<ApiForumTag>>,
+    #[serde(default)]
+    pub default_reaction_emoji: Option<ApiDefaultReaction>,
+}
+
+/// A tag that posts in a forum channel can be labelled with.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiForumTag {
+    pub id: Snowflake,
+    pub name: String,
+    #[serde(default)]
+    pub moderated: bool,
+    #[serde(default)]
+    pub emoji_id: Option<Snowflake>,
+    #[serde(default)]
+    pub emoji_name: Option<String>,
+}
+
+/// The emoji shown as the default reaction on forum posts; either a custom emoji id or a
+/// unicode emoji name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiDefaultReaction {
+    #[serde(default)]
+    pub emoji_id: Option<Snowflake>,
+    #[serde(default)]
+    pub emoji_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiThreadMetadata {
+    pub archived: bool,
+    pub auto_archive_duration: u32,
+    #[serde(with = "crate::ser::iso8601")]
+    pub archive_timestamp: DateTime<Utc>,
+    #[serde(default)]
+    pub locked: bool,
+    #[serde(default)]
+    pub invitable: Option<bool>,
+    #[serde(default, with = "crate::ser::iso8601::opt")]
+    pub create_timestamp: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiThreadMember {
+    #[serde(default)]
+    pub id: Option<Snowflake>,
+    #[serde(default)]
+    pub user_id: Option<Snowflake>,
+    #[serde(with = "crate::ser::iso8601")]
+    pub join_timestamp: DateTime<Utc>,
+    #[serde(default)]
+    pub flags: u32,
+    #[serde(default)]
+    pub member: Option<ApiGuildMember>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiThreadList {
+    pub threads: Vec<ApiChannel>,
+    #[serde(default)]
+    pub members: Vec<ApiThreadMember>,
+    #[serde(default)]
+    pub has_more: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StartThreadRequest {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_archive_duration: Option<AutoArchiveDuration>,
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub thread_type: Option<ChannelType>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invitable: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit_per_user: Option<u32>,
+}
+
+/// Body for creating a post in a forum channel (`POST /channels/{id}/threads`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StartForumThreadRequest {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_archive_duration: Option<AutoArchiveDuration>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit_per_user: Option<u32>,
+    pub message: ForumThreadMessage,
+    /// Ids of the forum's [`ApiForumTag`]s to label the post with.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub applied_tags: Vec<Snowflake>,
+}
+
+/// The first message of a forum post.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ForumThreadMessage {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embeds: Option<Vec<ApiEmbed>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<serde_json::Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flags: Option<u32>,
+}
+
+/// A created forum post: the thread channel and its first message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiForumThread {
+    #[serde(flatten)]
+    pub channel: ApiChannel,
+    /// `None` when absent or when it doesn't decode; the thread itself was still created.
+    #[serde(default, with = "crate::ser::lenient")]
+    pub message: Option<ApiMessage>,
+}
+
+/// Body for `PATCH /channels/{id}`. Absent fields are left untouched; `Patch::Null` clears them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModifyChannelRequest {
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub name: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub topic: Patch<String>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub nsfw: Patch<bool>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub rate_limit_per_user: Patch<u32>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub bitrate: Patch<u32>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub user_limit: Patch<u32>,
+    /// `Patch::Null` moves the channel out of its category.
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub parent_id: Patch<Snowflake>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub permission_overwrites: Patch<Vec<ApiChannelOverwrite>>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub position: Patch<i32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const DURATIONS: [(AutoArchiveDuration, i32); 4] = [
+        (AutoArchiveDuration::OneHour, 60),
+        (AutoArchiveDuration::OneDay, 1440),
+        (AutoArchiveDuration::ThreeDays, 4320),
+        (AutoArchiveDuration::OneWeek, 10080),
+    ];
+
+    #[test]
+    fn auto_archive_durations_serialize_as_minutes() {
+        for (duration, minutes) in DURATIONS {
+            assert_eq!(serde_json::to_value(duration).unwrap(), json!(minutes));
+            assert_eq!(duration.minutes(), minutes);
+            assert_eq!(AutoArchiveDuration::from_minutes(minutes), Some(duration));
+        }
+    }
+
+    #[test]
+    fn auto_archive_durations_reject_other_values() {
+        for minutes in [0, 30, 61, 20160] {
+            assert_eq!(AutoArchiveDuration::from_minutes(minutes), None);
+            assert!(serde_json::from_value::<AutoArchiveDuration>(json!(minutes)).is_err());
+        }
+    }
+
+    #[test]
+    fn thread_requests_send_the_duration_in_minutes() {
+        let body = StartThreadRequest {
+            name: "t".to_string(),
+            auto_archive_duration: Some(AutoArchiveDuration::OneDay),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&body).unwrap(),
+            json!({ "name": "t", "auto_archive_duration": 1440 })
+        );
+    }
+
+    #[test]
+    fn decodes_a_text_channel_with_last_message_and_pin() {
+        let channel: ApiChannel = serde_json::from_value(json!({
+            "id": "1",
+            "type": 0,
+            "guild_id": "2",
+            "name": "general",
+            "last_message_id": "99",
+            "last_pin_timestamp": "2024-01-01T00:00:00.000Z",
+        }))
+        .unwrap();
+        assert_eq!(channel.last_message_id.as_deref(), Some("99"));
+        assert_eq!(
+            channel.last_pin_timestamp.unwrap().to_rfc3339(),
+            "2024-01-01T00:00:00+00:00"
+        );
+
+        let empty: ApiChannel =
+            serde_json::from_value(json!({ "id": "1", "type": 0, "last_pin_timestamp": null }))
+                .unwrap();
+        assert!(empty.last_message_id.is_none());
+        assert!(empty.last_pin_timestamp.is_none());
+    }
 }
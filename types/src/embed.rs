@@ -8,7 +8,7 @@ pub struct ApiEmbedAuthor {
     pub url: Option<String>,
     #[serde(default)]
     pub icon_url: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing)]
     pub proxy_icon_url: Option<String>,
 }
 
@@ -17,14 +17,14 @@ pub struct ApiEmbedFooter {
     pub text: String,
     #[serde(default)]
     pub icon_url: Option<String>,
-    #[serde(default)]
+    #[serde(default, skip_serializing)]
     pub proxy_icon_url: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiEmbedMedia {
     pub url: String,
-    #[serde(default)]
+    #[serde(default, skip_serializing)]
     pub proxy_url: Option<String>,
     #[serde(default)]
     pub content_type: Option<String>,
@@ -52,9 +52,11 @@ pub struct ApiEmbedField {
     pub inline: Option<bool>,
 }
 
+/// Server-populated fields (`type`, `provider` and the `proxy_*` URLs) are read-only and never
+/// serialized, so an embed taken from a received message can be sent back in an edit.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiEmbed {
-    #[serde(default, rename = "type")]
+    #[serde(default, rename = "type", skip_serializing)]
     pub kind: Option<String>,
     #[serde(default)]
     pub url: Option<String>,
@@ -76,7 +78,7 @@ pub struct ApiEmbed {
     pub footer: Option<ApiEmbedFooter>,
     #[serde(default)]
     pub fields: Option<Vec<ApiEmbedField>>,
-    #[serde(default)]
+    #[serde(default, skip_serializing)]
     pub provider: Option<ApiEmbedAuthor>,
     #[serde(default)]
     pub video: Option<ApiEmbedMedia>,
@@ -7,6 +7,7 @@ use crate::user::ApiUser;
 pub struct ApiEmoji {
     pub id: Snowflake,
     pub name: String,
+    #[serde(default)]
     pub animated: bool,
 }
 
@@ -14,7 +15,98 @@ pub struct ApiEmoji {
 pub struct ApiEmojiWithUser {
     pub id: Snowflake,
     pub name: String,
+    #[serde(default)]
     pub animated: bool,
     #[serde(default)]
     pub user: Option<ApiUser>,
 }
+
+/// An emoji as it appears in a reaction route.
+///
+/// Unicode emoji are kept as the full grapheme (skin-tone modifiers, ZWJ joiners and variation
+/// selectors included) and percent-encoded as one UTF-8 sequence.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ReactionEmoji {
+    Unicode(String),
+    Custom { name: String, id: Snowflake },
+}
+
+impl ReactionEmoji {
+    /// Accepts a unicode emoji, `name:id`, `:name:id` or a `<:name:id>` / `<a:name:id>` mention.
+    pub fn parse(input: &str) -> Self {
+        let trimmed = input.trim();
+        let inner = trimmed
+            .strip_prefix('<')
+            .and_then(|s| s.strip_suffix('>'))
+            .map(|s| s.strip_prefix("a:").unwrap_or(s))
+            .unwrap_or(trimmed);
+        let inner = inner.strip_prefix(':').unwrap_or(inner);
+        if let Some((name, id)) = inner.rsplit_once(':')
+            && !id.is_empty()
+            && id.chars().all(|c| c.is_ascii_digit())
+        {
+            return Self::Custom {
+                name: name.to_string(),
+                id: id.to_string(),
+            };
+        }
+        Self::Unicode(trimmed.to_string())
+    }
+
+    pub fn to_route_segment(&self) -> String {
+        match self {
+            Self::Unicode(emoji) => crate::routes::urlencoding_encode(emoji),
+            Self::Custom { name, id } => {
+                format!("{}:{id}", crate::routes::urlencoding_encode(name))
+            }
+        }
+    }
+}
+
+impl From<&str> for ReactionEmoji {
+    fn from(input: &str) -> Self {
+        Self::parse(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Routes;
+
+    #[test]
+    fn encodes_skin_tone_and_zwj_sequences_whole() {
+        assert_eq!(
+            ReactionEmoji::parse("👍🏽").to_route_segment(),
+            "%F0%9F%91%8D%F0%9F%8F%BD"
+        );
+        assert_eq!(
+            ReactionEmoji::parse("👨‍👩‍👧").to_route_segment(),
+            "%F0%9F%91%A8%E2%80%8D%F0%9F%91%A9%E2%80%8D%F0%9F%91%A7"
+        );
+        assert_eq!(
+            ReactionEmoji::parse("❤️").to_route_segment(),
+            "%E2%9D%A4%EF%B8%8F"
+        );
+    }
+
+    #[test]
+    fn parses_custom_emoji_forms() {
+        let expected = ReactionEmoji::Custom {
+            name: "blob".to_string(),
+            id: "123".to_string(),
+        };
+        for input in ["blob:123", ":blob:123", "<:blob:123>", "<a:blob:123>"] {
+            assert_eq!(ReactionEmoji::parse(input), expected);
+        }
+        assert_eq!(expected.to_route_segment(), "blob:123");
+    }
+
+    #[test]
+    fn reaction_route_uses_the_encoded_emoji() {
+        assert_eq!(
+            Routes::channel_message_reaction("1", "2", "👍🏽"),
+            "/channels/1/messages/2/reactions/%F0%9F%91%8D%F0%9F%8F%BD"
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use std::fmt;
+
+/// Returned by the strict `TryFrom` conversions when a code has no known variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownEnumValue {
+    pub kind: &'static str,
+    pub code: i64,
+}
+
+impl fmt::Display for UnknownEnumValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown {} value {}", self.kind, self.code)
+    }
+}
+
+impl std::error::Error for UnknownEnumValue {}
+
+/// Adds `code`, `from_code`, `as_str`, `all` and a strict `TryFrom<repr>` to a fieldless
+/// `#[repr]` enum.
+macro_rules! code_enum {
+    ($name:ident: $repr:ty { $($variant:ident => $label:literal),+ $(,)? }) => {
+        impl $name {
+            pub const ALL: &'static [Self] = &[$(Self::$variant),+];
+
+            pub fn all() -> impl Iterator<Item = Self> {
+                Self::ALL.iter().copied()
+            }
+
+            pub fn code(self) -> $repr {
+                self as $repr
+            }
+
+            pub fn from_code(code: $repr) -> Option<Self> {
+                Self::all().find(|v| v.code() == code)
+            }
+
+            pub fn as_str(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $label),+
+                }
+            }
+        }
+
+        impl TryFrom<$repr> for $name {
+            type Error = $crate::enums::UnknownEnumValue;
+
+            fn try_from(code: $repr) -> Result<Self, Self::Error> {
+                Self::from_code(code).ok_or($crate::enums::UnknownEnumValue {
+                    kind: stringify!($name),
+                    code: code as i64,
+                })
+            }
+        }
+    };
+}
+
+pub(crate) use code_enum;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channel::{ChannelType, OverwriteType};
+    use crate::guild::{
+        AuditLogActionType, DefaultMessageNotifications, GuildExplicitContentFilter, GuildMfaLevel,
+        GuildVerificationLevel,
+    };
+    use crate::interaction::InteractionCallbackType;
+    use crate::message::{MessageType, ReactionKind};
+    use crate::scheduled_event::{ScheduledEventEntityType, ScheduledEventStatus};
+    use crate::user::PremiumType;
+
+    macro_rules! assert_round_trips {
+        ($($name:ident),+ $(,)?) => {$(
+            assert!(!$name::ALL.is_empty());
+            for variant in $name::all() {
+                assert_eq!($name::from_code(variant.code()), Some(variant));
+                assert_eq!($name::try_from(variant.code()), Ok(variant));
+                assert!(!variant.as_str().is_empty());
+            }
+        )+};
+    }
+
+    #[test]
+    fn every_variant_round_trips_through_its_code() {
+        assert_round_trips!(
+            GuildVerificationLevel,
+            GuildMfaLevel,
+            GuildExplicitContentFilter,
+            DefaultMessageNotifications,
+            AuditLogActionType,
+            ChannelType,
+            OverwriteType,
+            MessageType,
+            ReactionKind,
+            ScheduledEventEntityType,
+            ScheduledEventStatus,
+            PremiumType,
+            InteractionCallbackType,
+        );
+    }
+
+    #[test]
+    fn strict_conversion_rejects_unknown_codes() {
+        assert_eq!(ChannelType::from_code(9999), None);
+        assert_eq!(
+            ChannelType::try_from(9999),
+            Err(UnknownEnumValue {
+                kind: "ChannelType",
+                code: 9999,
+            })
+        );
+        assert_eq!(
+            PremiumType::try_from(42).unwrap_err().to_string(),
+            "unknown PremiumType value 42"
+        );
+    }
+}
@@ -1,8 +1,9 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
 use crate::Snowflake;
-use crate::channel::ApiChannel;
+use crate::channel::{ApiChannel, ApiThreadMember};
 use crate::emoji::ApiEmoji;
 use crate::guild::ApiGuild;
 use crate::role::ApiRole;
@@ -32,6 +33,43 @@ pub struct GatewayIdentifyProperties {
     pub device: String,
 }
 
+/// A validated `[shard_id, num_shards]` pair, serialized as a two-element array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "(u32, u32)", into = "(u32, u32)")]
+pub struct ShardIdentity {
+    id: u32,
+    total: u32,
+}
+
+impl ShardIdentity {
+    /// Returns `None` unless `id < total`.
+    pub fn new(id: u32, total: u32) -> Option<Self> {
+        (id < total).then_some(Self { id, total })
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn total(&self) -> u32 {
+        self.total
+    }
+}
+
+impl TryFrom<(u32, u32)> for ShardIdentity {
+    type Error = String;
+
+    fn try_from((id, total): (u32, u32)) -> Result<Self, Self::Error> {
+        Self::new(id, total).ok_or_else(|| format!("shard id {id} must be less than {total}"))
+    }
+}
+
+impl From<ShardIdentity> for (u32, u32) {
+    fn from(identity: ShardIdentity) -> Self {
+        (identity.id, identity.total)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GatewayIdentifyData {
     pub token: String,
@@ -42,7 +80,7 @@ pub struct GatewayIdentifyData {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub large_threshold: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub shard: Option<(u32, u32)>,
+    pub shard: Option<ShardIdentity>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub presence: Option<GatewayPresenceUpdateSendData>,
 }
@@ -71,8 +109,18 @@ pub struct GatewayActivity {
     pub kind: u8,
     #[serde(default)]
     pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+    /// Unix milliseconds; only present on received presences.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<u64>,
 }
 
+/// Activity `type` of a streaming activity, whose `url` is the stream.
+pub const ACTIVITY_STREAMING: u8 = 1;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GatewayPresenceUpdateSendData {
     #[serde(default)]
@@ -108,6 +156,10 @@ pub struct GatewayRequestGuildMembersData {
     #[serde(default)]
     pub query: Option<String>,
     pub limit: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub user_ids: Vec<Snowflake>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -128,7 +180,7 @@ pub struct GatewayReadyData {
     pub guilds: Vec<GatewayReadyGuild>,
     pub session_id: String,
     #[serde(default)]
-    pub shard: Option<(u32, u32)>,
+    pub shard: Option<ShardIdentity>,
     pub application: GatewayApplication,
 }
 
@@ -233,12 +285,12 @@ pub struct GatewayGuildMemberUpdateData {
     pub nick: Option<String>,
     #[serde(default)]
     pub avatar: Option<String>,
-    #[serde(default)]
-    pub joined_at: Option<String>,
-    #[serde(default)]
-    pub premium_since: Option<String>,
-    #[serde(default)]
-    pub communication_disabled_until: Option<String>,
+    #[serde(default, with = "crate::ser::iso8601::opt")]
+    pub joined_at: Option<DateTime<Utc>>,
+    #[serde(default, with = "crate::ser::iso8601::opt")]
+    pub premium_since: Option<DateTime<Utc>>,
+    #[serde(default, with = "crate::ser::iso8601::opt")]
+    pub communication_disabled_until: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -257,6 +309,16 @@ pub struct GatewayGuildMembersChunkData {
     pub nonce: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GatewayMessagePollVoteData {
+    pub user_id: Snowflake,
+    pub channel_id: Snowflake,
+    pub message_id: Snowflake,
+    #[serde(default)]
+    pub guild_id: Option<Snowflake>,
+    pub answer_id: u32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GatewayGuildBanAddData {
     pub guild_id: Snowflake,
@@ -283,9 +345,13 @@ pub struct GatewayInviteDeleteData {
 pub struct GatewayTypingStartData {
     pub channel_id: Snowflake,
     pub user_id: Snowflake,
-    pub timestamp: u64,
+    #[serde(with = "crate::ser::unix_seconds")]
+    pub timestamp: DateTime<Utc>,
     #[serde(default)]
     pub guild_id: Option<Snowflake>,
+    /// Present for typing in guild channels.
+    #[serde(default)]
+    pub member: Option<ApiGuildMember>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -333,6 +399,33 @@ pub struct GatewayVoiceStateUpdateData {
     pub suppress: Option<bool>,
 }
 
+/// The cache-relevant fields of a voice state, without the nested member, so a disconnect
+/// still decodes when the member payload doesn't.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiVoiceState {
+    #[serde(default)]
+    pub guild_id: Option<Snowflake>,
+    #[serde(default)]
+    pub channel_id: Option<Snowflake>,
+    pub user_id: Snowflake,
+    #[serde(default)]
+    pub session_id: String,
+    #[serde(default)]
+    pub deaf: Option<bool>,
+    #[serde(default)]
+    pub mute: Option<bool>,
+    #[serde(default)]
+    pub self_deaf: Option<bool>,
+    #[serde(default)]
+    pub self_mute: Option<bool>,
+    #[serde(default)]
+    pub self_video: Option<bool>,
+    #[serde(default)]
+    pub self_stream: Option<bool>,
+    #[serde(default)]
+    pub suppress: Option<bool>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GatewayVoiceServerUpdateData {
     pub token: String,
@@ -342,6 +435,14 @@ pub struct GatewayVoiceServerUpdateData {
     pub connection_id: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GatewayVoiceChannelStatusUpdateData {
+    pub id: Snowflake,
+    pub guild_id: Snowflake,
+    #[serde(default)]
+    pub status: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GatewayGuildEmojisUpdateData {
     pub guild_id: Snowflake,
@@ -364,8 +465,8 @@ pub struct GatewayChannelPinsUpdateData {
     #[serde(default)]
     pub guild_id: Option<Snowflake>,
     pub channel_id: Snowflake,
-    #[serde(default)]
-    pub last_pin_timestamp: Option<String>,
+    #[serde(default, with = "crate::ser::iso8601::opt")]
+    pub last_pin_timestamp: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -379,11 +480,53 @@ pub struct GatewayPresenceUpdateData {
     pub activities: Option<Vec<GatewayActivity>>,
     #[serde(default)]
     pub custom_status: Option<GatewayCustomStatus>,
+    #[serde(default)]
+    pub client_status: Option<ClientStatus>,
+    /// Fields this crate doesn't model yet.
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
+impl GatewayPresenceUpdateData {
+    pub fn is_online(&self) -> bool {
+        self.status
+            .as_deref()
+            .is_some_and(|status| status != "offline")
+    }
+
+    pub fn is_streaming(&self) -> bool {
+        self.activities
+            .iter()
+            .flatten()
+            .any(|activity| activity.kind == ACTIVITY_STREAMING)
+    }
+}
+
+/// Status per platform; a platform is absent when the user isn't connected from it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClientStatus {
+    #[serde(default)]
+    pub desktop: Option<String>,
+    #[serde(default)]
+    pub mobile: Option<String>,
+    #[serde(default)]
+    pub web: Option<String>,
+}
+
+/// The presence's user; only `id` is guaranteed, the rest is sent when it changed.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PresenceUser {
     pub id: Snowflake,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub discriminator: Option<String>,
+    #[serde(default)]
+    pub global_name: Option<String>,
+    #[serde(default)]
+    pub avatar: Option<String>,
+    #[serde(default)]
+    pub bot: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -392,6 +535,46 @@ pub struct GatewayWebhooksUpdateData {
     pub channel_id: Snowflake,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GatewayThreadDeleteData {
+    pub id: Snowflake,
+    #[serde(default)]
+    pub guild_id: Option<Snowflake>,
+    #[serde(default)]
+    pub parent_id: Option<Snowflake>,
+    #[serde(rename = "type")]
+    pub kind: u16,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GatewayThreadListSyncData {
+    pub guild_id: Snowflake,
+    #[serde(default)]
+    pub channel_ids: Option<Vec<Snowflake>>,
+    pub threads: Vec<ApiChannel>,
+    #[serde(default)]
+    pub members: Vec<ApiThreadMember>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GatewayThreadMemberUpdateData {
+    #[serde(flatten)]
+    pub member: ApiThreadMember,
+    #[serde(default)]
+    pub guild_id: Option<Snowflake>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GatewayThreadMembersUpdateData {
+    pub id: Snowflake,
+    pub guild_id: Snowflake,
+    pub member_count: u32,
+    #[serde(default)]
+    pub added_members: Option<Vec<ApiThreadMember>>,
+    #[serde(default)]
+    pub removed_member_ids: Option<Vec<Snowflake>>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GatewayGuildScheduledEventData {
     pub guild_id: Snowflake,
@@ -423,3 +606,25 @@ pub struct GatewayReceivePayload {
     #[serde(default)]
     pub t: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn shard_identity_requires_id_below_total() {
+        assert!(ShardIdentity::new(5, 3).is_none());
+        assert!(ShardIdentity::new(3, 3).is_none());
+        let identity = ShardIdentity::new(2, 3).unwrap();
+        assert_eq!((identity.id(), identity.total()), (2, 3));
+    }
+
+    #[test]
+    fn shard_identity_round_trips_as_an_array() {
+        let identity: ShardIdentity = serde_json::from_value(json!([2, 3])).unwrap();
+        assert_eq!(serde_json::to_value(identity).unwrap(), json!([2, 3]));
+        assert!(serde_json::from_value::<ShardIdentity>(json!([5, 3])).is_err());
+    }
+}
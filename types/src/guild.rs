@@ -2,6 +2,9 @@ use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
 use crate::Snowflake;
+use crate::channel::{ApiChannelOverwrite, ChannelType};
+use crate::enums::code_enum;
+use crate::role::CreateRoleBody;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize_repr, Deserialize_repr)]
 #[repr(u8)]
@@ -13,6 +16,14 @@ pub enum GuildVerificationLevel {
     VeryHigh = 4,
 }
 
+code_enum!(GuildVerificationLevel: u8 {
+    None => "none",
+    Low => "low",
+    Medium => "medium",
+    High => "high",
+    VeryHigh => "very_high",
+});
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize_repr, Deserialize_repr)]
 #[repr(u8)]
 pub enum GuildMfaLevel {
@@ -20,6 +31,11 @@ pub enum GuildMfaLevel {
     Elevated = 1,
 }
 
+code_enum!(GuildMfaLevel: u8 {
+    None => "none",
+    Elevated => "elevated",
+});
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize_repr, Deserialize_repr)]
 #[repr(u8)]
 pub enum GuildExplicitContentFilter {
@@ -28,6 +44,12 @@ pub enum GuildExplicitContentFilter {
     AllMembers = 2,
 }
 
+code_enum!(GuildExplicitContentFilter: u8 {
+    Disabled => "disabled",
+    MembersWithoutRoles => "members_without_roles",
+    AllMembers => "all_members",
+});
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize_repr, Deserialize_repr)]
 #[repr(u8)]
 pub enum DefaultMessageNotifications {
@@ -35,6 +57,11 @@ pub enum DefaultMessageNotifications {
     OnlyMentions = 1,
 }
 
+code_enum!(DefaultMessageNotifications: u8 {
+    AllMessages => "all_messages",
+    OnlyMentions => "only_mentions",
+});
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiGuild {
     pub id: Snowflake,
@@ -86,8 +113,102 @@ pub struct ApiGuild {
     pub message_history_cutoff: Option<String>,
     #[serde(default)]
     pub permissions: Option<String>,
+    #[serde(default)]
+    pub approximate_member_count: Option<u32>,
+    #[serde(default)]
+    pub approximate_presence_count: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize_repr, Deserialize_repr)]
+#[repr(u32)]
+pub enum AuditLogActionType {
+    GuildUpdate = 1,
+    ChannelCreate = 10,
+    ChannelUpdate = 11,
+    ChannelDelete = 12,
+    ChannelOverwriteCreate = 13,
+    ChannelOverwriteUpdate = 14,
+    ChannelOverwriteDelete = 15,
+    MemberKick = 20,
+    MemberPrune = 21,
+    MemberBanAdd = 22,
+    MemberBanRemove = 23,
+    MemberUpdate = 24,
+    MemberRoleUpdate = 25,
+    MemberMove = 26,
+    MemberDisconnect = 27,
+    BotAdd = 28,
+    RoleCreate = 30,
+    RoleUpdate = 31,
+    RoleDelete = 32,
+    InviteCreate = 40,
+    InviteUpdate = 41,
+    InviteDelete = 42,
+    WebhookCreate = 50,
+    WebhookUpdate = 51,
+    WebhookDelete = 52,
+    EmojiCreate = 60,
+    EmojiUpdate = 61,
+    EmojiDelete = 62,
+    MessageDelete = 72,
+    MessageBulkDelete = 73,
+    MessagePin = 74,
+    MessageUnpin = 75,
+    StickerCreate = 90,
+    StickerUpdate = 91,
+    StickerDelete = 92,
+    GuildScheduledEventCreate = 100,
+    GuildScheduledEventUpdate = 101,
+    GuildScheduledEventDelete = 102,
+    ThreadCreate = 110,
+    ThreadUpdate = 111,
+    ThreadDelete = 112,
 }
 
+code_enum!(AuditLogActionType: u32 {
+    GuildUpdate => "guild_update",
+    ChannelCreate => "channel_create",
+    ChannelUpdate => "channel_update",
+    ChannelDelete => "channel_delete",
+    ChannelOverwriteCreate => "channel_overwrite_create",
+    ChannelOverwriteUpdate => "channel_overwrite_update",
+    ChannelOverwriteDelete => "channel_overwrite_delete",
+    MemberKick => "member_kick",
+    MemberPrune => "member_prune",
+    MemberBanAdd => "member_ban_add",
+    MemberBanRemove => "member_ban_remove",
+    MemberUpdate => "member_update",
+    MemberRoleUpdate => "member_role_update",
+    MemberMove => "member_move",
+    MemberDisconnect => "member_disconnect",
+    BotAdd => "bot_add",
+    RoleCreate => "role_create",
+    RoleUpdate => "role_update",
+    RoleDelete => "role_delete",
+    InviteCreate => "invite_create",
+    InviteUpdate => "invite_update",
+    InviteDelete => "invite_delete",
+    WebhookCreate => "webhook_create",
+    WebhookUpdate => "webhook_update",
+    WebhookDelete => "webhook_delete",
+    EmojiCreate => "emoji_create",
+    EmojiUpdate => "emoji_update",
+    EmojiDelete => "emoji_delete",
+    MessageDelete => "message_delete",
+    MessageBulkDelete => "message_bulk_delete",
+    MessagePin => "message_pin",
+    MessageUnpin => "message_unpin",
+    StickerCreate => "sticker_create",
+    StickerUpdate => "sticker_update",
+    StickerDelete => "sticker_delete",
+    GuildScheduledEventCreate => "guild_scheduled_event_create",
+    GuildScheduledEventUpdate => "guild_scheduled_event_update",
+    GuildScheduledEventDelete => "guild_scheduled_event_delete",
+    ThreadCreate => "thread_create",
+    ThreadUpdate => "thread_update",
+    ThreadDelete => "thread_delete",
+});
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuditLogChange {
     pub key: String,
@@ -97,6 +218,18 @@ pub struct AuditLogChange {
     pub new_value: Option<serde_json::Value>,
 }
 
+impl AuditLogChange {
+    /// The previous value decoded as `T`; `None` when absent or of a different shape.
+    pub fn old_as<T: serde::de::DeserializeOwned>(&self) -> Option<T> {
+        serde_json::from_value(self.old_value.clone()?).ok()
+    }
+
+    /// The new value decoded as `T`; `None` when absent or of a different shape.
+    pub fn new_as<T: serde::de::DeserializeOwned>(&self) -> Option<T> {
+        serde_json::from_value(self.new_value.clone()?).ok()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiGuildAuditLogEntry {
     pub id: String,
@@ -111,6 +244,17 @@ pub struct ApiGuildAuditLogEntry {
     pub changes: Option<Vec<AuditLogChange>>,
 }
 
+impl ApiGuildAuditLogEntry {
+    /// `action_type` as a known action; `None` for codes this crate doesn't know yet.
+    pub fn action(&self) -> Option<AuditLogActionType> {
+        AuditLogActionType::from_code(self.action_type)
+    }
+
+    pub fn changes(&self) -> &[AuditLogChange] {
+        self.changes.as_deref().unwrap_or_default()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuditLogUser {
     pub id: Snowflake,
@@ -150,3 +294,40 @@ pub struct ApiVanityUrl {
 pub struct ApiGuildFeatureToggle {
     pub enabled: bool,
 }
+
+/// Body for `POST /guilds`. Roles and channels use placeholder ids that other entries in the
+/// same request refer to; role `"0"` is `@everyone`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GuildCreateRequest {
+    pub name: String,
+    /// Guild icon as a `data:` URI.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<GuildCreateRole>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub channels: Vec<GuildCreateChannel>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_channel_id: Option<Snowflake>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GuildCreateRole {
+    pub id: Snowflake,
+    #[serde(flatten)]
+    pub role: CreateRoleBody,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuildCreateChannel {
+    pub id: Snowflake,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: ChannelType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<Snowflake>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub permission_overwrites: Vec<ApiChannelOverwrite>,
+}
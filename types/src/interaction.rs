@@ -1,9 +1,34 @@
 use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
 
 use crate::Snowflake;
+use crate::embed::ApiEmbed;
+use crate::enums::code_enum;
 use crate::user::{ApiGuildMember, ApiUser};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum InteractionCallbackType {
+    Pong = 1,
+    ChannelMessageWithSource = 4,
+    DeferredChannelMessageWithSource = 5,
+    DeferredUpdateMessage = 6,
+    UpdateMessage = 7,
+    ApplicationCommandAutocompleteResult = 8,
+    Modal = 9,
+}
+
+code_enum!(InteractionCallbackType: u8 {
+    Pong => "pong",
+    ChannelMessageWithSource => "channel_message_with_source",
+    DeferredChannelMessageWithSource => "deferred_channel_message_with_source",
+    DeferredUpdateMessage => "deferred_update_message",
+    UpdateMessage => "update_message",
+    ApplicationCommandAutocompleteResult => "application_command_autocomplete_result",
+    Modal => "modal",
+});
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum CommandOptionValue {
     String(String),
@@ -11,24 +36,86 @@ pub enum CommandOptionValue {
     Bool(bool),
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CommandOption {
     pub name: String,
     #[serde(rename = "type")]
     pub kind: u8,
     #[serde(default)]
     pub value: Option<CommandOptionValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<Vec<CommandOption>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub focused: Option<bool>,
 }
 
+/// The raw `data` of an interaction. Which fields are present depends on the interaction type;
+/// use [`ApiApplicationCommandInteraction::interaction_data`] for a typed view.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CommandData {
     #[serde(default)]
     pub id: Option<String>,
+    #[serde(default)]
     pub name: String,
     #[serde(default, rename = "type")]
     pub kind: Option<u8>,
     #[serde(default)]
     pub options: Option<Vec<CommandOption>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub component_type: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub values: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<serde_json::Value>>,
+}
+
+pub const INTERACTION_TYPE_APPLICATION_COMMAND: u8 = 2;
+pub const INTERACTION_TYPE_MESSAGE_COMPONENT: u8 = 3;
+pub const INTERACTION_TYPE_AUTOCOMPLETE: u8 = 4;
+pub const INTERACTION_TYPE_MODAL_SUBMIT: u8 = 5;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InteractionData {
+    /// A slash, user or message command, or an autocomplete request for one.
+    ApplicationCommand {
+        name: String,
+        options: Vec<CommandOption>,
+    },
+    MessageComponent {
+        custom_id: String,
+        component_type: u8,
+        values: Vec<String>,
+    },
+    ModalSubmit {
+        custom_id: String,
+        components: Vec<serde_json::Value>,
+    },
+}
+
+impl CommandData {
+    /// Interprets this payload according to the interaction `kind` it arrived with.
+    pub fn typed(&self, kind: u8) -> Option<InteractionData> {
+        match kind {
+            INTERACTION_TYPE_APPLICATION_COMMAND | INTERACTION_TYPE_AUTOCOMPLETE => {
+                Some(InteractionData::ApplicationCommand {
+                    name: self.name.clone(),
+                    options: self.options.clone().unwrap_or_default(),
+                })
+            }
+            INTERACTION_TYPE_MESSAGE_COMPONENT => Some(InteractionData::MessageComponent {
+                custom_id: self.custom_id.clone()?,
+                component_type: self.component_type?,
+                values: self.values.clone().unwrap_or_default(),
+            }),
+            INTERACTION_TYPE_MODAL_SUBMIT => Some(InteractionData::ModalSubmit {
+                custom_id: self.custom_id.clone()?,
+                components: self.components.clone().unwrap_or_default(),
+            }),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +135,24 @@ pub struct ApiApplicationCommandInteraction {
     pub member: Option<InteractionMember>,
     #[serde(default)]
     pub user: Option<ApiUser>,
+    /// The message a component was attached to; only set for component interactions.
+    #[serde(default)]
+    pub message: Option<InteractionMessage>,
+}
+
+/// Identifies the message behind a component interaction. Kept minimal so an unexpected
+/// message shape never fails the whole interaction decode.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InteractionMessage {
+    pub id: Snowflake,
+    #[serde(default)]
+    pub channel_id: Option<Snowflake>,
+}
+
+impl ApiApplicationCommandInteraction {
+    pub fn interaction_data(&self) -> Option<InteractionData> {
+        self.data.as_ref()?.typed(self.kind)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,3 +162,130 @@ pub struct InteractionMember {
     #[serde(default)]
     pub guild_id: Option<Snowflake>,
 }
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InteractionCallbackData {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embeds: Option<Vec<ApiEmbed>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<serde_json::Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tts: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flags: Option<u32>,
+    /// Modal responses only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_id: Option<String>,
+    /// Modal responses only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InteractionResponse {
+    #[serde(rename = "type")]
+    pub kind: InteractionCallbackType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<InteractionCallbackData>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiApplicationCommandOptionChoice {
+    pub name: String,
+    pub value: CommandOptionValue,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiApplicationCommandOption {
+    #[serde(rename = "type")]
+    pub kind: u8,
+    pub name: String,
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub choices: Option<Vec<ApiApplicationCommandOptionChoice>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<Vec<ApiApplicationCommandOption>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiApplicationCommand {
+    pub id: Snowflake,
+    pub application_id: Snowflake,
+    #[serde(default)]
+    pub guild_id: Option<Snowflake>,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default, rename = "type")]
+    pub kind: Option<u8>,
+    #[serde(default)]
+    pub options: Option<Vec<ApiApplicationCommandOption>>,
+    #[serde(default)]
+    pub default_member_permissions: Option<String>,
+    #[serde(default)]
+    pub dm_permission: Option<bool>,
+    #[serde(default)]
+    pub nsfw: Option<bool>,
+    #[serde(default)]
+    pub version: Option<Snowflake>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ApplicationCommandCreateRequest {
+    pub name: String,
+    pub description: String,
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<Vec<ApiApplicationCommandOption>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_member_permissions: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dm_permission: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nsfw: Option<bool>,
+}
+
+impl ApplicationCommandCreateRequest {
+    /// Whether `existing` already matches this definition, treating omitted fields as their
+    /// server-side defaults.
+    pub fn matches(&self, existing: &ApiApplicationCommand) -> bool {
+        self.name == existing.name
+            && self.description == existing.description
+            && self.kind.unwrap_or(1) == existing.kind.unwrap_or(1)
+            && options_match(self.options.as_deref(), existing.options.as_deref())
+            && permissions_key(self.default_member_permissions.as_deref())
+                == permissions_key(existing.default_member_permissions.as_deref())
+            && self.dm_permission.unwrap_or(true) == existing.dm_permission.unwrap_or(true)
+            && self.nsfw.unwrap_or(false) == existing.nsfw.unwrap_or(false)
+    }
+}
+
+/// Compares option lists, treating an omitted list, `required` or `choices` as its default.
+fn options_match(
+    desired: Option<&[ApiApplicationCommandOption]>,
+    existing: Option<&[ApiApplicationCommandOption]>,
+) -> bool {
+    let desired = desired.unwrap_or_default();
+    let existing = existing.unwrap_or_default();
+    desired.len() == existing.len()
+        && desired.iter().zip(existing).all(|(a, b)| {
+            a.kind == b.kind
+                && a.name == b.name
+                && a.description == b.description
+                && a.required.unwrap_or(false) == b.required.unwrap_or(false)
+                && a.choices.as_deref().unwrap_or_default()
+                    == b.choices.as_deref().unwrap_or_default()
+                && options_match(a.options.as_deref(), b.options.as_deref())
+        })
+}
+
+/// Permission strings compared by value, so `"08"` and `"8"` match; unparsable strings are
+/// compared as written.
+fn permissions_key(permissions: Option<&str>) -> Option<Result<u64, &str>> {
+    permissions.map(|raw| raw.parse().map_err(|_| raw))
+}
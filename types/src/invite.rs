@@ -1,8 +1,9 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::Snowflake;
 use crate::channel::ApiChannelPartial;
 use crate::user::ApiUser;
+use crate::{QueryValues, Snowflake};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiGuildPartial {
@@ -31,12 +32,12 @@ pub struct ApiInvite {
     pub member_count: Option<u32>,
     #[serde(default)]
     pub presence_count: Option<u32>,
-    #[serde(default)]
-    pub expires_at: Option<String>,
+    #[serde(default, with = "crate::ser::iso8601::opt")]
+    pub expires_at: Option<DateTime<Utc>>,
     #[serde(default)]
     pub temporary: Option<bool>,
-    #[serde(default)]
-    pub created_at: Option<String>,
+    #[serde(default, with = "crate::ser::iso8601::opt")]
+    pub created_at: Option<DateTime<Utc>>,
     #[serde(default)]
     pub uses: Option<u32>,
     #[serde(default)]
@@ -44,3 +45,29 @@ pub struct ApiInvite {
     #[serde(default)]
     pub max_age: Option<u32>,
 }
+
+#[derive(Debug, Clone, Default)]
+pub struct GetInviteQuery {
+    /// Fills in `member_count` and `presence_count`.
+    pub with_counts: bool,
+    /// Fills in `expires_at`.
+    pub with_expiration: bool,
+    pub guild_scheduled_event_id: Option<Snowflake>,
+}
+
+impl GetInviteQuery {
+    pub fn to_query_string(&self) -> String {
+        let mut query = QueryValues::new();
+        if self.with_counts {
+            query.insert("with_counts", true);
+        }
+        if self.with_expiration {
+            query.insert("with_expiration", true);
+        }
+        query.insert_opt(
+            "guild_scheduled_event_id",
+            self.guild_scheduled_event_id.as_ref(),
+        );
+        query.to_query_string()
+    }
+}
@@ -1,7 +1,10 @@
+pub mod application;
+pub mod auth;
 pub mod ban;
 pub mod channel;
 pub mod embed;
 pub mod emoji;
+pub mod enums;
 pub mod errors;
 pub mod gateway;
 pub mod guild;
@@ -9,17 +12,26 @@ pub mod instance;
 pub mod interaction;
 pub mod invite;
 pub mod message;
+pub mod oauth2;
+pub mod patch;
+pub mod poll;
+pub mod query;
 pub mod role;
 pub mod routes;
+pub mod scheduled_event;
+pub mod ser;
 pub mod snowflake;
 pub mod sticker;
 pub mod user;
 pub mod webhook;
 
+pub use application::*;
+pub use auth::*;
 pub use ban::*;
 pub use channel::*;
 pub use embed::*;
 pub use emoji::*;
+pub use enums::UnknownEnumValue;
 pub use errors::*;
 pub use gateway::*;
 pub use guild::*;
@@ -27,8 +39,13 @@ pub use instance::*;
 pub use interaction::*;
 pub use invite::*;
 pub use message::*;
+pub use oauth2::*;
+pub use patch::*;
+pub use poll::*;
+pub use query::QueryValues;
 pub use role::*;
 pub use routes::*;
+pub use scheduled_event::*;
 pub use snowflake::*;
 pub use sticker::*;
 pub use user::*;
@@ -1,9 +1,11 @@
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
-use crate::Snowflake;
 use crate::embed::ApiEmbed;
+use crate::enums::code_enum;
 use crate::user::{ApiGuildMember, ApiUser};
+use crate::{QueryValues, Snowflake};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize_repr, Deserialize_repr)]
 #[repr(u8)]
@@ -19,6 +21,18 @@ pub enum MessageType {
     Reply = 19,
 }
 
+code_enum!(MessageType: u8 {
+    Default => "default",
+    RecipientAdd => "recipient_add",
+    RecipientRemove => "recipient_remove",
+    Call => "call",
+    ChannelNameChange => "channel_name_change",
+    ChannelIconChange => "channel_icon_change",
+    ChannelPinnedMessage => "channel_pinned_message",
+    UserJoin => "user_join",
+    Reply => "reply",
+});
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiReactionEmoji {
     pub id: Option<Snowflake>,
@@ -27,12 +41,45 @@ pub struct ApiReactionEmoji {
     pub animated: Option<bool>,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum ReactionKind {
+    #[default]
+    Normal = 0,
+    Burst = 1,
+}
+
+code_enum!(ReactionKind: u8 {
+    Normal => "normal",
+    Burst => "burst",
+});
+
+impl ReactionKind {
+    pub fn as_u8(self) -> u8 {
+        self as u8
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ApiReactionCountDetails {
+    #[serde(default)]
+    pub normal: u32,
+    #[serde(default)]
+    pub burst: u32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiMessageReaction {
     pub emoji: ApiReactionEmoji,
     pub count: u32,
     #[serde(default)]
     pub me: Option<bool>,
+    #[serde(default)]
+    pub me_burst: Option<bool>,
+    #[serde(default)]
+    pub count_details: ApiReactionCountDetails,
+    #[serde(default)]
+    pub burst_colors: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,17 +95,18 @@ pub struct ApiMessageReference {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiMessageCall {
     pub participants: Vec<String>,
-    #[serde(default)]
-    pub ended_timestamp: Option<String>,
+    #[serde(default, with = "crate::ser::iso8601::opt")]
+    pub ended_timestamp: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiMessageSnapshot {
     #[serde(default)]
     pub content: Option<String>,
-    pub timestamp: String,
-    #[serde(default)]
-    pub edited_timestamp: Option<String>,
+    #[serde(default, with = "crate::ser::iso8601::lenient")]
+    pub timestamp: Option<DateTime<Utc>>,
+    #[serde(default, with = "crate::ser::iso8601::lenient")]
+    pub edited_timestamp: Option<DateTime<Utc>>,
     #[serde(default)]
     pub mentions: Option<Vec<String>>,
     #[serde(default)]
@@ -104,8 +152,8 @@ pub struct ApiMessageAttachment {
     pub duration: Option<f64>,
     #[serde(default)]
     pub waveform: Option<String>,
-    #[serde(default)]
-    pub expires_at: Option<String>,
+    #[serde(default, with = "crate::ser::iso8601::opt")]
+    pub expires_at: Option<DateTime<Utc>>,
     #[serde(default)]
     pub expired: Option<bool>,
 }
@@ -136,8 +184,10 @@ pub struct ApiMessage {
     #[serde(default)]
     pub flags: Option<u32>,
     pub content: String,
-    pub timestamp: String,
-    pub edited_timestamp: Option<String>,
+    #[serde(default, with = "crate::ser::iso8601::lenient")]
+    pub timestamp: Option<DateTime<Utc>>,
+    #[serde(default, with = "crate::ser::iso8601::lenient")]
+    pub edited_timestamp: Option<DateTime<Utc>>,
     pub pinned: bool,
     #[serde(default)]
     pub mention_everyone: Option<bool>,
@@ -163,8 +213,174 @@ pub struct ApiMessage {
     pub nonce: Option<String>,
     #[serde(default)]
     pub call: Option<ApiMessageCall>,
+    #[serde(default, skip_serializing_if = "ReferencedMessage::is_not_a_reply")]
+    pub referenced_message: ReferencedMessage,
+    #[serde(default)]
+    pub member: Option<ApiGuildMember>,
     #[serde(default)]
-    pub referenced_message: Option<Box<ApiMessage>>,
+    pub application_id: Option<Snowflake>,
+    #[serde(default)]
+    pub interaction: Option<ApiMessageInteraction>,
+    #[serde(default)]
+    pub poll: Option<crate::poll::ApiPoll>,
+}
+
+/// Metadata about the interaction a message was sent in response to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiMessageInteraction {
+    pub id: Snowflake,
+    #[serde(rename = "type")]
+    pub kind: u8,
+    pub name: String,
+    pub user: ApiUser,
     #[serde(default)]
     pub member: Option<ApiGuildMember>,
 }
+
+/// The `referenced_message` of a reply: absent when the message is not a reply, `null` when the
+/// referenced message was deleted.
+#[derive(Debug, Clone, Default)]
+pub enum ReferencedMessage {
+    #[default]
+    NotAReply,
+    Deleted,
+    Message(Box<ApiMessage>),
+}
+
+impl ReferencedMessage {
+    pub fn is_not_a_reply(&self) -> bool {
+        matches!(self, Self::NotAReply)
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        matches!(self, Self::Deleted)
+    }
+
+    pub fn as_message(&self) -> Option<&ApiMessage> {
+        match self {
+            Self::Message(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
+
+impl Serialize for ReferencedMessage {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Message(msg) => serializer.serialize_some(msg),
+            _ => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ReferencedMessage {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(
+            match Option::<Box<ApiMessage>>::deserialize(deserializer)? {
+                Some(msg) => Self::Message(msg),
+                None => Self::Deleted,
+            },
+        )
+    }
+}
+
+/// Channel history page. `before`, `after` and `around` are mutually exclusive.
+#[derive(Debug, Clone, Default)]
+pub struct GetMessagesQuery {
+    pub before: Option<Snowflake>,
+    pub after: Option<Snowflake>,
+    pub around: Option<Snowflake>,
+    /// 1-100.
+    pub limit: Option<u32>,
+}
+
+impl GetMessagesQuery {
+    pub fn to_query_string(&self) -> String {
+        let mut query = QueryValues::new();
+        query
+            .insert_opt("limit", self.limit)
+            .insert_opt("before", self.before.as_ref())
+            .insert_opt("after", self.after.as_ref())
+            .insert_opt("around", self.around.as_ref());
+        query.to_query_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+
+    fn message(extra: Value) -> Value {
+        let mut msg = json!({
+            "id": "2",
+            "channel_id": "1",
+            "author": { "id": "3", "username": "u", "discriminator": "0001" },
+            "type": 0,
+            "content": "hi",
+            "timestamp": "2024-01-01T00:00:00.000Z",
+            "pinned": false,
+        });
+        msg.as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        msg
+    }
+
+    #[test]
+    fn referenced_message_absent_is_not_a_reply() {
+        let msg: ApiMessage = serde_json::from_value(message(json!({}))).unwrap();
+        assert!(msg.referenced_message.is_not_a_reply());
+        let out = serde_json::to_value(&msg).unwrap();
+        assert!(out.get("referenced_message").is_none());
+    }
+
+    #[test]
+    fn referenced_message_null_is_deleted() {
+        let msg: ApiMessage =
+            serde_json::from_value(message(json!({ "referenced_message": null }))).unwrap();
+        assert!(msg.referenced_message.is_deleted());
+        let out = serde_json::to_value(&msg).unwrap();
+        assert_eq!(out["referenced_message"], Value::Null);
+    }
+
+    #[test]
+    fn referenced_message_object_is_the_message() {
+        let msg: ApiMessage = serde_json::from_value(message(
+            json!({ "referenced_message": message(json!({ "id": "9", "content": "original" })) }),
+        ))
+        .unwrap();
+        let reply = msg.referenced_message.as_message().unwrap();
+        assert_eq!(reply.id, "9");
+        assert_eq!(reply.content, "original");
+    }
+
+    #[test]
+    fn decodes_an_application_webhook_message() {
+        let msg: ApiMessage = serde_json::from_value(message(json!({
+            "webhook_id": "5",
+            "application_id": "5",
+            "interaction": {
+                "id": "7",
+                "type": 2,
+                "name": "ping",
+                "user": { "id": "3", "username": "u", "discriminator": "0001" },
+            },
+        })))
+        .unwrap();
+        assert_eq!(msg.application_id.as_deref(), Some("5"));
+        let interaction = msg.interaction.unwrap();
+        assert_eq!(interaction.id, "7");
+        assert_eq!(interaction.kind, 2);
+        assert_eq!(interaction.name, "ping");
+        assert_eq!(interaction.user.id, "3");
+    }
+
+    #[test]
+    fn application_fields_default_to_none() {
+        let msg: ApiMessage = serde_json::from_value(message(json!({}))).unwrap();
+        assert!(msg.application_id.is_none());
+        assert!(msg.interaction.is_none());
+    }
+}
@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+
+use crate::Snowflake;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OAuth2Scope {
+    #[serde(rename = "identify")]
+    Identify,
+    #[serde(rename = "email")]
+    Email,
+    #[serde(rename = "connections")]
+    Connections,
+    #[serde(rename = "guilds")]
+    Guilds,
+    #[serde(rename = "guilds.join")]
+    GuildsJoin,
+    #[serde(rename = "guilds.members.read")]
+    GuildsMembersRead,
+    #[serde(rename = "bot")]
+    Bot,
+    #[serde(rename = "applications.commands")]
+    ApplicationsCommands,
+    #[serde(rename = "webhook.incoming")]
+    WebhookIncoming,
+}
+
+impl OAuth2Scope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Identify => "identify",
+            Self::Email => "email",
+            Self::Connections => "connections",
+            Self::Guilds => "guilds",
+            Self::GuildsJoin => "guilds.join",
+            Self::GuildsMembersRead => "guilds.members.read",
+            Self::Bot => "bot",
+            Self::ApplicationsCommands => "applications.commands",
+            Self::WebhookIncoming => "webhook.incoming",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        Some(match raw {
+            "identify" => Self::Identify,
+            "email" => Self::Email,
+            "connections" => Self::Connections,
+            "guilds" => Self::Guilds,
+            "guilds.join" => Self::GuildsJoin,
+            "guilds.members.read" => Self::GuildsMembersRead,
+            "bot" => Self::Bot,
+            "applications.commands" => Self::ApplicationsCommands,
+            "webhook.incoming" => Self::WebhookIncoming,
+            _ => return None,
+        })
+    }
+}
+
+/// (De)serializes a scope list as the space-delimited string OAuth2 uses on the wire.
+pub mod scopes_space_delimited {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::OAuth2Scope;
+
+    pub fn join(scopes: &[OAuth2Scope]) -> String {
+        scopes
+            .iter()
+            .map(OAuth2Scope::as_str)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    pub fn serialize<S: Serializer>(
+        scopes: &[OAuth2Scope],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&join(scopes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<OAuth2Scope>, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Ok(raw
+            .split_whitespace()
+            .filter_map(OAuth2Scope::parse)
+            .collect())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OAuth2Prompt {
+    Consent,
+    None,
+}
+
+impl OAuth2Prompt {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Consent => "consent",
+            Self::None => "none",
+        }
+    }
+}
+
+/// Query of the authorization URL; the client id comes from the OAuth2 client building it.
+#[derive(Debug, Clone, Default)]
+pub struct AuthorizeParams {
+    pub redirect_uri: String,
+    pub scopes: Vec<OAuth2Scope>,
+    pub state: Option<String>,
+    pub prompt: Option<OAuth2Prompt>,
+    pub permissions: Option<u64>,
+    pub guild_id: Option<Snowflake>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuth2TokenResponse {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: u64,
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+    #[serde(default, with = "scopes_space_delimited")]
+    pub scope: Vec<OAuth2Scope>,
+}
@@ -0,0 +1,84 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A field in a PATCH body that distinguishes "leave unchanged" from "clear".
+///
+/// Use with `#[serde(default, skip_serializing_if = "Patch::is_absent")]`: `Absent` omits the
+/// key, `Null` sends `null`, and `Value` sends the value as-is (so `Value(vec![])` is `[]`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Patch<T> {
+    #[default]
+    Absent,
+    Null,
+    Value(T),
+}
+
+impl<T> Patch<T> {
+    pub fn is_absent(&self) -> bool {
+        matches!(self, Patch::Absent)
+    }
+
+    pub fn as_option(&self) -> Option<&T> {
+        match self {
+            Patch::Value(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl<T> From<Option<T>> for Patch<T> {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(v) => Patch::Value(v),
+            None => Patch::Null,
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for Patch<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Patch::Value(v) => v.serialize(serializer),
+            Patch::Absent | Patch::Null => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Patch<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Option::<T>::deserialize(deserializer).map(Patch::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+
+    use super::*;
+
+    #[derive(Debug, Default, Serialize, Deserialize)]
+    struct Body {
+        #[serde(default, skip_serializing_if = "Patch::is_absent")]
+        components: Patch<Vec<u32>>,
+    }
+
+    fn body(components: Patch<Vec<u32>>) -> serde_json::Value {
+        serde_json::to_value(Body { components }).unwrap()
+    }
+
+    #[test]
+    fn distinguishes_omitted_null_and_empty() {
+        assert_eq!(body(Patch::Absent), json!({}));
+        assert_eq!(body(Patch::Null), json!({ "components": null }));
+        assert_eq!(body(Patch::Value(vec![])), json!({ "components": [] }));
+        assert_eq!(body(Patch::Value(vec![1])), json!({ "components": [1] }));
+    }
+
+    #[test]
+    fn deserializes_missing_null_and_value() {
+        let parse = |v| serde_json::from_value::<Body>(v).unwrap().components;
+        assert_eq!(parse(json!({})), Patch::Absent);
+        assert_eq!(parse(json!({ "components": null })), Patch::Null);
+        assert_eq!(parse(json!({ "components": [] })), Patch::Value(vec![]));
+    }
+}
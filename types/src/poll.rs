@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::message::ApiReactionEmoji;
+
+/// Layout of a poll; `1` is the only one currently defined.
+pub const POLL_LAYOUT_DEFAULT: u8 = 1;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ApiPollMedia {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub emoji: Option<ApiReactionEmoji>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiPollAnswer {
+    /// Assigned by the server; omit when creating a poll.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answer_id: Option<u32>,
+    pub poll_media: ApiPollMedia,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiPollAnswerCount {
+    pub id: u32,
+    pub count: u32,
+    pub me_voted: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiPollResults {
+    pub is_finalized: bool,
+    #[serde(default)]
+    pub answer_counts: Vec<ApiPollAnswerCount>,
+}
+
+/// A poll attached to a received message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiPoll {
+    pub question: ApiPollMedia,
+    pub answers: Vec<ApiPollAnswer>,
+    #[serde(default, with = "crate::ser::iso8601::opt")]
+    pub expiry: Option<DateTime<Utc>>,
+    pub allow_multiselect: bool,
+    #[serde(default)]
+    pub layout_type: Option<u8>,
+    #[serde(default)]
+    pub results: Option<ApiPollResults>,
+}
+
+/// The `poll` field of a message create request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PollCreateRequest {
+    pub question: ApiPollMedia,
+    pub answers: Vec<ApiPollAnswer>,
+    /// How long the poll stays open, in hours.
+    #[serde(rename = "duration")]
+    pub duration_hours: u32,
+    #[serde(default)]
+    pub allow_multiselect: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout_type: Option<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiPollAnswerVoters {
+    pub users: Vec<crate::user::ApiUser>,
+}
@@ -0,0 +1,65 @@
+use std::fmt;
+
+use crate::routes::urlencoding_encode;
+
+/// Query string parameters for a route. Values are URL-encoded; keys are written as given.
+///
+/// Displays as `?key=value&...`, or as an empty string when no parameter is set, so it can be
+/// appended to a route directly.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryValues {
+    pairs: Vec<(String, String)>,
+}
+
+impl QueryValues {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, key: impl Into<String>, value: impl fmt::Display) -> &mut Self {
+        self.pairs.push((key.into(), value.to_string()));
+        self
+    }
+
+    pub fn insert_opt(
+        &mut self,
+        key: impl Into<String>,
+        value: Option<impl fmt::Display>,
+    ) -> &mut Self {
+        if let Some(value) = value {
+            self.insert(key, value);
+        }
+        self
+    }
+
+    /// Adds `key` once per value, e.g. `ids=1&ids=2`; nothing is added for an empty iterator.
+    pub fn insert_many<V: fmt::Display>(
+        &mut self,
+        key: impl Into<String>,
+        values: impl IntoIterator<Item = V>,
+    ) -> &mut Self {
+        let key = key.into();
+        for value in values {
+            self.pairs.push((key.clone(), value.to_string()));
+        }
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    pub fn to_query_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for QueryValues {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (key, value)) in self.pairs.iter().enumerate() {
+            let sep = if i == 0 { '?' } else { '&' };
+            write!(f, "{sep}{key}={}", urlencoding_encode(value))?;
+        }
+        Ok(())
+    }
+}
@@ -14,6 +14,8 @@ pub struct ApiRole {
     pub hoist: bool,
     pub mentionable: bool,
     #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
     pub unicode_emoji: Option<String>,
 }
 
@@ -22,6 +24,7 @@ pub struct CreateRoleBody {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    /// Decimal permission bitset, e.g. from `fluxer_util::permissions_to_string`.
     pub permissions: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub color: Option<u32>,
@@ -29,6 +32,9 @@ pub struct CreateRoleBody {
     pub hoist: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mentionable: Option<bool>,
+    /// Role icon as a `data:` URI.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub unicode_emoji: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -42,6 +48,7 @@ pub struct UpdateRoleBody {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    /// Decimal permission bitset, e.g. from `fluxer_util::permissions_to_string`.
     pub permissions: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub color: Option<u32>,
@@ -49,6 +56,9 @@ pub struct UpdateRoleBody {
     pub hoist: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mentionable: Option<bool>,
+    /// Role icon as a `data:` URI.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub unicode_emoji: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -13,12 +13,24 @@ impl Routes {
         format!("/channels/{channel_id}/messages/{message_id}")
     }
 
+    pub fn channel_poll_expire(channel_id: &str, message_id: &str) -> String {
+        format!("/channels/{channel_id}/polls/{message_id}/expire")
+    }
+
+    pub fn channel_poll_answer_voters(
+        channel_id: &str,
+        message_id: &str,
+        answer_id: u32,
+    ) -> String {
+        format!("/channels/{channel_id}/polls/{message_id}/answers/{answer_id}")
+    }
+
     pub fn channel_message_reactions(channel_id: &str, message_id: &str) -> String {
         format!("/channels/{channel_id}/messages/{message_id}/reactions")
     }
 
     pub fn channel_message_reaction(channel_id: &str, message_id: &str, emoji: &str) -> String {
-        let encoded = urlencoding_encode(emoji);
+        let encoded = crate::emoji::ReactionEmoji::parse(emoji).to_route_segment();
         format!("/channels/{channel_id}/messages/{message_id}/reactions/{encoded}")
     }
 
@@ -58,6 +70,27 @@ impl Routes {
         format!("/channels/{channel_id}/recipients/{user_id}")
     }
 
+    pub fn channel_message_threads(channel_id: &str, message_id: &str) -> String {
+        format!("/channels/{channel_id}/messages/{message_id}/threads")
+    }
+
+    pub fn channel_threads(id: &str) -> String {
+        format!("/channels/{id}/threads")
+    }
+
+    pub fn channel_archived_threads(id: &str, private: bool) -> String {
+        let scope = if private { "private" } else { "public" };
+        format!("/channels/{id}/threads/archived/{scope}")
+    }
+
+    pub fn thread_members(id: &str) -> String {
+        format!("/channels/{id}/thread-members")
+    }
+
+    pub fn thread_member(id: &str, user_id: &str) -> String {
+        format!("/channels/{id}/thread-members/{user_id}")
+    }
+
     pub fn channel_message_attachment(
         channel_id: &str,
         message_id: &str,
@@ -102,10 +135,26 @@ impl Routes {
         format!("/guilds/{id}/channels")
     }
 
+    pub fn guild_active_threads(id: &str) -> String {
+        format!("/guilds/{id}/threads/active")
+    }
+
     pub fn guild_members(id: &str) -> String {
         format!("/guilds/{id}/members")
     }
 
+    pub fn guild_scheduled_events(guild_id: &str) -> String {
+        format!("/guilds/{guild_id}/scheduled-events")
+    }
+
+    pub fn guild_scheduled_event(guild_id: &str, event_id: &str) -> String {
+        format!("/guilds/{guild_id}/scheduled-events/{event_id}")
+    }
+
+    pub fn guild_scheduled_event_users(guild_id: &str, event_id: &str) -> String {
+        format!("/guilds/{guild_id}/scheduled-events/{event_id}/users")
+    }
+
     pub fn guild_member(guild_id: &str, user_id: &str) -> String {
         format!("/guilds/{guild_id}/members/{user_id}")
     }
@@ -159,6 +208,14 @@ impl Routes {
         format!("/guilds/{guild_id}/stickers/{sticker_id}")
     }
 
+    pub fn sticker_packs() -> &'static str {
+        "/sticker-packs"
+    }
+
+    pub fn sticker_pack(pack_id: &str) -> String {
+        format!("/sticker-packs/{pack_id}")
+    }
+
     pub fn guild_webhooks(id: &str) -> String {
         format!("/guilds/{id}/webhooks")
     }
@@ -171,6 +228,10 @@ impl Routes {
         format!("/webhooks/{id}/{token}")
     }
 
+    pub fn webhook_message(id: &str, token: &str, message_id: &str) -> String {
+        format!("/webhooks/{id}/{token}/messages/{message_id}")
+    }
+
     pub fn user(id: &str) -> String {
         format!("/users/{id}")
     }
@@ -202,10 +263,34 @@ impl Routes {
         "/instance"
     }
 
+    pub fn auth_login() -> &'static str {
+        "/auth/login"
+    }
+
+    pub fn auth_login_mfa_totp() -> &'static str {
+        "/auth/login/mfa/totp"
+    }
+
+    pub fn auth_register() -> &'static str {
+        "/auth/register"
+    }
+
+    pub fn auth_logout() -> &'static str {
+        "/auth/logout"
+    }
+
+    pub fn auth_verify_email() -> &'static str {
+        "/auth/verify"
+    }
+
     pub fn gateway_bot() -> &'static str {
         "/gateway/bot"
     }
 
+    pub fn current_application() -> &'static str {
+        "/applications/@me"
+    }
+
     pub fn stream_preview(stream_key: &str) -> String {
         let encoded = urlencoding_encode(stream_key);
         format!("/streams/{encoded}/preview")
@@ -219,12 +304,24 @@ impl Routes {
         format!("/applications/{application_id}/commands/{command_id}")
     }
 
+    pub fn guild_application_commands(application_id: &str, guild_id: &str) -> String {
+        format!("/applications/{application_id}/guilds/{guild_id}/commands")
+    }
+
+    pub fn guild_application_command(
+        application_id: &str,
+        guild_id: &str,
+        command_id: &str,
+    ) -> String {
+        format!("/applications/{application_id}/guilds/{guild_id}/commands/{command_id}")
+    }
+
     pub fn interaction_callback(interaction_id: &str, interaction_token: &str) -> String {
         format!("/interactions/{interaction_id}/{interaction_token}/callback")
     }
 }
 
-fn urlencoding_encode(s: &str) -> String {
+pub(crate) fn urlencoding_encode(s: &str) -> String {
     let mut result = String::with_capacity(s.len());
     for byte in s.bytes() {
         match byte {
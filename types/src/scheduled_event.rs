@@ -0,0 +1,173 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+use crate::enums::code_enum;
+use crate::user::{ApiGuildMember, ApiUser};
+use crate::{Patch, QueryValues, Snowflake};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum ScheduledEventEntityType {
+    StageInstance = 1,
+    Voice = 2,
+    External = 3,
+}
+
+code_enum!(ScheduledEventEntityType: u8 {
+    StageInstance => "stage_instance",
+    Voice => "voice",
+    External => "external",
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum ScheduledEventStatus {
+    Scheduled = 1,
+    Active = 2,
+    Completed = 3,
+    Canceled = 4,
+}
+
+code_enum!(ScheduledEventStatus: u8 {
+    Scheduled => "scheduled",
+    Active => "active",
+    Completed => "completed",
+    Canceled => "canceled",
+});
+
+/// Privacy level of a scheduled event; `2` (guild only) is the only one currently defined.
+pub const SCHEDULED_EVENT_GUILD_ONLY: u8 = 2;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ApiScheduledEventMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiGuildScheduledEvent {
+    pub id: Snowflake,
+    pub guild_id: Snowflake,
+    #[serde(default)]
+    pub channel_id: Option<Snowflake>,
+    #[serde(default)]
+    pub creator_id: Option<Snowflake>,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(with = "crate::ser::iso8601")]
+    pub scheduled_start_time: DateTime<Utc>,
+    #[serde(default, with = "crate::ser::iso8601::opt")]
+    pub scheduled_end_time: Option<DateTime<Utc>>,
+    pub privacy_level: u8,
+    pub status: ScheduledEventStatus,
+    pub entity_type: ScheduledEventEntityType,
+    #[serde(default)]
+    pub entity_id: Option<Snowflake>,
+    #[serde(default)]
+    pub entity_metadata: Option<ApiScheduledEventMetadata>,
+    #[serde(default)]
+    pub creator: Option<ApiUser>,
+    #[serde(default)]
+    pub user_count: Option<u32>,
+    #[serde(default)]
+    pub image: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateScheduledEventRequest {
+    pub name: String,
+    pub privacy_level: u8,
+    #[serde(with = "crate::ser::iso8601")]
+    pub scheduled_start_time: DateTime<Utc>,
+    /// Required for [`ScheduledEventEntityType::External`].
+    #[serde(
+        default,
+        with = "crate::ser::iso8601::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub scheduled_end_time: Option<DateTime<Utc>>,
+    pub entity_type: ScheduledEventEntityType,
+    /// Required for stage and voice events.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel_id: Option<Snowflake>,
+    /// Required for external events (`location`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity_metadata: Option<ApiScheduledEventMetadata>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Cover image as a `data:` URI.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateScheduledEventRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// `Patch::Null` moves the event out of its channel, e.g. when switching to external.
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub channel_id: Patch<Snowflake>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity_metadata: Option<ApiScheduledEventMetadata>,
+    #[serde(
+        default,
+        with = "crate::ser::iso8601::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub scheduled_start_time: Option<DateTime<Utc>>,
+    #[serde(
+        default,
+        with = "crate::ser::iso8601::opt",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub scheduled_end_time: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub description: Patch<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity_type: Option<ScheduledEventEntityType>,
+    /// Starts, completes or cancels the event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ScheduledEventStatus>,
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub image: Patch<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiScheduledEventUser {
+    pub guild_scheduled_event_id: Snowflake,
+    pub user: ApiUser,
+    #[serde(default)]
+    pub member: Option<ApiGuildMember>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ScheduledEventUsersQuery {
+    /// 1-100.
+    pub limit: Option<u32>,
+    pub with_member: bool,
+    pub before: Option<Snowflake>,
+    pub after: Option<Snowflake>,
+}
+
+impl ScheduledEventUsersQuery {
+    pub fn to_query_string(&self) -> String {
+        let mut query = QueryValues::new();
+        query
+            .insert_opt("limit", self.limit)
+            .insert_opt("before", self.before.as_ref())
+            .insert_opt("after", self.after.as_ref());
+        if self.with_member {
+            query.insert("with_member", true);
+        }
+        query.to_query_string()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GatewayScheduledEventUserData {
+    pub guild_scheduled_event_id: Snowflake,
+    pub user_id: Snowflake,
+    pub guild_id: Snowflake,
+}
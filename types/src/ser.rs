@@ -0,0 +1,322 @@
+pub mod iso8601 {
+    use chrono::{DateTime, NaiveDateTime, SecondsFormat, Utc};
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        value: &DateTime<Utc>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<DateTime<Utc>, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        parse(&raw).ok_or_else(|| D::Error::custom(format!("invalid ISO-8601 timestamp: {raw}")))
+    }
+
+    /// Parses an RFC 3339 timestamp, falling back to a naive timestamp read as UTC.
+    pub fn parse(raw: &str) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(raw)
+            .map(|dt| dt.with_timezone(&Utc))
+            .ok()
+            .or_else(|| {
+                NaiveDateTime::parse_from_str(raw, "%Y-%m-%dT%H:%M:%S%.f")
+                    .ok()
+                    .map(|dt| dt.and_utc())
+            })
+    }
+
+    pub mod opt {
+        use chrono::{DateTime, Utc};
+        use serde::de::Error;
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(
+            value: &Option<DateTime<Utc>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match value {
+                Some(dt) => super::serialize(dt, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<DateTime<Utc>>, D::Error> {
+            match Option::<String>::deserialize(deserializer)? {
+                Some(raw) => super::parse(&raw)
+                    .map(Some)
+                    .ok_or_else(|| D::Error::custom(format!("invalid ISO-8601 timestamp: {raw}"))),
+                None => Ok(None),
+            }
+        }
+    }
+
+    /// Like [`opt`], but a malformed or non-string value decodes as `None` instead of failing
+    /// the surrounding struct.
+    pub mod lenient {
+        use chrono::{DateTime, Utc};
+        use serde::{Deserialize, Deserializer, Serializer};
+        use serde_json::Value;
+
+        pub fn serialize<S: Serializer>(
+            value: &Option<DateTime<Utc>>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            super::opt::serialize(value, serializer)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<DateTime<Utc>>, D::Error> {
+            Ok(match Option::<Value>::deserialize(deserializer)? {
+                Some(Value::String(raw)) => super::parse(&raw),
+                _ => None,
+            })
+        }
+    }
+}
+
+/// Optional fields whose value is dropped to `None` when it doesn't decode, so an unexpected
+/// shape in a nested object never fails the response around it.
+pub mod lenient {
+    use serde::de::DeserializeOwned;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde_json::Value;
+
+    pub fn serialize<S: Serializer, T: Serialize>(
+        value: &Option<T>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: DeserializeOwned>(
+        deserializer: D,
+    ) -> Result<Option<T>, D::Error> {
+        Ok(Option::<Value>::deserialize(deserializer)?
+            .and_then(|value| serde_json::from_value(value).ok()))
+    }
+}
+
+/// Integer Unix timestamps in seconds.
+pub mod unix_seconds {
+    use chrono::{DateTime, Utc};
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        value: &DateTime<Utc>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(value.timestamp())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<DateTime<Utc>, D::Error> {
+        let secs = i64::deserialize(deserializer)?;
+        DateTime::from_timestamp(secs, 0)
+            .ok_or_else(|| D::Error::custom(format!("invalid Unix timestamp: {secs}")))
+    }
+}
+
+/// Snowflakes written as JSON strings; reads either a string or an integer.
+pub mod snowflake_string {
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &str, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(value)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+        deserializer.deserialize_any(super::SnowflakeVisitor)
+    }
+
+    pub mod opt {
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(
+            value: &Option<String>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match value {
+                Some(id) => super::serialize(id, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<String>, D::Error> {
+            #[derive(Deserialize)]
+            struct Wrapped(#[serde(deserialize_with = "super::deserialize")] String);
+
+            Ok(Option::<Wrapped>::deserialize(deserializer)?.map(|Wrapped(id)| id))
+        }
+    }
+}
+
+/// Snowflakes written as JSON integers, for routes that expect numeric ids; reads either a
+/// string or an integer.
+pub mod snowflake_int {
+    use serde::ser::Error;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &str, serializer: S) -> Result<S::Ok, S::Error> {
+        let id: u64 = value
+            .parse()
+            .map_err(|_| S::Error::custom(format!("invalid snowflake: {value}")))?;
+        serializer.serialize_u64(id)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+        deserializer.deserialize_any(super::SnowflakeVisitor)
+    }
+
+    pub mod opt {
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(
+            value: &Option<String>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match value {
+                Some(id) => super::serialize(id, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<String>, D::Error> {
+            #[derive(Deserialize)]
+            struct Wrapped(#[serde(deserialize_with = "super::deserialize")] String);
+
+            Ok(Option::<Wrapped>::deserialize(deserializer)?.map(|Wrapped(id)| id))
+        }
+    }
+}
+
+/// Reads an unsigned integer sent either as a JSON number or as its decimal string, the two
+/// forms snowflakes and bitsets arrive in. Use with `Deserializer::deserialize_any`.
+pub struct U64Visitor;
+
+impl serde::de::Visitor<'_> for U64Visitor {
+    type Value = u64;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("an unsigned integer or its decimal string")
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<u64, E> {
+        Ok(value)
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<u64, E> {
+        u64::try_from(value).map_err(|_| E::custom(format!("invalid unsigned integer: {value}")))
+    }
+
+    fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<u64, E> {
+        value
+            .parse()
+            .map_err(|_| E::custom(format!("invalid unsigned integer: {value}")))
+    }
+}
+
+struct SnowflakeVisitor;
+
+impl serde::de::Visitor<'_> for SnowflakeVisitor {
+    type Value = String;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("a snowflake as a string or an unsigned integer")
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<String, E> {
+        U64Visitor.visit_u64(value).map(|id| id.to_string())
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<String, E> {
+        U64Visitor.visit_i64(value).map(|id| id.to_string())
+    }
+
+    fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<String, E> {
+        U64Visitor.visit_str(value).map(|id| id.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct AsString {
+        #[serde(with = "super::snowflake_string")]
+        id: String,
+        #[serde(default, with = "super::snowflake_string::opt")]
+        parent_id: Option<String>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct AsInt {
+        #[serde(with = "super::snowflake_int")]
+        id: String,
+        #[serde(default, with = "super::snowflake_int::opt")]
+        parent_id: Option<String>,
+    }
+
+    #[test]
+    fn snowflake_string_reads_both_forms_and_writes_a_string() {
+        for input in [
+            json!({ "id": "123456789012345678", "parent_id": 42 }),
+            json!({ "id": 123456789012345678u64, "parent_id": "42" }),
+        ] {
+            let value: AsString = serde_json::from_value(input).unwrap();
+            assert_eq!(value.id, "123456789012345678");
+            assert_eq!(value.parent_id.as_deref(), Some("42"));
+            assert_eq!(
+                serde_json::to_value(&value).unwrap(),
+                json!({ "id": "123456789012345678", "parent_id": "42" })
+            );
+        }
+    }
+
+    #[test]
+    fn snowflake_int_reads_both_forms_and_writes_a_number() {
+        for input in [
+            json!({ "id": "123456789012345678", "parent_id": 42 }),
+            json!({ "id": 123456789012345678u64, "parent_id": "42" }),
+        ] {
+            let value: AsInt = serde_json::from_value(input).unwrap();
+            assert_eq!(value.id, "123456789012345678");
+            assert_eq!(
+                serde_json::to_value(&value).unwrap(),
+                json!({ "id": 123456789012345678u64, "parent_id": 42 })
+            );
+        }
+    }
+
+    #[test]
+    fn optional_snowflakes_accept_null_and_absence() {
+        let value: AsInt = serde_json::from_value(json!({ "id": 1, "parent_id": null })).unwrap();
+        assert_eq!(value.parent_id, None);
+        let value: AsString = serde_json::from_value(json!({ "id": "1" })).unwrap();
+        assert_eq!(value.parent_id, None);
+        assert_eq!(
+            serde_json::to_value(&value).unwrap(),
+            json!({ "id": "1", "parent_id": null })
+        );
+    }
+
+    #[test]
+    fn snowflakes_reject_non_numeric_input() {
+        assert!(serde_json::from_value::<AsString>(json!({ "id": "abc" })).is_err());
+        assert!(serde_json::from_value::<AsInt>(json!({ "id": -5 })).is_err());
+    }
+}
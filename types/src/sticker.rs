@@ -26,3 +26,24 @@ pub struct ApiStickerWithUser {
     #[serde(default)]
     pub user: Option<ApiUser>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiStickerPack {
+    pub id: Snowflake,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub stickers: Vec<ApiSticker>,
+    #[serde(default)]
+    pub sku_id: Option<Snowflake>,
+    #[serde(default)]
+    pub cover_sticker_id: Option<Snowflake>,
+    #[serde(default)]
+    pub banner_asset_id: Option<Snowflake>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiStickerPackList {
+    pub sticker_packs: Vec<ApiStickerPack>,
+}
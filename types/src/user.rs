@@ -1,6 +1,26 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
 
-use crate::Snowflake;
+use crate::enums::code_enum;
+use crate::{Patch, QueryValues, Snowflake};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize_repr, Deserialize_repr)]
+#[repr(u8)]
+pub enum PremiumType {
+    #[default]
+    None = 0,
+    Classic = 1,
+    Nitro = 2,
+    Basic = 3,
+}
+
+code_enum!(PremiumType: u8 {
+    None => "none",
+    Classic => "classic",
+    Nitro => "nitro",
+    Basic => "basic",
+});
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiUser {
@@ -23,6 +43,8 @@ pub struct ApiUser {
     pub system: Option<bool>,
     #[serde(default)]
     pub banner: Option<String>,
+    #[serde(default)]
+    pub premium_type: Option<PremiumType>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -79,15 +101,89 @@ pub struct ApiGuildMember {
     #[serde(default)]
     pub accent_color: Option<u32>,
     pub roles: Vec<Snowflake>,
-    pub joined_at: String,
+    #[serde(default, with = "crate::ser::iso8601::lenient")]
+    pub joined_at: Option<DateTime<Utc>>,
     #[serde(default)]
     pub mute: Option<bool>,
     #[serde(default)]
     pub deaf: Option<bool>,
-    #[serde(default)]
-    pub communication_disabled_until: Option<String>,
+    #[serde(default, with = "crate::ser::iso8601::lenient")]
+    pub communication_disabled_until: Option<DateTime<Utc>>,
     #[serde(default)]
     pub profile_flags: Option<u32>,
-    #[serde(default)]
-    pub premium_since: Option<String>,
+    #[serde(default, with = "crate::ser::iso8601::lenient")]
+    pub premium_since: Option<DateTime<Utc>>,
+}
+
+impl ApiGuildMember {
+    pub fn is_timed_out(&self) -> bool {
+        self.communication_disabled_until
+            .is_some_and(|until| until > Utc::now())
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EditMemberRequest {
+    /// `Patch::Null` resets the nickname.
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub nick: Patch<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub roles: Option<Vec<Snowflake>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mute: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deaf: Option<bool>,
+    /// Moves the member to another voice channel; `Patch::Null` disconnects them.
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub channel_id: Patch<Snowflake>,
+    /// `Patch::Null` lifts an active timeout.
+    #[serde(default, skip_serializing_if = "Patch::is_absent")]
+    pub communication_disabled_until: Patch<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UserGuildsQuery {
+    pub before: Option<Snowflake>,
+    pub after: Option<Snowflake>,
+    pub limit: Option<u32>,
+    pub with_counts: bool,
+}
+
+impl UserGuildsQuery {
+    pub fn to_query_string(&self) -> String {
+        let mut query = QueryValues::new();
+        query
+            .insert_opt("before", self.before.as_ref())
+            .insert_opt("after", self.after.as_ref())
+            .insert_opt("limit", self.limit);
+        if self.with_counts {
+            query.insert("with_counts", true);
+        }
+        query.to_query_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn user(premium_type: serde_json::Value) -> ApiUser {
+        serde_json::from_value(json!({
+            "id": "1",
+            "username": "u",
+            "discriminator": "0001",
+            "premium_type": premium_type,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn decodes_premium_type() {
+        assert_eq!(user(json!(2)).premium_type, Some(PremiumType::Nitro));
+        assert_eq!(user(json!(0)).premium_type, Some(PremiumType::None));
+        assert_eq!(user(json!(null)).premium_type, None);
+        assert!(serde_json::from_value::<PremiumType>(json!(9)).is_err());
+    }
 }
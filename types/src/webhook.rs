@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::Snowflake;
+use crate::embed::ApiEmbed;
 use crate::user::ApiUser;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,6 +16,14 @@ pub struct ApiWebhook {
     pub user: ApiUser,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WebhookCreateRequest {
+    pub name: String,
+    /// Avatar as a `data:` URI.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar: Option<String>,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct WebhookUpdateRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -32,3 +41,24 @@ pub struct WebhookTokenUpdateRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub avatar: Option<String>,
 }
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExecuteWebhookRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tts: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embeds: Option<Vec<ApiEmbed>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flags: Option<u32>,
+    /// Creates a new thread with this name when executing against a forum channel.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub applied_tags: Option<Vec<Snowflake>>,
+}
@@ -0,0 +1,322 @@
+use bitflags::bitflags;
+
+/// Implements serde for bitflags types: serialized as a decimal string (or as a JSON number
+/// with the `number` prefix), deserialized from a string or an integer, since instances differ
+/// in how they send flags. Unknown bits are dropped.
+macro_rules! serde_bits {
+    (number $($name:ident: $repr:ty),+ $(,)?) => {$(
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serde::Serialize::serialize(&self.bits(), serializer)
+            }
+        }
+
+        $crate::flags::serde_bits!(@deserialize $name: $repr);
+    )+};
+    (@deserialize $name:ident: $repr:ty) => {
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let bits = deserializer.deserialize_any(fluxer_types::ser::U64Visitor)?;
+                <$repr>::try_from(bits)
+                    .map(Self::from_bits_truncate)
+                    .map_err(serde::de::Error::custom)
+            }
+        }
+    };
+    ($($name:ident: $repr:ty),+ $(,)?) => {$(
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(&self.bits())
+            }
+        }
+
+        $crate::flags::serde_bits!(@deserialize $name: $repr);
+    )+};
+}
+
+pub(crate) use serde_bits;
+
+bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct MessageFlags: u32 {
+        const CROSSPOSTED            = 1 << 0;
+        const IS_CROSSPOST           = 1 << 1;
+        const SUPPRESS_EMBEDS        = 1 << 2;
+        const SOURCE_MESSAGE_DELETED = 1 << 3;
+        const URGENT                 = 1 << 4;
+        const HAS_THREAD             = 1 << 5;
+        const EPHEMERAL              = 1 << 6;
+        const LOADING                = 1 << 7;
+        const SUPPRESS_NOTIFICATIONS = 1 << 12;
+        const IS_VOICE_MESSAGE       = 1 << 13;
+        const IS_COMPONENTS_V2       = 1 << 15;
+    }
+}
+
+impl MessageFlags {
+    /// Flags that may be set on an interaction response.
+    pub const INTERACTION_RESPONSE: MessageFlags = MessageFlags::EPHEMERAL
+        .union(MessageFlags::SUPPRESS_EMBEDS)
+        .union(MessageFlags::SUPPRESS_NOTIFICATIONS)
+        .union(MessageFlags::IS_COMPONENTS_V2);
+
+    pub const fn ephemeral() -> Self {
+        Self::EPHEMERAL
+    }
+
+    pub const fn suppress_embeds() -> Self {
+        Self::SUPPRESS_EMBEDS
+    }
+
+    /// Delivers the message without push or desktop notifications.
+    pub const fn silent() -> Self {
+        Self::SUPPRESS_NOTIFICATIONS
+    }
+
+    /// `self` with `other` set, e.g. `MessageFlags::ephemeral().with(MessageFlags::SUPPRESS_EMBEDS)`.
+    pub const fn with(self, other: Self) -> Self {
+        self.union(other)
+    }
+
+    pub const fn without(self, other: Self) -> Self {
+        self.difference(other)
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct UserFlags: u64 {
+        const STAFF                    = 1 << 0;
+        const PARTNER                  = 1 << 1;
+        const HYPESQUAD                = 1 << 2;
+        const BUG_HUNTER_LEVEL_1       = 1 << 3;
+        const HYPESQUAD_ONLINE_HOUSE_1 = 1 << 6;
+        const HYPESQUAD_ONLINE_HOUSE_2 = 1 << 7;
+        const HYPESQUAD_ONLINE_HOUSE_3 = 1 << 8;
+        const PREMIUM_EARLY_SUPPORTER  = 1 << 9;
+        const TEAM_PSEUDO_USER         = 1 << 10;
+        const BUG_HUNTER_LEVEL_2       = 1 << 14;
+        const VERIFIED_BOT             = 1 << 16;
+        const VERIFIED_DEVELOPER       = 1 << 17;
+        const CERTIFIED_MODERATOR      = 1 << 18;
+        const BOT_HTTP_INTERACTIONS    = 1 << 19;
+        const ACTIVE_DEVELOPER         = 1 << 22;
+    }
+}
+
+/// A profile badge derived from [`UserFlags`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Badge {
+    Staff,
+    Partner,
+    Hypesquad,
+    BugHunterLevel1,
+    HypesquadBravery,
+    HypesquadBrilliance,
+    HypesquadBalance,
+    EarlySupporter,
+    BugHunterLevel2,
+    VerifiedDeveloper,
+    CertifiedModerator,
+    ActiveDeveloper,
+}
+
+const BADGES: [(UserFlags, Badge); 12] = [
+    (UserFlags::STAFF, Badge::Staff),
+    (UserFlags::PARTNER, Badge::Partner),
+    (UserFlags::HYPESQUAD, Badge::Hypesquad),
+    (UserFlags::BUG_HUNTER_LEVEL_1, Badge::BugHunterLevel1),
+    (UserFlags::HYPESQUAD_ONLINE_HOUSE_1, Badge::HypesquadBravery),
+    (
+        UserFlags::HYPESQUAD_ONLINE_HOUSE_2,
+        Badge::HypesquadBrilliance,
+    ),
+    (UserFlags::HYPESQUAD_ONLINE_HOUSE_3, Badge::HypesquadBalance),
+    (UserFlags::PREMIUM_EARLY_SUPPORTER, Badge::EarlySupporter),
+    (UserFlags::BUG_HUNTER_LEVEL_2, Badge::BugHunterLevel2),
+    (UserFlags::VERIFIED_DEVELOPER, Badge::VerifiedDeveloper),
+    (UserFlags::CERTIFIED_MODERATOR, Badge::CertifiedModerator),
+    (UserFlags::ACTIVE_DEVELOPER, Badge::ActiveDeveloper),
+];
+
+impl UserFlags {
+    /// Badges shown on a profile, in display order.
+    pub fn badges(self) -> Vec<Badge> {
+        BADGES
+            .iter()
+            .filter(|(flag, _)| self.contains(*flag))
+            .map(|&(_, badge)| badge)
+            .collect()
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ApplicationFlags: u64 {
+        const AUTO_MODERATION_RULE_CREATE_BADGE = 1 << 6;
+        const GATEWAY_PRESENCE                  = 1 << 12;
+        const GATEWAY_PRESENCE_LIMITED          = 1 << 13;
+        const GATEWAY_GUILD_MEMBERS             = 1 << 14;
+        const GATEWAY_GUILD_MEMBERS_LIMITED     = 1 << 15;
+        const VERIFICATION_PENDING_GUILD_LIMIT  = 1 << 16;
+        const EMBEDDED                          = 1 << 17;
+        const GATEWAY_MESSAGE_CONTENT           = 1 << 18;
+        const GATEWAY_MESSAGE_CONTENT_LIMITED   = 1 << 19;
+        const APPLICATION_COMMAND_BADGE         = 1 << 23;
+    }
+}
+
+impl ApplicationFlags {
+    /// Whether the application may receive message content, fully or under the limited grant.
+    pub fn has_message_content(self) -> bool {
+        self.intersects(Self::GATEWAY_MESSAGE_CONTENT | Self::GATEWAY_MESSAGE_CONTENT_LIMITED)
+    }
+
+    pub fn has_guild_members(self) -> bool {
+        self.intersects(Self::GATEWAY_GUILD_MEMBERS | Self::GATEWAY_GUILD_MEMBERS_LIMITED)
+    }
+
+    pub fn has_presence(self) -> bool {
+        self.intersects(Self::GATEWAY_PRESENCE | Self::GATEWAY_PRESENCE_LIMITED)
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    pub struct GatewayIntents: u64 {
+        const GUILDS                        = 1 << 0;
+        const GUILD_MEMBERS                 = 1 << 1;
+        const GUILD_MODERATION              = 1 << 2;
+        const GUILD_EMOJIS_AND_STICKERS     = 1 << 3;
+        const GUILD_INTEGRATIONS            = 1 << 4;
+        const GUILD_WEBHOOKS                = 1 << 5;
+        const GUILD_INVITES                 = 1 << 6;
+        const GUILD_VOICE_STATES            = 1 << 7;
+        const GUILD_PRESENCES               = 1 << 8;
+        const GUILD_MESSAGES                = 1 << 9;
+        const GUILD_MESSAGE_REACTIONS       = 1 << 10;
+        const GUILD_MESSAGE_TYPING          = 1 << 11;
+        const DIRECT_MESSAGES               = 1 << 12;
+        const DIRECT_MESSAGE_REACTIONS      = 1 << 13;
+        const DIRECT_MESSAGE_TYPING         = 1 << 14;
+        const MESSAGE_CONTENT               = 1 << 15;
+        const GUILD_SCHEDULED_EVENTS        = 1 << 16;
+    }
+}
+
+impl GatewayIntents {
+    /// Intents that must be enabled for the application before it may identify with them.
+    pub const PRIVILEGED: GatewayIntents = GatewayIntents::GUILD_MEMBERS
+        .union(GatewayIntents::GUILD_PRESENCES)
+        .union(GatewayIntents::MESSAGE_CONTENT);
+
+    /// The privileged intents among `self`.
+    pub const fn privileged(self) -> Self {
+        self.intersection(Self::PRIVILEGED)
+    }
+
+    /// Privileged intents in `self` that are not in `allowed`.
+    pub const fn disallowed(self, allowed: Self) -> Self {
+        self.privileged().difference(allowed)
+    }
+}
+
+impl ApplicationFlags {
+    /// The privileged intents these flags grant.
+    pub fn privileged_intents(self) -> GatewayIntents {
+        let mut intents = GatewayIntents::empty();
+        intents.set(GatewayIntents::GUILD_MEMBERS, self.has_guild_members());
+        intents.set(GatewayIntents::GUILD_PRESENCES, self.has_presence());
+        intents.set(GatewayIntents::MESSAGE_CONTENT, self.has_message_content());
+        intents
+    }
+}
+
+serde_bits!(UserFlags: u64, ApplicationFlags: u64);
+
+// Message flags and intents are plain integers on the wire.
+serde_bits!(number MessageFlags: u32, GatewayIntents: u64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Permissions;
+
+    #[test]
+    fn permissions_decode_from_string_and_integer() {
+        let expected = Permissions::ADMINISTRATOR | Permissions::SEND_MESSAGES;
+        let bits = expected.bits();
+
+        let from_str: Permissions = serde_json::from_str(&format!("\"{bits}\"")).unwrap();
+        let from_int: Permissions = serde_json::from_str(&bits.to_string()).unwrap();
+
+        assert_eq!(from_str, expected);
+        assert_eq!(from_int, expected);
+        assert_eq!(
+            serde_json::to_string(&expected).unwrap(),
+            format!("\"{bits}\"")
+        );
+    }
+
+    #[test]
+    fn message_flags_decode_from_string_and_integer() {
+        let expected = MessageFlags::EPHEMERAL | MessageFlags::SUPPRESS_EMBEDS;
+
+        let from_str: MessageFlags = serde_json::from_str("\"68\"").unwrap();
+        let from_int: MessageFlags = serde_json::from_str("68").unwrap();
+
+        assert_eq!(from_str, expected);
+        assert_eq!(from_int, expected);
+    }
+
+    #[test]
+    fn flags_drop_unknown_bits_and_reject_garbage() {
+        let flags: MessageFlags = serde_json::from_str(&(1u64 << 30 | 64).to_string()).unwrap();
+        assert_eq!(flags, MessageFlags::EPHEMERAL);
+
+        assert!(serde_json::from_str::<MessageFlags>("\"ephemeral\"").is_err());
+        assert!(serde_json::from_str::<MessageFlags>("-1").is_err());
+        assert!(serde_json::from_str::<MessageFlags>(&u64::MAX.to_string()).is_err());
+    }
+
+    #[test]
+    fn intents_serialize_as_a_number() {
+        let intents = GatewayIntents::GUILDS | GatewayIntents::GUILD_MESSAGES;
+        assert_eq!(serde_json::to_string(&intents).unwrap(), "513");
+
+        let round_trip: GatewayIntents = serde_json::from_str("513").unwrap();
+        assert_eq!(round_trip, intents);
+    }
+
+    #[test]
+    fn derives_badges_in_display_order() {
+        let flags = UserFlags::ACTIVE_DEVELOPER
+            | UserFlags::STAFF
+            | UserFlags::HYPESQUAD_ONLINE_HOUSE_2
+            | UserFlags::BOT_HTTP_INTERACTIONS;
+        assert_eq!(
+            flags.badges(),
+            [
+                Badge::Staff,
+                Badge::HypesquadBrilliance,
+                Badge::ActiveDeveloper
+            ]
+        );
+        assert!(UserFlags::empty().badges().is_empty());
+    }
+
+    #[test]
+    fn message_flags_round_trip_as_an_integer() {
+        let flags = MessageFlags::ephemeral()
+            .with(MessageFlags::SUPPRESS_EMBEDS)
+            .with(MessageFlags::silent())
+            .with(MessageFlags::IS_COMPONENTS_V2)
+            .without(MessageFlags::SUPPRESS_EMBEDS);
+        assert_eq!(flags, MessageFlags::from_bits_retain(64 | 4096 | 32768));
+
+        let json = serde_json::to_string(&flags).unwrap();
+        assert_eq!(json, "36928");
+        assert_eq!(serde_json::from_str::<MessageFlags>(&json).unwrap(), flags);
+        assert!(MessageFlags::INTERACTION_RESPONSE.contains(flags));
+    }
+}
@@ -1,4 +1,6 @@
-﻿/// Truncates `s` to at most `max_len` chars, appending `…` if truncated.
+﻿use chrono::{DateTime, Utc};
+
+/// Truncates `s` to at most `max_len` chars, appending `…` if truncated.
 pub fn truncate(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
         return s.to_string();
@@ -32,3 +34,70 @@ pub fn format_timestamp(unix_secs: u64, style: Option<char>) -> String {
         None => format!("<t:{unix_secs}>"),
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampStyle {
+    ShortTime,
+    LongTime,
+    ShortDate,
+    LongDate,
+    ShortDateTime,
+    LongDateTime,
+    Relative,
+}
+
+impl TimestampStyle {
+    pub fn as_char(self) -> char {
+        match self {
+            TimestampStyle::ShortTime => 't',
+            TimestampStyle::LongTime => 'T',
+            TimestampStyle::ShortDate => 'd',
+            TimestampStyle::LongDate => 'D',
+            TimestampStyle::ShortDateTime => 'f',
+            TimestampStyle::LongDateTime => 'F',
+            TimestampStyle::Relative => 'R',
+        }
+    }
+
+    pub fn from_char(c: char) -> Option<Self> {
+        Some(match c {
+            't' => TimestampStyle::ShortTime,
+            'T' => TimestampStyle::LongTime,
+            'd' => TimestampStyle::ShortDate,
+            'D' => TimestampStyle::LongDate,
+            'f' => TimestampStyle::ShortDateTime,
+            'F' => TimestampStyle::LongDateTime,
+            'R' => TimestampStyle::Relative,
+            _ => return None,
+        })
+    }
+}
+
+/// Formats a `<t:unix:style>` tag for a point in time.
+pub fn timestamp(at: DateTime<Utc>, style: Option<TimestampStyle>) -> String {
+    format_timestamp(
+        at.timestamp().max(0) as u64,
+        style.map(TimestampStyle::as_char),
+    )
+}
+
+pub fn user_mention(id: &str) -> String {
+    format!("<@{id}>")
+}
+
+pub fn channel_mention(id: &str) -> String {
+    format!("<#{id}>")
+}
+
+pub fn role_mention(id: &str) -> String {
+    format!("<@&{id}>")
+}
+
+pub fn custom_emoji(name: &str, id: &str, animated: bool) -> String {
+    crate::resolvers::format_emoji(name, id, animated)
+}
+
+/// Formats a clickable `</name:id>` slash command mention; `name` may include subcommands.
+pub fn slash_command(name: &str, id: &str) -> String {
+    format!("</{name}:{id}>")
+}
@@ -0,0 +1,130 @@
+use std::fmt;
+use std::path::Path;
+
+use base64::Engine;
+
+/// Largest image accepted for avatars, icons and similar uploads.
+pub const MAX_IMAGE_BYTES: usize = 10 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+}
+
+impl ImageFormat {
+    /// Detects the format from the file's magic bytes.
+    pub fn sniff(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(Self::Png)
+        } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            Some(Self::Jpeg)
+        } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+            Some(Self::Gif)
+        } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+            Some(Self::Webp)
+        } else {
+            None
+        }
+    }
+
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Self::Png => "image/png",
+            Self::Jpeg => "image/jpeg",
+            Self::Gif => "image/gif",
+            Self::Webp => "image/webp",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ImageError {
+    Io(std::io::Error),
+    Empty,
+    TooLarge { size: usize, max: usize },
+    UnknownFormat,
+    UnsupportedFormat(ImageFormat),
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "failed to read image: {e}"),
+            Self::Empty => f.write_str("image is empty"),
+            Self::TooLarge { size, max } => {
+                write!(f, "image is {size} bytes, the limit is {max}")
+            }
+            Self::UnknownFormat => f.write_str("not a PNG, JPEG, GIF or WebP image"),
+            Self::UnsupportedFormat(format) => {
+                write!(f, "{} is not accepted here", format.mime_type())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ImageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Validated image bytes, ready to be sent as a `data:` URI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageData {
+    format: ImageFormat,
+    bytes: Vec<u8>,
+}
+
+impl ImageData {
+    /// Accepts any format [`ImageFormat::sniff`] recognises, up to [`MAX_IMAGE_BYTES`].
+    pub fn from_bytes(bytes: impl Into<Vec<u8>>) -> Result<Self, ImageError> {
+        let bytes = bytes.into();
+        if bytes.is_empty() {
+            return Err(ImageError::Empty);
+        }
+        if bytes.len() > MAX_IMAGE_BYTES {
+            return Err(ImageError::TooLarge {
+                size: bytes.len(),
+                max: MAX_IMAGE_BYTES,
+            });
+        }
+        let format = ImageFormat::sniff(&bytes).ok_or(ImageError::UnknownFormat)?;
+        Ok(Self { format, bytes })
+    }
+
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, ImageError> {
+        let bytes = std::fs::read(path).map_err(ImageError::Io)?;
+        Self::from_bytes(bytes)
+    }
+
+    /// Fails unless the image is one of `allowed`.
+    pub fn require(self, allowed: &[ImageFormat]) -> Result<Self, ImageError> {
+        if allowed.contains(&self.format) {
+            Ok(self)
+        } else {
+            Err(ImageError::UnsupportedFormat(self.format))
+        }
+    }
+
+    pub fn format(&self) -> ImageFormat {
+        self.format
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn to_data_uri(&self) -> String {
+        format!(
+            "data:{};base64,{}",
+            self.format.mime_type(),
+            base64::engine::general_purpose::STANDARD.encode(&self.bytes)
+        )
+    }
+}
@@ -1,13 +1,17 @@
 pub mod emoji;
+pub mod flags;
 pub mod formatters;
+pub mod image;
 pub mod permissions;
 pub mod resolvers;
 pub mod snowflake;
 pub mod tenor;
 
 pub use emoji::*;
+pub use flags::*;
 pub use formatters::*;
+pub use image::{ImageData, ImageError, ImageFormat, MAX_IMAGE_BYTES};
 pub use permissions::*;
 pub use resolvers::*;
-pub use snowflake::SnowflakeUtil;
+pub use snowflake::{Nonce, SnowflakeUtil};
 pub use tenor::*;
@@ -52,3 +52,6 @@ pub fn parse_permissions(s: &str) -> Permissions {
 pub fn permissions_to_string(p: Permissions) -> String {
     p.bits().to_string()
 }
+
+// Serialized as a decimal string, matching how the API transports permission bitsets.
+crate::flags::serde_bits!(Permissions: u64);
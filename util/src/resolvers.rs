@@ -1,3 +1,5 @@
+use crate::formatters::TimestampStyle;
+
 pub fn resolve_color(input: &str) -> Option<u32> {
     let hex = input.trim().trim_start_matches('#');
     if hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
@@ -73,3 +75,74 @@ pub fn parse_prefix_command<'a>(content: &'a str, prefix: &str) -> Option<(&'a s
     }
     Some((cmd, args))
 }
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mention {
+    User(String),
+    Channel(String),
+    Role(String),
+    Emoji {
+        name: String,
+        id: String,
+        animated: bool,
+    },
+    Timestamp {
+        unix_secs: i64,
+        style: Option<TimestampStyle>,
+    },
+    SlashCommand {
+        name: String,
+        id: String,
+    },
+}
+
+/// Parses any single `<...>` mention form produced by the formatters.
+pub fn parse_mention(s: &str) -> Option<Mention> {
+    let s = s.trim();
+    if let Some(id) = parse_role_mention(s) {
+        return Some(Mention::Role(id));
+    }
+    if let Some(id) = parse_user_mention(s) {
+        return Some(Mention::User(id));
+    }
+    if let Some((name, id, animated)) = parse_emoji(s) {
+        return Some(Mention::Emoji { name, id, animated });
+    }
+
+    let inner = s.strip_prefix('<')?.strip_suffix('>')?;
+    if let Some(id) = inner.strip_prefix('#') {
+        return is_snowflake(id).then(|| Mention::Channel(id.to_string()));
+    }
+    if let Some(rest) = inner.strip_prefix("t:") {
+        let (secs, style) = match rest.split_once(':') {
+            Some((secs, style)) => {
+                let mut chars = style.chars();
+                let c = chars.next()?;
+                if chars.next().is_some() {
+                    return None;
+                }
+                (secs, Some(TimestampStyle::from_char(c)?))
+            }
+            None => (rest, None),
+        };
+        return Some(Mention::Timestamp {
+            unix_secs: secs.parse().ok()?,
+            style,
+        });
+    }
+    if let Some(rest) = inner.strip_prefix('/') {
+        let (name, id) = rest.rsplit_once(':')?;
+        if name.is_empty() || !is_snowflake(id) {
+            return None;
+        }
+        return Some(Mention::SlashCommand {
+            name: name.to_string(),
+            id: id.to_string(),
+        });
+    }
+    None
+}
+
+fn is_snowflake(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_digit())
+}
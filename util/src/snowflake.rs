@@ -1,3 +1,4 @@
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 const FLUXER_EPOCH: u64 = 1_420_070_400_000;
@@ -26,3 +27,64 @@ impl SnowflakeUtil {
         id.parse::<u64>().is_ok() && !id.is_empty()
     }
 }
+
+static LAST_NONCE: AtomicU64 = AtomicU64::new(0);
+
+pub struct Nonce;
+
+impl Nonce {
+    /// Generates a snowflake-shaped nonce that is unique and increasing within this process.
+    pub fn generate() -> String {
+        let now_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        let candidate = now_ms.saturating_sub(FLUXER_EPOCH) << 22;
+        let mut last = LAST_NONCE.load(Ordering::Relaxed);
+        loop {
+            let next = candidate.max(last + 1);
+            match LAST_NONCE.compare_exchange_weak(last, next, Ordering::Relaxed, Ordering::Relaxed)
+            {
+                Ok(_) => return next.to_string(),
+                Err(actual) => last = actual,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nonces_are_unique_and_increasing() {
+        let nonces: Vec<u64> = (0..1_000)
+            .map(|_| Nonce::generate().parse().unwrap())
+            .collect();
+        assert!(nonces.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn nonces_are_unique_across_threads() {
+        let handles: Vec<_> = (0..4)
+            .map(|_| std::thread::spawn(|| (0..250).map(|_| Nonce::generate()).collect::<Vec<_>>()))
+            .collect();
+        let mut all: Vec<String> = handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect();
+        let total = all.len();
+        all.sort();
+        all.dedup();
+        assert_eq!(all.len(), total);
+    }
+
+    #[test]
+    fn nonces_carry_the_current_time() {
+        let before = SystemTime::now() - Duration::from_secs(1);
+        let nonce = Nonce::generate();
+        let date = SnowflakeUtil::date_from_snowflake(&nonce).unwrap();
+        assert!(date >= before);
+        assert!(SnowflakeUtil::is_valid(&nonce));
+    }
+}
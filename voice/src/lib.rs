@@ -5,4 +5,4 @@ pub mod pcm;
 
 pub use connection::FluxerVoiceConnection;
 pub use error::VoiceError;
-pub use manager::VoiceManager;
+pub use manager::{SelfVoiceState, VoiceManager};
@@ -8,10 +8,19 @@ use crate::error::VoiceError;
 
 pub type GatewaySender = Arc<dyn Fn(Value) + Send + Sync>;
 
+/// The bot's own mute, deafen and video flags in a guild, sent with every voice state update.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SelfVoiceState {
+    pub self_mute: bool,
+    pub self_deaf: bool,
+    pub self_video: bool,
+}
+
 pub struct VoiceManager {
     active_connections: Arc<DashMap<String, Arc<FluxerVoiceConnection>>>,
     pending_connections: Arc<DashMap<String, Arc<Mutex<Option<Value>>>>>,
     gateway_sender: Arc<RwLock<Option<GatewaySender>>>,
+    self_states: Arc<DashMap<String, SelfVoiceState>>,
 }
 
 impl Default for VoiceManager {
@@ -26,6 +35,7 @@ impl VoiceManager {
             active_connections: Arc::new(DashMap::new()),
             pending_connections: Arc::new(DashMap::new()),
             gateway_sender: Arc::new(RwLock::new(None)),
+            self_states: Arc::new(DashMap::new()),
         }
     }
 
@@ -59,15 +69,7 @@ impl VoiceManager {
         self.pending_connections
             .insert(guild_id.to_string(), slot.clone());
 
-        let payload = serde_json::json!({
-            "op": 4,
-            "d": {
-                "guild_id": guild_id,
-                "channel_id": channel_id,
-                "self_mute": false,
-                "self_deaf": false
-            }
-        });
+        let payload = voice_state_payload(guild_id, Some(channel_id), self.self_state(guild_id));
 
         tracing::info!(
             "Sending opcode 4: guild_id={} channel_id={}",
@@ -135,17 +137,13 @@ impl VoiceManager {
             conn.disconnect().await?;
 
             let sender_opt = self.gateway_sender.read().await.clone();
+            self.self_states.remove(&conn.guild_id);
             if let Some(sender) = sender_opt {
-                let payload = serde_json::json!({
-                    "op": 4,
-                    "d": {
-                        "guild_id": conn.guild_id.clone(),
-                        "channel_id": serde_json::Value::Null,
-                        "self_mute": false,
-                        "self_deaf": false
-                    }
-                });
-                sender(payload);
+                sender(voice_state_payload(
+                    &conn.guild_id,
+                    None,
+                    SelfVoiceState::default(),
+                ));
             }
         }
         Ok(())
@@ -183,6 +181,56 @@ impl VoiceManager {
         }
     }
 
+    /// Flags sent with the next voice state update in `guild_id`; all `false` by default.
+    pub fn self_state(&self, guild_id: &str) -> SelfVoiceState {
+        self.self_states
+            .get(guild_id)
+            .map(|s| *s)
+            .unwrap_or_default()
+    }
+
+    pub async fn set_self_mute(&self, guild_id: &str, mute: bool) -> Result<(), VoiceError> {
+        self.update_self_state(guild_id, |s| s.self_mute = mute)
+            .await
+    }
+
+    pub async fn set_self_deaf(&self, guild_id: &str, deaf: bool) -> Result<(), VoiceError> {
+        self.update_self_state(guild_id, |s| s.self_deaf = deaf)
+            .await
+    }
+
+    pub async fn set_self_video(&self, guild_id: &str, video: bool) -> Result<(), VoiceError> {
+        self.update_self_state(guild_id, |s| s.self_video = video)
+            .await
+    }
+
+    /// Re-sends op 4 for the channel the bot is connected to in `guild_id` with updated flags,
+    /// without leaving the channel.
+    async fn update_self_state(
+        &self,
+        guild_id: &str,
+        update: impl FnOnce(&mut SelfVoiceState),
+    ) -> Result<(), VoiceError> {
+        let channel_id = self
+            .active_connections
+            .iter()
+            .find(|kv| kv.value().guild_id == guild_id)
+            .map(|kv| kv.value().channel_id.clone())
+            .ok_or(VoiceError::NotConnected)?;
+        let sender = self
+            .gateway_sender
+            .read()
+            .await
+            .clone()
+            .ok_or(VoiceError::GatewayUnavailable)?;
+
+        let mut state = self.self_state(guild_id);
+        update(&mut state);
+        self.self_states.insert(guild_id.to_string(), state);
+        sender(voice_state_payload(guild_id, Some(&channel_id), state));
+        Ok(())
+    }
+
     pub fn get_connection(&self, channel_id: &str) -> Option<Arc<FluxerVoiceConnection>> {
         self.active_connections.get(channel_id).map(|c| c.clone())
     }
@@ -217,3 +265,16 @@ impl VoiceManager {
         }
     }
 }
+
+fn voice_state_payload(guild_id: &str, channel_id: Option<&str>, state: SelfVoiceState) -> Value {
+    serde_json::json!({
+        "op": 4,
+        "d": {
+            "guild_id": guild_id,
+            "channel_id": channel_id,
+            "self_mute": state.self_mute,
+            "self_deaf": state.self_deaf,
+            "self_video": state.self_video
+        }
+    })
+}
@@ -0,0 +1,66 @@
+/// Gateway-specific close codes (4000-4014).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum GatewayCloseCode {
+    UnknownError = 4000,
+    UnknownOpcode = 4001,
+    DecodeError = 4002,
+    NotAuthenticated = 4003,
+    AuthenticationFailed = 4004,
+    AlreadyAuthenticated = 4005,
+    InvalidSeq = 4007,
+    RateLimited = 4008,
+    SessionTimedOut = 4009,
+    InvalidShard = 4010,
+    ShardingRequired = 4011,
+    InvalidApiVersion = 4012,
+    InvalidIntents = 4013,
+    DisallowedIntents = 4014,
+}
+
+/// Close code sent when IDENTIFY asks for privileged intents the application doesn't have.
+pub const DISALLOWED_INTENTS_CLOSE_CODE: u16 = GatewayCloseCode::DisallowedIntents as u16;
+
+impl GatewayCloseCode {
+    pub fn from_code(code: u16) -> Option<Self> {
+        Some(match code {
+            4000 => Self::UnknownError,
+            4001 => Self::UnknownOpcode,
+            4002 => Self::DecodeError,
+            4003 => Self::NotAuthenticated,
+            4004 => Self::AuthenticationFailed,
+            4005 => Self::AlreadyAuthenticated,
+            4007 => Self::InvalidSeq,
+            4008 => Self::RateLimited,
+            4009 => Self::SessionTimedOut,
+            4010 => Self::InvalidShard,
+            4011 => Self::ShardingRequired,
+            4012 => Self::InvalidApiVersion,
+            4013 => Self::InvalidIntents,
+            4014 => Self::DisallowedIntents,
+            _ => return None,
+        })
+    }
+
+    pub fn code(self) -> u16 {
+        self as u16
+    }
+
+    /// Whether reconnecting can't help: the token, shard, version or intents must be fixed first.
+    pub fn is_fatal(self) -> bool {
+        matches!(
+            self,
+            Self::AuthenticationFailed
+                | Self::InvalidShard
+                | Self::ShardingRequired
+                | Self::InvalidApiVersion
+                | Self::InvalidIntents
+                | Self::DisallowedIntents
+        )
+    }
+}
+
+/// Whether a shard closed with `code` stops instead of reconnecting.
+pub fn is_fatal_close_code(code: u16) -> bool {
+    GatewayCloseCode::from_code(code).is_some_and(GatewayCloseCode::is_fatal)
+}
@@ -0,0 +1,67 @@
+use std::io::Read;
+
+use flate2::read::ZlibDecoder;
+
+/// How gateway payloads are compressed on the wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CompressionMode {
+    /// Plain JSON text frames.
+    #[default]
+    None,
+    /// Requested with `compress: true` in IDENTIFY; payloads may arrive as binary frames, each
+    /// a complete zlib stream on its own.
+    ZlibPayload,
+}
+
+impl CompressionMode {
+    /// The `compress` flag to send in IDENTIFY.
+    pub fn identify_compress(self) -> Option<bool> {
+        match self {
+            Self::None => None,
+            Self::ZlibPayload => Some(true),
+        }
+    }
+}
+
+/// Inflates a single-shot zlib-compressed frame into its JSON text.
+pub fn inflate_payload(frame: &[u8]) -> std::io::Result<String> {
+    let mut text = String::new();
+    ZlibDecoder::new(frame).read_to_string(&mut text)?;
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::Compression;
+    use flate2::write::ZlibEncoder;
+
+    use super::*;
+
+    #[test]
+    fn inflates_a_compressed_frame() {
+        let payload = r#"{"op":0,"t":"READY","s":1,"d":{"session_id":"abc"}}"#;
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(payload.as_bytes()).unwrap();
+        let frame = encoder.finish().unwrap();
+
+        assert_eq!(inflate_payload(&frame).unwrap(), payload);
+    }
+
+    #[test]
+    fn rejects_a_truncated_frame() {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(br#"{"op":11}"#).unwrap();
+        let frame = encoder.finish().unwrap();
+
+        assert!(inflate_payload(&frame[..frame.len() / 2]).is_err());
+        assert!(inflate_payload(b"not zlib").is_err());
+    }
+
+    #[test]
+    fn only_payload_compression_sets_the_identify_flag() {
+        assert_eq!(CompressionMode::None.identify_compress(), None);
+        assert_eq!(CompressionMode::ZlibPayload.identify_compress(), Some(true));
+    }
+}
@@ -0,0 +1,9 @@
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum GatewayError {
+    #[error("invalid shard identity [{id}, {total}]: id must be less than total")]
+    InvalidShard { id: u32, total: u32 },
+
+    #[error("REST error: {0}")]
+    Rest(#[from] fluxer_rest::RestError),
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use fluxer_types::gateway::GatewayReceivePayload;
 use serde_json::Value;
 
@@ -6,7 +8,22 @@ pub enum ShardEvent {
     Ready(Value),
     Resumed,
     Dispatch(GatewayReceivePayload),
-    Close(u16),
+    /// The socket closed with `code` and the server's stated `reason` (empty when none was sent).
+    Close {
+        code: u16,
+        reason: String,
+    },
+    /// Op 9; the shard follows up with a RESUME when `resumable`, otherwise it discards the
+    /// stored session and sends a fresh IDENTIFY.
+    InvalidSession {
+        resumable: bool,
+    },
+    /// Op 7; the gateway asked the shard to reconnect and resume.
+    Reconnect,
+    /// The shard waited on its identify concurrency bucket before connecting.
+    IdentifyQueued {
+        waited: Duration,
+    },
     Error(String),
     Debug(String),
 }
@@ -27,6 +44,18 @@ pub enum WsEvent {
     ShardClose {
         shard_id: u32,
         code: u16,
+        reason: String,
+    },
+    ShardInvalidSession {
+        shard_id: u32,
+        resumable: bool,
+    },
+    ShardReconnect {
+        shard_id: u32,
+    },
+    ShardIdentifyQueued {
+        shard_id: u32,
+        waited: Duration,
     },
     Error {
         shard_id: u32,
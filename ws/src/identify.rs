@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+use tokio::time::{Instant, sleep_until};
+
+/// Minimum spacing between IDENTIFY payloads sent from the same concurrency bucket.
+pub const IDENTIFY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Serializes IDENTIFYs per `shard_id % max_concurrency` bucket, as required by the gateway's
+/// session start limit.
+#[derive(Debug)]
+pub struct IdentifyLimiter {
+    buckets: Vec<Mutex<Option<Instant>>>,
+}
+
+impl IdentifyLimiter {
+    pub fn new(max_concurrency: u32) -> Self {
+        let buckets = (0..max_concurrency.max(1))
+            .map(|_| Mutex::new(None))
+            .collect();
+        Self { buckets }
+    }
+
+    pub fn max_concurrency(&self) -> u32 {
+        self.buckets.len() as u32
+    }
+
+    pub fn bucket_for(&self, shard_id: u32) -> u32 {
+        shard_id % self.max_concurrency()
+    }
+
+    /// Waits until `shard_id`'s bucket may identify again and reserves the slot.
+    ///
+    /// Returns how long the caller was queued.
+    pub async fn acquire(&self, shard_id: u32) -> Duration {
+        let started = Instant::now();
+        let mut next_allowed = self.buckets[self.bucket_for(shard_id) as usize]
+            .lock()
+            .await;
+        if let Some(at) = *next_allowed {
+            sleep_until(at).await;
+        }
+        *next_allowed = Some(Instant::now() + IDENTIFY_INTERVAL);
+        started.elapsed()
+    }
+
+    /// Pushes the bucket's next slot back after the gateway rejected an identify (close 4008).
+    pub async fn penalize(&self, shard_id: u32) {
+        let mut next_allowed = self.buckets[self.bucket_for(shard_id) as usize]
+            .lock()
+            .await;
+        let penalty = Instant::now() + IDENTIFY_INTERVAL;
+        *next_allowed = Some(next_allowed.map_or(penalty, |at| at.max(penalty)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    async fn identify_times(limiter: IdentifyLimiter, shards: u32) -> Vec<Duration> {
+        let limiter = Arc::new(limiter);
+        let start = Instant::now();
+        let tasks: Vec<_> = (0..shards)
+            .map(|shard_id| {
+                let limiter = limiter.clone();
+                tokio::spawn(async move {
+                    limiter.acquire(shard_id).await;
+                    start.elapsed()
+                })
+            })
+            .collect();
+        let mut times = Vec::new();
+        for task in tasks {
+            times.push(task.await.unwrap());
+        }
+        times.sort();
+        times
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn identifies_serially_with_a_single_bucket() {
+        let times = identify_times(IdentifyLimiter::new(1), 3).await;
+        for pair in times.windows(2) {
+            assert!(pair[1] - pair[0] >= IDENTIFY_INTERVAL);
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn identifies_buckets_concurrently() {
+        let times = identify_times(IdentifyLimiter::new(3), 3).await;
+        assert!(times.iter().all(|elapsed| *elapsed < IDENTIFY_INTERVAL));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn penalize_delays_the_next_identify() {
+        let limiter = IdentifyLimiter::new(1);
+        limiter.penalize(0).await;
+        assert!(limiter.acquire(0).await >= IDENTIFY_INTERVAL);
+    }
+}
@@ -1,7 +1,17 @@
+pub mod close_code;
+pub mod compression;
+pub mod error;
 pub mod events;
+pub mod identify;
 pub mod manager;
+pub mod send_queue;
 pub mod shard;
 
+pub use close_code::*;
+pub use compression::*;
+pub use error::*;
 pub use events::*;
+pub use identify::*;
 pub use manager::*;
+pub use send_queue::*;
 pub use shard::*;
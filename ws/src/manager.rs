@@ -1,13 +1,19 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use serde_json::Value;
 use tokio::sync::{RwLock, mpsc};
+use tokio::task::JoinHandle;
 
-use fluxer_types::gateway::{ApiGatewayBotResponse, GatewayPresenceUpdateSendData};
+use fluxer_types::gateway::{ApiGatewayBotResponse, GatewayPresenceUpdateSendData, ShardIdentity};
 
+use crate::compression::CompressionMode;
+use crate::error::GatewayError;
 use crate::events::{ShardEvent, WsEvent};
-use crate::shard::{ShardOptions, WebSocketShard};
+use crate::identify::IdentifyLimiter;
+use crate::send_queue::{DEFAULT_SEND_QUEUE_CAPACITY, SendOverflowPolicy, SendQueue};
+use crate::shard::{DEFAULT_RECONNECT_MAX_DELAY, ShardOptions, WebSocketShard};
 
 #[derive(Debug, Clone)]
 pub struct WebSocketManagerOptions {
@@ -17,6 +23,10 @@ pub struct WebSocketManagerOptions {
     pub shard_ids: Option<Vec<u32>>,
     pub shard_count: Option<u32>,
     pub version: String,
+    pub reconnect_max_delay: Duration,
+    pub send_queue_capacity: usize,
+    pub send_overflow_policy: SendOverflowPolicy,
+    pub compression: CompressionMode,
 }
 
 impl Default for WebSocketManagerOptions {
@@ -28,6 +38,10 @@ impl Default for WebSocketManagerOptions {
             shard_ids: None,
             shard_count: None,
             version: "1".to_string(),
+            reconnect_max_delay: DEFAULT_RECONNECT_MAX_DELAY,
+            send_queue_capacity: DEFAULT_SEND_QUEUE_CAPACITY,
+            send_overflow_policy: SendOverflowPolicy::default(),
+            compression: CompressionMode::default(),
         }
     }
 }
@@ -38,7 +52,11 @@ pub struct WebSocketManager {
     tx: mpsc::UnboundedSender<WsEvent>,
     shard_count: u32,
     gateway_url: Option<String>,
-    shard_senders: Arc<RwLock<HashMap<u32, mpsc::UnboundedSender<Value>>>>,
+    identities: Vec<ShardIdentity>,
+    shard_senders: Arc<RwLock<HashMap<u32, Arc<SendQueue>>>>,
+    shard_latencies: HashMap<u32, Arc<RwLock<Option<Duration>>>>,
+    shard_close_codes: HashMap<u32, Arc<RwLock<Option<u16>>>>,
+    shard_tasks: Vec<JoinHandle<()>>,
 }
 
 impl WebSocketManager {
@@ -53,49 +71,82 @@ impl WebSocketManager {
             tx,
             shard_count: 1,
             gateway_url: None,
+            identities: Vec::new(),
             shard_senders: Arc::new(RwLock::new(HashMap::new())),
+            shard_latencies: HashMap::new(),
+            shard_close_codes: HashMap::new(),
+            shard_tasks: Vec::new(),
         }
     }
 
-    pub async fn connect(&mut self) -> Result<(), fluxer_rest::RestError> {
-        let gateway: ApiGatewayBotResponse = self.rest.get("/gateway/bot").await?;
+    pub fn shard_identities(&self) -> &[ShardIdentity] {
+        &self.identities
+    }
+
+    /// Connects every configured shard; fails before opening any socket if a shard id is out of
+    /// range for the shard count.
+    pub async fn connect(&mut self) -> Result<(), GatewayError> {
+        let gateway: ApiGatewayBotResponse = self.rest.gateway_bot().await?;
 
         self.gateway_url = Some(gateway.url.clone());
-        self.shard_count = self.options.shard_count.unwrap_or(gateway.shards);
+        self.shard_count = self.options.shard_count.unwrap_or(gateway.shards.max(1));
 
         let ids: Vec<u32> = self
             .options
             .shard_ids
             .clone()
             .unwrap_or_else(|| (0..self.shard_count).collect());
+        self.identities = ids
+            .iter()
+            .map(|&id| {
+                ShardIdentity::new(id, self.shard_count).ok_or(GatewayError::InvalidShard {
+                    id,
+                    total: self.shard_count,
+                })
+            })
+            .collect::<Result<_, _>>()?;
 
-        for &shard_id in &ids {
+        let identify_limiter = Arc::new(IdentifyLimiter::new(
+            gateway.session_start_limit.max_concurrency,
+        ));
+
+        for &identity in &self.identities {
+            let shard_id = identity.id();
             let shard_opts = ShardOptions {
                 url: gateway.url.clone(),
                 token: self.options.token.clone(),
                 intents: self.options.intents,
                 presence: self.options.presence.clone(),
-                shard_id,
-                num_shards: self.shard_count,
+                identity,
                 version: self.options.version.clone(),
+                reconnect_max_delay: self.options.reconnect_max_delay,
+                identify_limiter: identify_limiter.clone(),
+                compression: self.options.compression,
             };
 
             let ws_tx = self.tx.clone();
             let (shard_tx, mut shard_rx) = mpsc::unbounded_channel::<ShardEvent>();
-            let (user_tx, user_rx) = mpsc::unbounded_channel::<Value>();
+            let queue = Arc::new(SendQueue::new(
+                self.options.send_queue_capacity,
+                self.options.send_overflow_policy,
+            ));
 
             {
                 let mut senders = self.shard_senders.write().await;
-                senders.insert(shard_id, user_tx);
+                senders.insert(shard_id, queue.clone());
             }
 
-            tokio::spawn(async move {
-                let mut shard = WebSocketShard::new(shard_opts, shard_tx, user_rx);
+            let mut shard = WebSocketShard::new(shard_opts, shard_tx, queue);
+            self.shard_latencies
+                .insert(shard_id, shard.latency_handle());
+            self.shard_close_codes
+                .insert(shard_id, shard.last_close_code_handle());
+            self.shard_tasks.push(tokio::spawn(async move {
                 shard.run().await;
-            });
+            }));
 
             let id = shard_id;
-            tokio::spawn(async move {
+            self.shard_tasks.push(tokio::spawn(async move {
                 while let Some(event) = shard_rx.recv().await {
                     let ws_event = match event {
                         ShardEvent::Ready(data) => WsEvent::ShardReady { shard_id: id, data },
@@ -104,7 +155,20 @@ impl WebSocketManager {
                             shard_id: id,
                             payload,
                         },
-                        ShardEvent::Close(code) => WsEvent::ShardClose { shard_id: id, code },
+                        ShardEvent::Close { code, reason } => WsEvent::ShardClose {
+                            shard_id: id,
+                            code,
+                            reason,
+                        },
+                        ShardEvent::InvalidSession { resumable } => WsEvent::ShardInvalidSession {
+                            shard_id: id,
+                            resumable,
+                        },
+                        ShardEvent::Reconnect => WsEvent::ShardReconnect { shard_id: id },
+                        ShardEvent::IdentifyQueued { waited } => WsEvent::ShardIdentifyQueued {
+                            shard_id: id,
+                            waited,
+                        },
                         ShardEvent::Error(msg) => WsEvent::Error {
                             shard_id: id,
                             error: msg,
@@ -115,26 +179,66 @@ impl WebSocketManager {
                         break;
                     }
                 }
-            });
+            }));
         }
 
         Ok(())
     }
 
+    /// Queues a command for a shard, applying the configured overflow policy when full.
     pub async fn send(&self, shard_id: u32, payload: Value) -> Result<(), String> {
-        let senders = self.shard_senders.read().await;
-        match senders.get(&shard_id) {
-            Some(tx) => tx
-                .send(payload)
-                .map_err(|_| format!("Shard {shard_id} channel closed")),
+        let queue = self.shard_senders.read().await.get(&shard_id).cloned();
+        match queue {
+            Some(queue) => queue
+                .push(payload)
+                .await
+                .map_err(|e| format!("Shard {shard_id}: {e}")),
             None => Err(format!("Shard {shard_id} not found")),
         }
     }
 
     pub async fn broadcast(&self, payload: Value) {
-        let senders = self.shard_senders.read().await;
-        for (_, tx) in senders.iter() {
-            let _ = tx.send(payload.clone());
+        let queues: Vec<Arc<SendQueue>> =
+            self.shard_senders.read().await.values().cloned().collect();
+        for queue in queues {
+            let _ = queue.push(payload.clone()).await;
+        }
+    }
+
+    /// Commands currently waiting in each shard's send queue.
+    pub async fn queued_commands(&self) -> HashMap<u32, usize> {
+        self.shard_senders
+            .read()
+            .await
+            .iter()
+            .map(|(&id, queue)| (id, queue.len()))
+            .collect()
+    }
+
+    /// Heartbeat round-trip of one shard; `None` until it has received its first ACK.
+    pub async fn latency(&self, shard_id: u32) -> Option<Duration> {
+        match self.shard_latencies.get(&shard_id) {
+            Some(latency) => *latency.read().await,
+            None => None,
+        }
+    }
+
+    /// Heartbeat round-trip of every shard that has received an ACK.
+    pub async fn latencies(&self) -> HashMap<u32, Duration> {
+        let mut out = HashMap::new();
+        for (&id, latency) in &self.shard_latencies {
+            if let Some(value) = *latency.read().await {
+                out.insert(id, value);
+            }
+        }
+        out
+    }
+
+    /// Code the shard's socket last closed with; `None` if it hasn't closed.
+    pub async fn last_close_code(&self, shard_id: u32) -> Option<u16> {
+        match self.shard_close_codes.get(&shard_id) {
+            Some(code) => *code.read().await,
+            None => None,
         }
     }
 
@@ -146,7 +250,20 @@ impl WebSocketManager {
         self.gateway_url.as_deref()
     }
 
-    pub fn shard_senders(&self) -> Arc<RwLock<HashMap<u32, mpsc::UnboundedSender<Value>>>> {
+    pub fn shard_senders(&self) -> Arc<RwLock<HashMap<u32, Arc<SendQueue>>>> {
         self.shard_senders.clone()
     }
+
+    /// Stops every shard: their sockets are dropped and none of them reconnects.
+    pub fn shutdown(&mut self) {
+        for task in self.shard_tasks.drain(..) {
+            task.abort();
+        }
+    }
+}
+
+impl Drop for WebSocketManager {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
 }
@@ -0,0 +1,125 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use serde_json::Value;
+use tokio::sync::Notify;
+
+pub const DEFAULT_SEND_QUEUE_CAPACITY: usize = 256;
+
+/// What [`SendQueue::push`] does when the queue is already at capacity.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SendOverflowPolicy {
+    /// Wait until the shard drains a slot.
+    #[default]
+    Block,
+    /// Discard the oldest queued command to make room.
+    DropOldest,
+    /// Reject the new command with [`SendQueueError::Full`].
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum SendQueueError {
+    #[error("send queue is full")]
+    Full,
+    #[error("send queue is closed")]
+    Closed,
+}
+
+/// Bounded outbound command queue between callers and a shard's socket writer.
+#[derive(Debug)]
+pub struct SendQueue {
+    items: Mutex<VecDeque<Value>>,
+    capacity: usize,
+    policy: SendOverflowPolicy,
+    item_ready: Notify,
+    space_ready: Notify,
+    closed: AtomicBool,
+    dropped: AtomicU64,
+}
+
+impl SendQueue {
+    pub fn new(capacity: usize, policy: SendOverflowPolicy) -> Self {
+        Self {
+            items: Mutex::new(VecDeque::new()),
+            capacity: capacity.max(1),
+            policy,
+            item_ready: Notify::new(),
+            space_ready: Notify::new(),
+            closed: AtomicBool::new(false),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    pub async fn push(&self, payload: Value) -> Result<(), SendQueueError> {
+        loop {
+            let space = self.space_ready.notified();
+            {
+                let mut items = self.items.lock().unwrap_or_else(|e| e.into_inner());
+                if self.closed.load(Ordering::Acquire) {
+                    return Err(SendQueueError::Closed);
+                }
+                if items.len() < self.capacity {
+                    items.push_back(payload);
+                    self.item_ready.notify_one();
+                    return Ok(());
+                }
+                match self.policy {
+                    SendOverflowPolicy::DropOldest => {
+                        items.pop_front();
+                        items.push_back(payload);
+                        self.dropped.fetch_add(1, Ordering::Relaxed);
+                        self.item_ready.notify_one();
+                        return Ok(());
+                    }
+                    SendOverflowPolicy::Error => return Err(SendQueueError::Full),
+                    SendOverflowPolicy::Block => {}
+                }
+            }
+            space.await;
+        }
+    }
+
+    /// Waits for the next queued command; returns `None` once the queue is closed and drained.
+    pub async fn pop(&self) -> Option<Value> {
+        loop {
+            let ready = self.item_ready.notified();
+            {
+                let mut items = self.items.lock().unwrap_or_else(|e| e.into_inner());
+                if let Some(payload) = items.pop_front() {
+                    self.space_ready.notify_one();
+                    return Some(payload);
+                }
+                if self.closed.load(Ordering::Acquire) {
+                    return None;
+                }
+            }
+            ready.await;
+        }
+    }
+
+    /// Number of commands waiting to be written.
+    pub fn len(&self) -> usize {
+        self.items.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Commands discarded under [`SendOverflowPolicy::DropOldest`].
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        self.item_ready.notify_waiters();
+        self.space_ready.notify_waiters();
+    }
+}
@@ -1,21 +1,32 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
 use std::time::Duration;
 
 use futures_util::{SinkExt, StreamExt};
 use serde_json::Value;
-use tokio::sync::mpsc;
-use tokio::time::{interval, sleep};
+use tokio::sync::{RwLock, mpsc};
+use tokio::time::{Instant, sleep, sleep_until};
 use tokio_tungstenite::tungstenite::Message as WsMessage;
 use tracing::warn;
 
 use fluxer_types::gateway::{
     GatewayHelloData, GatewayIdentifyData, GatewayIdentifyProperties, GatewayOpcode,
-    GatewayPresenceUpdateSendData, GatewayReceivePayload, GatewayResumeData,
+    GatewayPresenceUpdateSendData, GatewayReceivePayload, GatewayResumeData, ShardIdentity,
 };
 
+use crate::close_code::GatewayCloseCode;
+use crate::compression::{CompressionMode, inflate_payload};
 use crate::events::ShardEvent;
+use crate::identify::IdentifyLimiter;
+use crate::send_queue::SendQueue;
 
 const RECONNECT_INITIAL_MS: u64 = 1_000;
-const RECONNECT_MAX_MS: u64 = 45_000;
+pub const DEFAULT_RECONNECT_MAX_DELAY: Duration = Duration::from_secs(45);
+
+/// Resolves once an op 9 recovery may send its RESUME or IDENTIFY, yielding the time spent
+/// queued on the identify bucket.
+type PendingRecovery = Pin<Box<dyn Future<Output = Duration> + Send>>;
 
 #[derive(Debug, Clone)]
 pub struct ShardOptions {
@@ -23,9 +34,11 @@ pub struct ShardOptions {
     pub token: String,
     pub intents: u64,
     pub presence: Option<GatewayPresenceUpdateSendData>,
-    pub shard_id: u32,
-    pub num_shards: u32,
+    pub identity: ShardIdentity,
     pub version: String,
+    pub reconnect_max_delay: Duration,
+    pub identify_limiter: Arc<IdentifyLimiter>,
+    pub compression: CompressionMode,
 }
 
 pub struct WebSocketShard {
@@ -35,14 +48,17 @@ pub struct WebSocketShard {
     destroying: bool,
     reconnect_delay_ms: u64,
     tx: mpsc::UnboundedSender<ShardEvent>,
-    user_rx: mpsc::UnboundedReceiver<Value>,
+    queue: Arc<SendQueue>,
+    latency: Arc<RwLock<Option<Duration>>>,
+    last_close_code: Arc<RwLock<Option<u16>>>,
+    heartbeat_jitter: fn() -> f64,
 }
 
 impl WebSocketShard {
     pub fn new(
         options: ShardOptions,
         tx: mpsc::UnboundedSender<ShardEvent>,
-        user_rx: mpsc::UnboundedReceiver<Value>,
+        queue: Arc<SendQueue>,
     ) -> Self {
         Self {
             options,
@@ -51,16 +67,57 @@ impl WebSocketShard {
             destroying: false,
             reconnect_delay_ms: RECONNECT_INITIAL_MS,
             tx,
-            user_rx,
+            queue,
+            latency: Arc::new(RwLock::new(None)),
+            last_close_code: Arc::new(RwLock::new(None)),
+            heartbeat_jitter: rand_f64,
         }
     }
 
+    pub fn identity(&self) -> ShardIdentity {
+        self.options.identity
+    }
+
+    /// Round-trip time of the last acknowledged heartbeat; `None` until the first ACK on the
+    /// current connection.
+    pub async fn latency(&self) -> Option<Duration> {
+        *self.latency.read().await
+    }
+
+    /// Shared handle to the latency slot, readable after the shard is moved into its task.
+    pub fn latency_handle(&self) -> Arc<RwLock<Option<Duration>>> {
+        self.latency.clone()
+    }
+
+    /// Code of the most recent close; `None` if the socket hasn't closed yet.
+    pub async fn last_close_code(&self) -> Option<u16> {
+        *self.last_close_code.read().await
+    }
+
+    pub fn last_close_code_handle(&self) -> Arc<RwLock<Option<u16>>> {
+        self.last_close_code.clone()
+    }
+
     pub async fn run(&mut self) {
         loop {
             if self.destroying {
                 return;
             }
 
+            if self.session_id.is_none() {
+                let waited = self
+                    .options
+                    .identify_limiter
+                    .acquire(self.options.identity.id())
+                    .await;
+                if !waited.is_zero() {
+                    self.emit(ShardEvent::IdentifyQueued { waited });
+                }
+                if self.destroying {
+                    return;
+                }
+            }
+
             let url = format!(
                 "{}/?v={}&encoding=json",
                 self.options.url, self.options.version
@@ -68,8 +125,14 @@ impl WebSocketShard {
 
             self.emit(ShardEvent::Debug(format!(
                 "[Shard {}] Connecting to {url}",
-                self.options.shard_id
+                self.options.identity.id()
             )));
+            #[cfg(feature = "tracing")]
+            tracing::info!(
+                shard_id = self.options.identity.id(),
+                status = "connecting",
+                "shard status"
+            );
 
             let ws_stream = match tokio_tungstenite::connect_async_tls_with_config(
                 &url,
@@ -93,15 +156,34 @@ impl WebSocketShard {
             };
 
             self.reconnect_delay_ms = RECONNECT_INITIAL_MS;
+            *self.latency.write().await = None;
             let (mut write, mut read) = ws_stream.split();
 
-            let (hb_tx, mut hb_rx) = mpsc::unbounded_channel::<Value>();
-            let mut _heartbeat_interval_ms = None;
+            let mut heartbeat_interval: Option<Duration> = None;
+            let mut next_heartbeat: Option<Instant> = None;
             let mut last_heartbeat_ack = true;
+            let mut heartbeat_sent_at: Option<Instant> = None;
+            let mut recovery: Option<PendingRecovery> = None;
 
             loop {
                 tokio::select! {
                     msg = read.next() => {
+                        let msg = match msg {
+                            Some(Ok(WsMessage::Binary(frame)))
+                                if self.options.compression == CompressionMode::ZlibPayload =>
+                            {
+                                match inflate_payload(&frame) {
+                                    Ok(text) => Some(Ok(WsMessage::Text(text))),
+                                    Err(e) => {
+                                        self.emit(ShardEvent::Error(format!(
+                                            "Failed to inflate payload: {e}"
+                                        )));
+                                        continue;
+                                    }
+                                }
+                            }
+                            other => other,
+                        };
                         match msg {
                             Some(Ok(WsMessage::Text(text))) => {
                                 match serde_json::from_str::<GatewayReceivePayload>(&text) {
@@ -110,24 +192,33 @@ impl WebSocketShard {
                                             GatewayOpcode::Hello => {
                                                 if let Some(d) = &payload.d
                                                     && let Ok(hello) = serde_json::from_value::<GatewayHelloData>(d.clone()) {
-                                                        _heartbeat_interval_ms = Some(hello.heartbeat_interval);
+                                                        let hb = Duration::from_millis(hello.heartbeat_interval);
+                                                        heartbeat_interval = Some(hb);
+                                                        next_heartbeat = Some(Instant::now() + first_heartbeat_delay(hb, (self.heartbeat_jitter)()));
                                                         last_heartbeat_ack = true;
 
-                                                        let hb_ms = hello.heartbeat_interval;
-                                                        let hb_tx_clone = hb_tx.clone();
-                                                        let seq = self.seq;
-                                                        tokio::spawn(async move {
-                                                            run_heartbeat(hb_ms, hb_tx_clone, seq).await;
-                                                        });
-
                                                         let identify_payload = self.build_identify_or_resume();
                                                         let json = serde_json::to_string(&identify_payload)
                                                             .unwrap_or_default();
                                                         let _ = write.send(WsMessage::Text(json)).await;
                                                     }
                                             }
+                                            GatewayOpcode::Heartbeat => {
+                                                let json = serde_json::to_string(&self.heartbeat_payload())
+                                                    .unwrap_or_default();
+                                                let _ = write.send(WsMessage::Text(json)).await;
+                                                heartbeat_sent_at = Some(Instant::now());
+                                                #[cfg(feature = "tracing")]
+                                                self.trace_heartbeat("sent");
+                                                next_heartbeat = heartbeat_interval.map(|hb| Instant::now() + hb);
+                                            }
                                             GatewayOpcode::HeartbeatAck => {
                                                 last_heartbeat_ack = true;
+                                                if let Some(sent) = heartbeat_sent_at.take() {
+                                                    *self.latency.write().await = Some(sent.elapsed());
+                                                }
+                                                #[cfg(feature = "tracing")]
+                                                self.trace_heartbeat("ack");
                                             }
                                             GatewayOpcode::Dispatch => {
                                                 if let Some(s) = payload.s {
@@ -138,6 +229,18 @@ impl WebSocketShard {
                                                         && let Some(sid) = d.get("session_id").and_then(|v| v.as_str()) {
                                                             self.session_id = Some(sid.to_string());
                                                         }
+                                                    if let Some(shard) = payload
+                                                        .d
+                                                        .as_ref()
+                                                        .and_then(|d| d.get("shard"))
+                                                        .and_then(|s| serde_json::from_value::<ShardIdentity>(s.clone()).ok())
+                                                        && shard != self.options.identity
+                                                    {
+                                                        warn!(
+                                                            "READY confirmed shard {:?}, expected {:?}",
+                                                            shard, self.options.identity
+                                                        );
+                                                    }
                                                     self.reconnect_delay_ms = RECONNECT_INITIAL_MS;
                                                     self.emit(ShardEvent::Ready(
                                                         payload.d.clone().unwrap_or(Value::Null),
@@ -149,20 +252,19 @@ impl WebSocketShard {
                                                 self.emit(ShardEvent::Dispatch(payload));
                                             }
                                             GatewayOpcode::InvalidSession => {
-                                                self.emit(ShardEvent::Debug(format!(
-                                                    "[Shard {}] Invalid session, reconnecting",
-                                                    self.options.shard_id
-                                                )));
-                                                self.session_id = None;
-                                                self.seq = None;
-                                                sleep(Duration::from_millis(1000 + rand_u64(4000))).await;
-                                                break;
+                                                let resumable = payload
+                                                    .d
+                                                    .as_ref()
+                                                    .and_then(Value::as_bool)
+                                                    .unwrap_or(false);
+                                                recovery = Some(self.recover_invalid_session(resumable));
                                             }
                                             GatewayOpcode::Reconnect => {
                                                 self.emit(ShardEvent::Debug(format!(
                                                     "[Shard {}] Reconnect requested",
-                                                    self.options.shard_id
+                                                    self.options.identity.id()
                                                 )));
+                                                self.emit(ShardEvent::Reconnect);
                                                 break;
                                             }
                                             _ => {}
@@ -174,12 +276,25 @@ impl WebSocketShard {
                                 }
                             }
                             Some(Ok(WsMessage::Close(frame))) => {
-                                let code = frame.as_ref().map(|f| f.code.into()).unwrap_or(1006u16);
-                                self.emit(ShardEvent::Close(code));
+                                let (code, reason) = match &frame {
+                                    Some(f) => (f.code.into(), f.reason.to_string()),
+                                    None => (1006u16, String::new()),
+                                };
                                 self.emit(ShardEvent::Debug(format!(
-                                    "[Shard {}] Closed: {code}",
-                                    self.options.shard_id
+                                    "[Shard {}] Closed: {code} {reason}",
+                                    self.options.identity.id()
                                 )));
+                                *self.last_close_code.write().await = Some(code);
+                                self.emit(ShardEvent::Close {
+                                    code,
+                                    reason,
+                                });
+                                if code == GatewayCloseCode::RateLimited.code() {
+                                    self.options
+                                        .identify_limiter
+                                        .penalize(self.options.identity.id())
+                                        .await;
+                                }
                                 if !self.destroying && should_reconnect_on_close(code) {
                                     break;
                                 }
@@ -190,27 +305,45 @@ impl WebSocketShard {
                                 break;
                             }
                             None => {
-                                self.emit(ShardEvent::Close(1006));
+                                *self.last_close_code.write().await = Some(1006);
+                                self.emit(ShardEvent::Close {
+                                    code: 1006,
+                                    reason: String::new(),
+                                });
                                 break;
                             }
                             _ => {}
                         }
                     }
-                    hb = hb_rx.recv() => {
-                        if let Some(payload) = hb {
-                            if !last_heartbeat_ack && self.seq.is_some() {
-                                self.emit(ShardEvent::Debug(format!(
-                                    "[Shard {}] Heartbeat ack missed; reconnecting",
-                                    self.options.shard_id
-                                )));
-                                break;
-                            }
-                            last_heartbeat_ack = false;
-                            let json = serde_json::to_string(&payload).unwrap_or_default();
-                            let _ = write.send(WsMessage::Text(json)).await;
+                    _ = sleep_until(next_heartbeat.unwrap_or_else(Instant::now)), if next_heartbeat.is_some() => {
+                        if !last_heartbeat_ack && self.seq.is_some() {
+                            self.emit(ShardEvent::Debug(format!(
+                                "[Shard {}] Heartbeat ack missed; reconnecting",
+                                self.options.identity.id()
+                            )));
+                            break;
+                        }
+                        last_heartbeat_ack = false;
+                        let json = serde_json::to_string(&self.heartbeat_payload()).unwrap_or_default();
+                        let _ = write.send(WsMessage::Text(json)).await;
+                        heartbeat_sent_at = Some(Instant::now());
+                        #[cfg(feature = "tracing")]
+                        self.trace_heartbeat("sent");
+                        next_heartbeat = heartbeat_interval.map(|hb| Instant::now() + hb);
+                    }
+                    waited = wait_recovery(&mut recovery), if recovery.is_some() => {
+                        recovery = None;
+                        if self.destroying {
+                            return;
+                        }
+                        if !waited.is_zero() {
+                            self.emit(ShardEvent::IdentifyQueued { waited });
                         }
+                        let json = serde_json::to_string(&self.build_identify_or_resume())
+                            .unwrap_or_default();
+                        let _ = write.send(WsMessage::Text(json)).await;
                     }
-                    user_msg = self.user_rx.recv() => {
+                    user_msg = self.queue.pop() => {
                         if let Some(payload) = user_msg {
                             let json = serde_json::to_string(&payload).unwrap_or_default();
                             let _ = write.send(WsMessage::Text(json)).await;
@@ -226,6 +359,41 @@ impl WebSocketShard {
         }
     }
 
+    /// Handles op 9 on the live connection: discards the session unless `resumable`, and returns
+    /// the 1–5s wait (plus the identify bucket when a fresh IDENTIFY is needed) that the
+    /// connection loop polls alongside heartbeats before sending the RESUME or IDENTIFY.
+    fn recover_invalid_session(&mut self, resumable: bool) -> PendingRecovery {
+        self.emit(ShardEvent::Debug(format!(
+            "[Shard {}] Invalid session (resumable: {resumable})",
+            self.options.identity.id()
+        )));
+        if !resumable {
+            self.session_id = None;
+            self.seq = None;
+        }
+        self.emit(ShardEvent::InvalidSession { resumable });
+
+        let delay = Duration::from_millis(1000 + rand_u64(4000));
+        let identify = self.session_id.is_none() || self.seq.is_none();
+        let limiter = self.options.identify_limiter.clone();
+        let shard_id = self.options.identity.id();
+        Box::pin(async move {
+            sleep(delay).await;
+            if identify {
+                limiter.acquire(shard_id).await
+            } else {
+                Duration::ZERO
+            }
+        })
+    }
+
+    fn heartbeat_payload(&self) -> Value {
+        serde_json::json!({
+            "op": GatewayOpcode::Heartbeat as u8,
+            "d": self.seq
+        })
+    }
+
     fn build_identify_or_resume(&self) -> Value {
         if let (Some(session_id), Some(seq)) = (&self.session_id, self.seq) {
             let resume = GatewayResumeData {
@@ -246,9 +414,9 @@ impl WebSocketShard {
                     browser: "fluxer-rust".to_string(),
                     device: "fluxer-rust".to_string(),
                 },
-                compress: None,
+                compress: self.options.compression.identify_compress(),
                 large_threshold: None,
-                shard: Some((self.options.shard_id, self.options.num_shards)),
+                shard: Some(self.options.identity),
                 presence: self.options.presence.clone(),
             };
             serde_json::json!({
@@ -258,70 +426,111 @@ impl WebSocketShard {
         }
     }
 
+    /// Sleeps for a full-jitter delay (uniform in `0..=backoff`) and grows the backoff,
+    /// so shards dropped together don't reconnect in lockstep.
     async fn schedule_reconnect(&mut self) {
-        let jitter = (self.reconnect_delay_ms as f64) * (0.75 + rand_f64() * 0.5);
-        let delay = jitter.min(RECONNECT_MAX_MS as f64) as u64;
-        self.reconnect_delay_ms =
-            (self.reconnect_delay_ms as f64 * 1.5).min(RECONNECT_MAX_MS as f64) as u64;
+        let cap_ms = self.options.reconnect_max_delay.as_millis() as u64;
+        let (delay, next) = reconnect_backoff(self.reconnect_delay_ms, cap_ms);
+        self.reconnect_delay_ms = next;
         self.emit(ShardEvent::Debug(format!(
             "[Shard {}] Reconnecting in {delay}ms…",
-            self.options.shard_id
+            self.options.identity.id()
         )));
         sleep(Duration::from_millis(delay)).await;
     }
 
     fn emit(&self, event: ShardEvent) {
+        #[cfg(feature = "tracing")]
+        self.trace_event(&event);
         let _ = self.tx.send(event);
     }
 
+    #[cfg(feature = "tracing")]
+    fn trace_event(&self, event: &ShardEvent) {
+        let shard_id = self.options.identity.id();
+        match event {
+            ShardEvent::Ready(_) => tracing::info!(shard_id, status = "ready", "shard status"),
+            ShardEvent::Resumed => tracing::info!(shard_id, status = "resumed", "shard status"),
+            ShardEvent::Close { code, reason } => {
+                tracing::info!(
+                    shard_id,
+                    status = "closed",
+                    code,
+                    reason = reason.as_str(),
+                    "shard status"
+                )
+            }
+            ShardEvent::InvalidSession { resumable } => {
+                tracing::info!(
+                    shard_id,
+                    status = "invalid_session",
+                    resumable,
+                    "shard status"
+                )
+            }
+            ShardEvent::Reconnect => {
+                tracing::info!(shard_id, status = "reconnect_requested", "shard status")
+            }
+            ShardEvent::IdentifyQueued { waited } => tracing::debug!(
+                shard_id,
+                status = "identify_queued",
+                waited_ms = waited.as_millis() as u64,
+                "shard status"
+            ),
+            ShardEvent::Error(error) => tracing::warn!(shard_id, %error, "shard error"),
+            ShardEvent::Dispatch(_) | ShardEvent::Debug(_) => {}
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    fn trace_heartbeat(&self, kind: &'static str) {
+        tracing::trace!(
+            shard_id = self.options.identity.id(),
+            kind,
+            seq = self.seq,
+            "heartbeat"
+        );
+    }
+
     pub fn destroy(&mut self) {
         self.destroying = true;
+        self.queue.close();
         self.session_id = None;
         self.seq = None;
     }
 }
 
-async fn run_heartbeat(
-    interval_ms: u64,
-    tx: mpsc::UnboundedSender<Value>,
-    initial_seq: Option<u64>,
-) {
-    let jitter = Duration::from_millis((interval_ms as f64 * rand_f64()) as u64);
-    sleep(jitter).await;
-
-    let mut tick = interval(Duration::from_millis(interval_ms));
-    loop {
-        tick.tick().await;
-        let payload = serde_json::json!({
-            "op": GatewayOpcode::Heartbeat as u8,
-            "d": initial_seq
-        });
-        if tx.send(payload).is_err() {
-            break;
-        }
+async fn wait_recovery(recovery: &mut Option<PendingRecovery>) -> Duration {
+    match recovery {
+        Some(pending) => pending.await,
+        None => std::future::pending().await,
     }
 }
 
 fn should_reconnect_on_close(code: u16) -> bool {
-    matches!(
-        code,
-        1000 | 1001
-            | 1005
-            | 1006
-            | 1011
-            | 1012
-            | 1013
-            | 1014
-            | 1015
-            | 4000
-            | 4007
-            | 4009
-            | 4010
-            | 4011
-            | 4012
-    )
+    match GatewayCloseCode::from_code(code) {
+        Some(code) => !code.is_fatal(),
+        None => matches!(
+            code,
+            1000 | 1001 | 1005 | 1006 | 1011 | 1012 | 1013 | 1014 | 1015
+        ),
+    }
+}
+
+/// Full-jitter reconnect delay for the current backoff, and the backoff to use next.
+fn reconnect_backoff(current_ms: u64, cap_ms: u64) -> (u64, u64) {
+    let backoff = current_ms.min(cap_ms);
+    let next = ((backoff as f64 * 1.5) as u64).min(cap_ms);
+    (rand_u64(backoff), next)
+}
+
+/// Delay before the first heartbeat after HELLO: `interval * jitter` with jitter in `[0, 1)`, as
+/// the gateway expects. Later beats follow the fixed interval.
+fn first_heartbeat_delay(interval: Duration, jitter: f64) -> Duration {
+    interval.mul_f64(jitter)
 }
 
+/// Pseudo-random value in `[0, 1)`.
 fn rand_f64() -> f64 {
     use std::time::SystemTime;
     let nanos = SystemTime::now()
@@ -334,3 +543,272 @@ fn rand_f64() -> f64 {
 fn rand_u64(max: u64) -> u64 {
     (rand_f64() * max as f64) as u64
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio::task::JoinHandle;
+    use tokio::time::timeout;
+    use tokio_tungstenite::WebSocketStream;
+
+    use super::*;
+    use crate::send_queue::SendOverflowPolicy;
+
+    type ServerSocket = WebSocketStream<TcpStream>;
+
+    /// Runs a shard against a local socket, returning the listener it will connect to.
+    async fn spawn_shard() -> (
+        TcpListener,
+        JoinHandle<()>,
+        mpsc::UnboundedReceiver<ShardEvent>,
+    ) {
+        spawn_shard_with_jitter(rand_f64).await
+    }
+
+    /// Like [`spawn_shard`], but with a fixed first-heartbeat jitter.
+    async fn spawn_shard_with_jitter(
+        jitter: fn() -> f64,
+    ) -> (
+        TcpListener,
+        JoinHandle<()>,
+        mpsc::UnboundedReceiver<ShardEvent>,
+    ) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let options = ShardOptions {
+            url: format!("ws://{}", listener.local_addr().unwrap()),
+            token: "token".to_string(),
+            intents: 0,
+            presence: None,
+            identity: ShardIdentity::new(0, 1).unwrap(),
+            version: "1".to_string(),
+            reconnect_max_delay: DEFAULT_RECONNECT_MAX_DELAY,
+            identify_limiter: Arc::new(IdentifyLimiter::new(1)),
+            compression: CompressionMode::None,
+        };
+        let (tx, rx) = mpsc::unbounded_channel();
+        let queue = Arc::new(SendQueue::new(16, SendOverflowPolicy::default()));
+        let mut shard = WebSocketShard::new(options, tx, queue);
+        shard.heartbeat_jitter = jitter;
+        let task = tokio::spawn(async move { shard.run().await });
+        (listener, task, rx)
+    }
+
+    async fn accept(listener: &TcpListener) -> ServerSocket {
+        let (stream, _) = timeout(Duration::from_secs(5), listener.accept())
+            .await
+            .unwrap()
+            .unwrap();
+        tokio_tungstenite::accept_async(stream).await.unwrap()
+    }
+
+    async fn send(socket: &mut ServerSocket, payload: Value) {
+        socket
+            .send(WsMessage::Text(payload.to_string()))
+            .await
+            .unwrap();
+    }
+
+    async fn recv(socket: &mut ServerSocket) -> Value {
+        recv_within(socket, Duration::from_secs(5)).await
+    }
+
+    async fn recv_within(socket: &mut ServerSocket, limit: Duration) -> Value {
+        loop {
+            let msg = timeout(limit, socket.next())
+                .await
+                .expect("shard sent nothing")
+                .unwrap()
+                .unwrap();
+            if let WsMessage::Text(text) = msg {
+                return serde_json::from_str(&text).unwrap();
+            }
+        }
+    }
+
+    /// Sends HELLO with an interval long enough that no scheduled heartbeat fires mid-test, and
+    /// returns the shard's IDENTIFY or RESUME.
+    async fn hello(socket: &mut ServerSocket) -> Value {
+        send(
+            socket,
+            json!({ "op": 10, "d": { "heartbeat_interval": u32::MAX } }),
+        )
+        .await;
+        recv(socket).await
+    }
+
+    #[tokio::test]
+    async fn answers_a_heartbeat_request_with_the_current_sequence() {
+        let (listener, task, _rx) = spawn_shard().await;
+        let mut socket = accept(&listener).await;
+        assert_eq!(hello(&mut socket).await["op"], 2);
+
+        send(
+            &mut socket,
+            json!({ "op": 0, "s": 7, "t": "TYPING_START", "d": {} }),
+        )
+        .await;
+        send(&mut socket, json!({ "op": 1, "d": null })).await;
+
+        assert_eq!(recv(&mut socket).await, json!({ "op": 1, "d": 7 }));
+        task.abort();
+    }
+
+    #[tokio::test]
+    async fn answers_a_heartbeat_request_before_any_dispatch() {
+        let (listener, task, _rx) = spawn_shard().await;
+        let mut socket = accept(&listener).await;
+        hello(&mut socket).await;
+
+        send(&mut socket, json!({ "op": 1, "d": null })).await;
+
+        assert_eq!(recv(&mut socket).await, json!({ "op": 1, "d": null }));
+        task.abort();
+    }
+
+    #[test]
+    fn reconnect_delay_never_exceeds_the_cap() {
+        let cap_ms = 45_000;
+        let mut current = RECONNECT_INITIAL_MS;
+        for _ in 0..32 {
+            let (delay, next) = reconnect_backoff(current, cap_ms);
+            assert!(delay < current.min(cap_ms).max(1));
+            assert!(next <= cap_ms);
+            current = next;
+        }
+        assert_eq!(current, cap_ms);
+    }
+
+    #[test]
+    fn reconnect_delay_is_jittered() {
+        let delays: std::collections::HashSet<u64> = (0..16)
+            .map(|_| {
+                std::thread::sleep(Duration::from_millis(1));
+                reconnect_backoff(45_000, 45_000).0
+            })
+            .collect();
+        assert!(delays.len() > 1);
+    }
+
+    /// Sends READY (session `abc`) followed by op 9, returning the shard's next payload. The
+    /// shard waits 1–5s, plus the identify bucket when it must start a new session.
+    async fn invalidate_session(socket: &mut ServerSocket, resumable: bool) -> Value {
+        hello(socket).await;
+        send(
+            socket,
+            json!({ "op": 0, "s": 3, "t": "READY", "d": { "session_id": "abc" } }),
+        )
+        .await;
+        send(socket, json!({ "op": 9, "d": resumable })).await;
+        recv_within(socket, Duration::from_secs(15)).await
+    }
+
+    async fn saw_invalid_session(rx: &mut mpsc::UnboundedReceiver<ShardEvent>, expected: bool) {
+        while let Ok(event) = rx.try_recv() {
+            if let ShardEvent::InvalidSession { resumable } = event {
+                assert_eq!(resumable, expected);
+                return;
+            }
+        }
+        panic!("no InvalidSession event");
+    }
+
+    #[tokio::test]
+    async fn resumes_after_a_resumable_invalid_session() {
+        let (listener, task, mut rx) = spawn_shard().await;
+        let mut socket = accept(&listener).await;
+
+        let payload = invalidate_session(&mut socket, true).await;
+
+        assert_eq!(payload["op"], 6);
+        assert_eq!(payload["d"]["session_id"], "abc");
+        assert_eq!(payload["d"]["seq"], 3);
+        saw_invalid_session(&mut rx, true).await;
+        task.abort();
+    }
+
+    #[tokio::test]
+    async fn identifies_after_a_non_resumable_invalid_session() {
+        let (listener, task, mut rx) = spawn_shard().await;
+        let mut socket = accept(&listener).await;
+
+        let payload = invalidate_session(&mut socket, false).await;
+
+        assert_eq!(payload["op"], 2);
+        assert_eq!(payload["d"]["token"], "token");
+        saw_invalid_session(&mut rx, false).await;
+        task.abort();
+    }
+
+    #[test]
+    fn first_heartbeat_is_within_the_interval() {
+        let interval = Duration::from_millis(41_250);
+        for _ in 0..64 {
+            assert!(first_heartbeat_delay(interval, rand_f64()) < interval);
+        }
+        assert_eq!(
+            first_heartbeat_delay(Duration::ZERO, rand_f64()),
+            Duration::ZERO
+        );
+    }
+
+    #[tokio::test]
+    async fn surfaces_the_close_code_and_reason() {
+        use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+        use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+
+        let (listener, task, mut rx) = spawn_shard().await;
+        let mut socket = accept(&listener).await;
+        hello(&mut socket).await;
+
+        socket
+            .close(Some(CloseFrame {
+                code: CloseCode::from(4002),
+                reason: "Invalid payload".into(),
+            }))
+            .await
+            .unwrap();
+
+        let (code, reason) = timeout(Duration::from_secs(5), async {
+            loop {
+                if let Some(ShardEvent::Close { code, reason }) = rx.recv().await {
+                    return (code, reason);
+                }
+            }
+        })
+        .await
+        .expect("no Close event");
+        assert_eq!(code, 4002);
+        assert_eq!(reason, "Invalid payload");
+        task.abort();
+    }
+
+    #[tokio::test]
+    async fn a_heartbeat_request_restarts_the_heartbeat_timer() {
+        // The first scheduled beat lands halfway through the interval, so the beat answering
+        // op 1 always comes first and a timer that isn't restarted would fire well before
+        // a full interval has passed.
+        let interval = Duration::from_millis(2_000);
+        let (listener, task, _rx) = spawn_shard_with_jitter(|| 0.5).await;
+        let mut socket = accept(&listener).await;
+        send(
+            &mut socket,
+            json!({ "op": 10, "d": { "heartbeat_interval": interval.as_millis() as u64 } }),
+        )
+        .await;
+        assert_eq!(recv(&mut socket).await["op"], 2);
+
+        send(
+            &mut socket,
+            json!({ "op": 0, "s": 7, "t": "TYPING_START", "d": {} }),
+        )
+        .await;
+        send(&mut socket, json!({ "op": 1, "d": null })).await;
+        assert_eq!(recv(&mut socket).await, json!({ "op": 1, "d": 7 }));
+        let answered = Instant::now();
+
+        assert_eq!(recv(&mut socket).await, json!({ "op": 1, "d": 7 }));
+        assert!(answered.elapsed() >= interval.mul_f64(0.9));
+        task.abort();
+    }
+}